@@ -2,11 +2,41 @@
 //! documentation of `parser::parse::take_link()` to see a list of supported markup languages. The
 //! iterator resolves link references.
 
+use crate::parser;
+use crate::parser::asciidoc::adoc_xref_destination;
+use crate::parser::html::html_anchor_target;
+use crate::parser::html::html_comment;
+use crate::parser::parse::detect_format;
+use crate::parser::parse::insert_definition;
+use crate::parser::parse::normalize_label;
 use crate::parser::parse::take_link;
+use crate::parser::parse::take_link_config;
+use crate::parser::parse::take_link_kind;
+use crate::parser::parse::Format;
+use crate::parser::parse::ParserConfig;
+use crate::parser::restructured_text::rst_explicit_markup;
+use crate::parser::restructured_text::rst_inline_target;
 use crate::parser::Link;
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::io;
+use std::io::Read;
+use std::iter::FusedIterator;
 use std::mem::swap;
+use std::ops::Range;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Number of bytes `HyperlinkReader` reads at once, also the minimum block
+/// length of `par_hyperlinks()`.
+const READ_CHUNK_LEN: usize = 0x10000;
+
+/// Maximum number of bytes `LinkScanner` keeps in memory while waiting for a
+/// blank line.
+const MAX_BLOCK_LEN: usize = 0x100000;
 
 #[derive(Debug, PartialEq)]
 /// A collection of `Link` objects grouped by link type.
@@ -35,9 +65,10 @@ impl<'a> HyperlinkCollection<'a> {
     /// stores them in `Self::HyperlinkCollection` according to their category.
     /// One type is treated specially: `Link::TextLabel2Dest` are cloned and one
     /// copy is stored in `HyperlinkCollection::Text2Dest` and the other copy is
-    /// stored in `HyperlinkCollection::Label2Dest`.
+    /// stored in `HyperlinkCollection::Label2Dest`. Links are searched as
+    /// configured by `config`.
     #[inline]
-    fn from(input: &'a str, render_label2dest: bool) -> Self {
+    fn from(input: &'a str, render_label2dest: bool, config: &ParserConfig) -> Self {
         let mut i = input;
         let mut hc = HyperlinkCollection::new();
         let mut anonymous_text2label_counter = 0;
@@ -45,7 +76,7 @@ impl<'a> HyperlinkCollection<'a> {
         // This index refers to `input`.
         let mut input_idx = 0;
 
-        while let Ok((j, (skipped, res))) = take_link(i) {
+        while let Ok((j, (skipped, res))) = take_link_config(i, config) {
             match res {
                 // `Text2Dest` is stored without modification in `hc.text2dest_label`.
                 l if matches!(l, Link::Text2Dest { .. }) => {
@@ -76,8 +107,7 @@ impl<'a> HyperlinkCollection<'a> {
                         Link::Text2Dest(tl.clone(), d.clone(), t.clone()),
                     ));
 
-                    // Silently ignore when overwriting a key that exists already.
-                    hc.label2dest.insert(tl, (d, t));
+                    insert_definition(&mut hc.label2dest, tl, (d, t));
                 }
 
                 // `Label2Label` are unpacked and stored in `hc.label2label`.
@@ -86,7 +116,8 @@ impl<'a> HyperlinkCollection<'a> {
                         anonymous_label2x_counter += 1;
                         from = Cow::Owned(format!("_{}", anonymous_label2x_counter));
                     }
-                    hc.label2label.push((from, to));
+                    hc.label2label
+                        .push((normalize_label(from), normalize_label(to)));
                 }
 
                 // `Label2Dest` are unpacked and stored as `HashMap` in `hc.label2dest`:
@@ -112,8 +143,21 @@ impl<'a> HyperlinkCollection<'a> {
                         ));
                     };
 
-                    // Silently ignore when overwriting a key that exists already.
-                    hc.label2dest.insert(l, (d, t));
+                    insert_definition(&mut hc.label2dest, l, (d, t));
+                }
+
+                // `Text2Xref` are stored as `Text2Dest` in `hc.text2dest_label`.
+                Link::Text2Xref(mut text, target) => {
+                    let link_offset = input_idx + skipped.len();
+                    let link_len = i.len() - j.len() - skipped.len();
+                    if text.is_empty() {
+                        text = target.clone();
+                    }
+                    hc.text2dest_label.push((
+                        link_offset,
+                        link_len,
+                        Link::Text2Dest(text, adoc_xref_destination(target), Cow::from("")),
+                    ));
                 }
                 _ => unreachable!(),
             };
@@ -173,7 +217,10 @@ impl<'a> HyperlinkCollection<'a> {
         while idx < self.text2dest_label.len() {
             // If we can not resolve the label, we just skip it.
             if let (input_offset, len, Link::Text2Label(text, label)) = &self.text2dest_label[idx] {
-                if let Some((dest, title)) = &self.label2dest.get(&*label) {
+                if let Some((dest, title)) = &self
+                    .label2dest
+                    .get(&normalize_label(Cow::Borrowed(label.as_ref())))
+                {
                     let new_link = if text == "" {
                         (
                             *input_offset,
@@ -196,7 +243,7 @@ impl<'a> HyperlinkCollection<'a> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 /// The interator's state.
 enum Status<'a> {
     /// Initial state. Iterator is not started.
@@ -213,7 +260,7 @@ enum Status<'a> {
     End,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 /// Iterator over all the hyperlinks in the `input` text.
 /// This struct holds the iterator's state and an advancing pointer into the `input` text.
 /// The iterator's `next()` method returns a tuple with 2 tuples inside:
@@ -336,6 +383,11 @@ pub struct Hyperlink<'a> {
     /// link: with the full link reference definition's source as _link text_ and
     /// the definition's destination as _link destination_.
     render_label: bool,
+    /// Configures the search for links.
+    config: ParserConfig,
+    /// When set, only links with a _link destination_ starting with one of
+    /// these URI schemes are returned.
+    schemes: Option<Vec<String>>,
 }
 
 /// Constructor for the `Hyperlink` struct.
@@ -381,16 +433,635 @@ impl<'a> Hyperlink<'a> {
     /// assert_eq!(iter.next(), None);
     /// ```
     ///
+    /// # Wikilinks
+    ///
+    /// Wikilinks `[[page]]` are Markdown _shortcut reference links_ as well.
+    /// Unless a _link reference definition_ matches, they are dropped like
+    /// other unresolved references. They are found as wikilinks only with
+    /// `Format::Wikitext`, see `Hyperlink::with_format()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use parse_hyperlinks::parser::parse::Format;
+    /// use std::borrow::Cow;
+    ///
+    /// let i = "abc [[Page|label]] abc [[Other]] abc";
+    ///
+    /// assert_eq!(Hyperlink::new(i, false).next(), None);
+    /// let mut iter = Hyperlink::with_format(i, false, Format::Wikitext);
+    /// assert_eq!(iter.next().unwrap().1, (Cow::from("label"), Cow::from("Page"), Cow::from("")));
+    /// assert_eq!(iter.next().unwrap().1, (Cow::from("Other"), Cow::from("Other"), Cow::from("")));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    ///
     #[inline]
     pub fn new(input: &'a str, render_label: bool) -> Self {
+        Self::with_format(input, render_label, Format::Unknown)
+    }
+
+    /// Constructor for an iterator searching only for links of the markup
+    /// language `format`, see `parser::parse::take_link_format()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use parse_hyperlinks::parser::parse::Format;
+    /// use std::borrow::Cow;
+    ///
+    /// let i = "abc `text1`_ abc [text2](dest2) abc\n\n.. _text1: dest1\n";
+    ///
+    /// let mut iter = Hyperlink::with_format(i, false, Format::ReStructuredText);
+    /// assert_eq!(iter.next().unwrap().1, (Cow::from("text1"), Cow::from("dest1"), Cow::from("")));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn with_format(input: &'a str, render_label: bool, format: Format) -> Self {
+        Self::with_config(input, render_label, ParserConfig::new().format(format))
+    }
+
+    /// Constructor for an iterator searching for links as configured by
+    /// `config`, see `parser::parse::ParserConfig`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use parse_hyperlinks::parser::html::HtmlMode;
+    /// use parse_hyperlinks::parser::parse::{Format, ParserConfig};
+    /// use std::borrow::Cow;
+    ///
+    /// let i = r#"abc <a href="dest1"/>text1</a> abc [text2](dest2) abc"#;
+    ///
+    /// let config = ParserConfig::new()
+    ///     .enable(Format::Markdown, false)
+    ///     .html_mode(HtmlMode::Lenient);
+    /// let mut iter = Hyperlink::with_config(i, false, config);
+    /// assert_eq!(iter.next().unwrap().1, (Cow::from("text1"), Cow::from("dest1"), Cow::from("")));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn with_config(input: &'a str, render_label: bool, config: ParserConfig) -> Self {
         Self {
             input,
             status: Status::Init,
             last_output_offset: 0,
             last_output_len: 0,
             render_label,
+            config,
+            schemes: None,
+        }
+    }
+
+    /// Returns only links with a _link destination_ starting with one of
+    /// the URI `schemes`, e.g. `https`. The schemes are compared
+    /// case-insensitively. Relative URLs have no scheme and are skipped.
+    /// The `skipped_characters` of the next returned link include the
+    /// skipped links.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use std::borrow::Cow;
+    ///
+    /// let i = "[text1](dest1) [text2](HTTPS://dest2) [text3](ftp://dest3)";
+    ///
+    /// let mut iter = Hyperlink::new(i, false).schemes(&["http", "https", "mailto"]);
+    /// let link = iter.next().unwrap();
+    /// assert_eq!(link.0 .0, "[text1](dest1) ");
+    /// assert_eq!(link.1 .1, Cow::from("HTTPS://dest2"));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn schemes(mut self, schemes: &[&str]) -> Self {
+        self.schemes = Some(schemes.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// True, if the _link destination_ `dest` passes the `schemes` filter.
+    fn accepts(&self, dest: &str) -> bool {
+        match &self.schemes {
+            None => true,
+            Some(schemes) => uri_scheme(dest)
+                .is_some_and(|scheme| schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme))),
         }
     }
+
+    /// Like `parser::parse::take_link_format()`, but skips `Link::Text2Dest`
+    /// links not accepted by `accepts()`. They are part of the skipped input.
+    fn take_accepted_link(&self, input: &'a str) -> nom::IResult<&'a str, (&'a str, Link<'a>)> {
+        let mut i = input;
+        loop {
+            let (j, (skipped, link)) = take_link_config(i, &self.config)?;
+            match link {
+                Link::Text2Dest(_, ref de, _) if !self.accepts(de) => i = j,
+                link => {
+                    let skipped = &input[..input.len() - i.len() + skipped.len()];
+                    return Ok((j, (skipped, link)));
+                }
+            }
+        }
+    }
+
+    /// Constructor for an iterator searching only for links of the markup
+    /// language guessed by `parser::parse::detect_format()`. When the
+    /// format can not be guessed, links of all markup languages are
+    /// searched, as with `Hyperlink::new()`.
+    #[inline]
+    pub fn with_detected_format(input: &'a str, render_label: bool) -> Self {
+        Self::with_format(input, render_label, detect_format(input))
+    }
+
+    /// Prepends to every item the position `(line, column)` of the link's
+    /// first character in the `input`, e.g. for diagnostics in the
+    /// `file:line:column` format. Lines and columns are counted from 1,
+    /// columns in Unicode scalar values (`char`), see `line_column()`.
+    /// Must be called before the first call to `next()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    ///
+    /// let i = "abc\näbc[text1](dest1)\n\n  <a href=\"dest2\">text2</a>";
+    ///
+    /// let mut iter = Hyperlink::new(i, false).positions();
+    /// assert_eq!(iter.next().unwrap().0, (2, 4));
+    /// assert_eq!(iter.next().unwrap().0, (4, 3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn positions(
+        self,
+    ) -> impl Iterator<Item = ((usize, usize), <Self as Iterator>::Item)> + 'a {
+        let input = self.input;
+        // The last link's byte offset, its line and the offset of this line.
+        let mut last = (0, 1, 0);
+        self.map(move |item| {
+            let offset = input.len() - item.0 .1.len() - item.0 .2.len();
+            if offset < last.0 {
+                last = (0, 1, 0);
+            }
+            let (last_offset, mut line, mut line_offset) = last;
+            let skipped = &input[last_offset..offset];
+            line += skipped.matches('\n').count();
+            if let Some(idx) = skipped.rfind('\n') {
+                line_offset = last_offset + idx + 1;
+            }
+            last = (offset, line, line_offset);
+            let column = input[line_offset..offset].chars().count() + 1;
+            ((line, column), item)
+        })
+    }
+
+    /// Prepends to every item up to `chars` Unicode scalar values (`char`)
+    /// of the `input` directly before and after the link, e.g. to show a
+    /// broken link in its context. Must be called before the first call to
+    /// `next()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    ///
+    /// let i = "See the [manual](dest1) for dätails.";
+    ///
+    /// let mut iter = Hyperlink::new(i, false).context(5);
+    /// assert_eq!(iter.next().unwrap().0, (" the ", " for "));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn context(
+        self,
+        chars: usize,
+    ) -> impl Iterator<Item = ((&'a str, &'a str), <Self as Iterator>::Item)> + 'a {
+        let input = self.input;
+        self.map(move |item| {
+            let end = input.len() - item.0 .2.len();
+            let start = end - item.0 .1.len();
+            let before_start = input[..start]
+                .char_indices()
+                .rev()
+                .take(chars)
+                .last()
+                .map_or(start, |(idx, _)| idx);
+            let after_end = input[end..]
+                .char_indices()
+                .nth(chars)
+                .map_or(input.len(), |(idx, _)| end + idx);
+            ((&input[before_start..start], &input[end..after_end]), item)
+        })
+    }
+
+    /// Prepends to every item the line of the `input` containing the link,
+    /// without line ending, e.g. to show a broken link in its context. When
+    /// the link spans several lines, all of them are returned. Must be
+    /// called before the first call to `next()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    ///
+    /// let i = "abc\r\nSee the [manual](dest1) for details.\r\nabc";
+    ///
+    /// let mut iter = Hyperlink::new(i, false).line_context();
+    /// assert_eq!(iter.next().unwrap().0, "See the [manual](dest1) for details.");
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn line_context(self) -> impl Iterator<Item = (&'a str, <Self as Iterator>::Item)> + 'a {
+        let input = self.input;
+        self.map(move |item| {
+            let end = input.len() - item.0 .2.len();
+            let start = end - item.0 .1.len();
+            let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+            let line_end = input[end..].find('\n').map_or(input.len(), |idx| end + idx);
+            let line = &input[line_start..line_end];
+            (line.strip_suffix('\r').unwrap_or(line), item)
+        })
+    }
+
+    /// Skips links, whose _link destination_ was returned before, e.g. to
+    /// check every URL only once. Of links with the same destination, the
+    /// first one is returned.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use std::borrow::Cow;
+    ///
+    /// let i = "[text1](dest1) [text2](dest2) [text3](dest1)";
+    ///
+    /// let mut iter = Hyperlink::new(i, false).unique_by_destination();
+    /// assert_eq!(iter.next().unwrap().1 .0, Cow::from("text1"));
+    /// assert_eq!(iter.next().unwrap().1 .0, Cow::from("text2"));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn unique_by_destination(self) -> impl Iterator<Item = <Self as Iterator>::Item> + 'a {
+        let mut seen = HashSet::new();
+        self.filter(move |item| seen.insert(item.1 .1.clone()))
+    }
+}
+
+/// Sorts the items of a `Hyperlink` iterator by the position of the link in
+/// the input. All items must stem from the same input.
+///
+/// ```
+/// use parse_hyperlinks::iterator::{sort_by_position, Hyperlink};
+/// use std::borrow::Cow;
+///
+/// let i = "[text1](dest2) [text2](dest1)";
+///
+/// let mut links: Vec<_> = Hyperlink::new(i, false).collect();
+/// links.reverse();
+/// sort_by_position(&mut links);
+/// assert_eq!(links[0].1 .0, Cow::from("text1"));
+/// assert_eq!(links[1].1 .0, Cow::from("text2"));
+/// ```
+pub fn sort_by_position(links: &mut [<Hyperlink<'_> as Iterator>::Item]) {
+    // The more remaining input, the earlier the link.
+    links.sort_by_key(|item| std::cmp::Reverse(item.0 .2.len()));
+}
+
+/// Sorts the items of a `Hyperlink` iterator by _link destination_. Links
+/// with the same destination keep their order.
+///
+/// ```
+/// use parse_hyperlinks::iterator::{sort_by_destination, Hyperlink};
+/// use std::borrow::Cow;
+///
+/// let i = "[text1](dest2) [text2](dest1) [text3](dest2)";
+///
+/// let mut links: Vec<_> = Hyperlink::new(i, false).collect();
+/// sort_by_destination(&mut links);
+/// assert_eq!(links[0].1 .0, Cow::from("text2"));
+/// assert_eq!(links[1].1 .0, Cow::from("text1"));
+/// assert_eq!(links[2].1 .0, Cow::from("text3"));
+/// ```
+pub fn sort_by_destination(links: &mut [<Hyperlink<'_> as Iterator>::Item]) {
+    links.sort_by(|a, b| a.1 .1.cmp(&b.1 .1));
+}
+
+impl Hyperlink<'static> {
+    /// Constructor for an iterator over the _inline links_ of markup language
+    /// `format` read from `reader`, see `HyperlinkReader`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::iterator::Hyperlink;
+    /// use parse_hyperlinks::parser::parse::Format;
+    /// use std::borrow::Cow;
+    ///
+    /// let i = "abc [text1](dest1) abc\n\nabc <a href=\"dest2\">text2</a>\n";
+    ///
+    /// let mut iter = Hyperlink::from_reader(i.as_bytes(), Format::Unknown);
+    /// assert_eq!(
+    ///     iter.next().unwrap().unwrap(),
+    ///     (Cow::from("text1"), Cow::from("dest1"), Cow::from(""))
+    /// );
+    /// assert_eq!(
+    ///     iter.next().unwrap().unwrap(),
+    ///     (Cow::from("text2"), Cow::from("dest2"), Cow::from(""))
+    /// );
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn from_reader<R: Read>(reader: R, format: Format) -> HyperlinkReader<R> {
+        HyperlinkReader {
+            reader,
+            buf: Vec::new(),
+            scanner: LinkScanner::new(format),
+            links: VecDeque::new(),
+            eof: false,
+        }
+    }
+}
+
+/// Incremental search for the _inline links_ of a text, that is not
+/// available at once, e.g. a network stream. The text is fed piece by
+/// piece with `push()`, `finish()` marks its end.
+///
+/// The text is searched in blocks ending with a blank line, so that links
+/// split between two pieces are found completely. Links spanning a blank
+/// line are not recognized. When no blank line comes for `MAX_BLOCK_LEN`
+/// bytes, the block is cut after the last line end, or whitespace, of its
+/// second half. A link spanning such a cut is not recognized either.
+///
+/// Unlike `Hyperlink`, the scanner does not resolve _reference links_, as
+/// their _link reference definition_ may come later in the text. Only
+/// _inline links_ are returned, as owned `parser::ParsedLink`.
+///
+/// ```
+/// use parse_hyperlinks::iterator::LinkScanner;
+/// use parse_hyperlinks::parser::parse::Format;
+///
+/// let mut scanner = LinkScanner::new(Format::Unknown);
+/// assert_eq!(scanner.push("abc [text1](de").count(), 0);
+/// let links: Vec<_> = scanner.push("st1) abc\n\nabc <a href=\"dest2\">te").collect();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].name, "text1");
+/// assert_eq!(links[0].destination, "dest1");
+/// assert_eq!(links[0].source, "[text1](dest1)");
+/// assert_eq!(scanner.push("xt2</a>").count(), 0);
+/// let links: Vec<_> = scanner.finish().collect();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].name, "text2");
+/// assert_eq!(links[0].destination, "dest2");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LinkScanner {
+    /// Text pushed, but not searched yet.
+    buf: String,
+    /// Length of the beginning of `buf` known to contain no blank line.
+    searched: usize,
+    /// Only links of this markup language are searched.
+    format: Format,
+}
+
+impl LinkScanner {
+    /// Constructor for a scanner searching only for links of the markup
+    /// language `format`, see `parser::parse::take_link_format()`.
+    pub fn new(format: Format) -> Self {
+        Self {
+            buf: String::new(),
+            searched: 0,
+            format,
+        }
+    }
+
+    /// Appends `input` to the text and returns the links of all blocks
+    /// completed by it.
+    pub fn push(&mut self, input: &str) -> impl Iterator<Item = parser::ParsedLink<'static>> {
+        self.buf.push_str(input);
+        // Only the new text is searched, together with the last two bytes
+        // before it, which may begin a blank line.
+        let mut start = self.searched.saturating_sub(2);
+        while !self.buf.is_char_boundary(start) {
+            start -= 1;
+        }
+        let tail = &self.buf[start..];
+        let mut block_len = match (tail.rfind("\n\n"), tail.rfind("\n\r\n")) {
+            (None, None) => 0,
+            (Some(lf), None) => start + lf + 2,
+            (None, Some(crlf)) => start + crlf + 3,
+            (Some(lf), Some(crlf)) => start + (lf + 2).max(crlf + 3),
+        };
+        if block_len == 0 && self.buf.len() > MAX_BLOCK_LEN {
+            // Keep at most `MAX_BLOCK_LEN / 2` bytes.
+            let mut min = self.buf.len() - MAX_BLOCK_LEN / 2;
+            while !self.buf.is_char_boundary(min) {
+                min += 1;
+            }
+            let tail = &self.buf[min..];
+            block_len = min
+                + tail
+                    .rfind('\n')
+                    .or_else(|| tail.rfind(char::is_whitespace))
+                    .map_or(0, |idx| {
+                        idx + tail[idx..].chars().next().unwrap().len_utf8()
+                    });
+        }
+        let links = self.drain(block_len);
+        self.searched = self.buf.len();
+        links
+    }
+
+    /// Marks the end of the text and returns the links of the last block.
+    /// Afterwards, the scanner can be reused for another text.
+    pub fn finish(&mut self) -> impl Iterator<Item = parser::ParsedLink<'static>> {
+        self.searched = 0;
+        self.drain(self.buf.len())
+    }
+
+    /// Removes the first `block_len` bytes from `buf` and returns the
+    /// links found in them.
+    fn drain(&mut self, block_len: usize) -> std::vec::IntoIter<parser::ParsedLink<'static>> {
+        let links: Vec<_> = search_block(&self.buf[..block_len], self.format)
+            .into_iter()
+            .map(parser::ParsedLink::into_owned)
+            .collect();
+        self.buf.drain(..block_len);
+        links.into_iter()
+    }
+}
+
+/// Returns the _inline links_ of markup language `format` in `block`.
+fn search_block(block: &str, format: Format) -> Vec<parser::ParsedLink<'_>> {
+    let mut links = Vec::new();
+    let mut i = block;
+    let config = ParserConfig::new().format(format);
+    while let Ok((j, (skipped, link, kind))) = take_link_kind(i, &config) {
+        if let Link::Text2Dest(te, de, ti) | Link::TextLabel2Dest(te, de, ti) = link {
+            let mut link = parser::ParsedLink::new(te, de, ti);
+            link.kind = kind;
+            link.source = Cow::Borrowed(&i[skipped.len()..i.len() - j.len()]);
+            links.push(link);
+        }
+        i = j;
+    }
+    links
+}
+
+/// Searches the _inline links_ of markup language `format` in `input` on
+/// all CPU cores, for very large inputs. The result is in document order.
+/// Requires the `rayon` feature.
+///
+/// The `input` is split into blocks of at least `READ_CHUNK_LEN` bytes
+/// ending with a blank line, which are searched in parallel. As with
+/// `LinkScanner`, links spanning a blank line are not recognized and
+/// _reference links_ are not resolved. To process many files in parallel,
+/// call this function or `LinkScanner` from a `rayon` parallel iterator
+/// over the files.
+///
+/// ```
+/// use parse_hyperlinks::iterator::par_hyperlinks;
+/// use parse_hyperlinks::parser::parse::Format;
+///
+/// let i = "abc [text1](dest1) abc\n\nabc <a href=\"dest2\">text2</a>\n".repeat(10000);
+///
+/// let links = par_hyperlinks(&i, Format::Unknown);
+/// assert_eq!(links.len(), 20000);
+/// assert_eq!(links[0].destination, "dest1");
+/// assert_eq!(links[19999].destination, "dest2");
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_hyperlinks(input: &str, format: Format) -> Vec<parser::ParsedLink<'_>> {
+    let mut blocks = Vec::new();
+    let mut rest = input;
+    while rest.len() > READ_CHUNK_LEN {
+        // The end of the first blank line after `READ_CHUNK_LEN`.
+        let block_len = rest
+            .match_indices('\n')
+            .map(|(idx, _)| idx + 1)
+            .filter(|&idx| idx >= READ_CHUNK_LEN)
+            .find_map(|idx| {
+                if rest[idx..].starts_with('\n') {
+                    Some(idx + 1)
+                } else if rest[idx..].starts_with("\r\n") {
+                    Some(idx + 2)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(rest.len());
+        blocks.push(&rest[..block_len]);
+        rest = &rest[block_len..];
+    }
+    blocks.push(rest);
+
+    let links: Vec<Vec<_>> = blocks
+        .par_iter()
+        .map(|block| search_block(block, format))
+        .collect();
+    links.into_iter().flatten().collect()
+}
+
+/// Iterator over the _inline links_ of a text read from an `io::Read`, e.g.
+/// a large log file, without loading it into memory completely. Constructed
+/// with `Hyperlink::from_reader()`.
+///
+/// The text is read in chunks and searched with a `LinkScanner`, see there
+/// for how links overlapping a chunk boundary are handled.
+///
+/// Unlike `Hyperlink`, this iterator does not resolve _reference links_, as
+/// their _link reference definition_ may come later in the text. Only
+/// _inline links_ are returned, as owned
+/// `(link_text, link_destination, link_title)`. A read error or invalid
+/// UTF-8 is returned as `io::Error`, after which the iterator ends.
+pub struct HyperlinkReader<R> {
+    /// The source of the text.
+    reader: R,
+    /// Bytes read, but not decoded yet.
+    buf: Vec<u8>,
+    /// Searches the decoded text.
+    scanner: LinkScanner,
+    /// Links found, but not returned yet.
+    links: VecDeque<(Cow<'static, str>, Cow<'static, str>, Cow<'static, str>)>,
+    /// True when the `reader` is exhausted.
+    eof: bool,
+}
+
+impl<R: Read> HyperlinkReader<R> {
+    /// Reads one more chunk and passes the valid UTF-8 in `buf` to the
+    /// `scanner`.
+    fn fill(&mut self) -> io::Result<()> {
+        if !self.eof {
+            let len = self.buf.len();
+            self.buf.resize(len + READ_CHUNK_LEN, 0);
+            let n = loop {
+                match self.reader.read(&mut self.buf[len..]) {
+                    Ok(n) => break n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        self.buf.truncate(len);
+                        return Err(e);
+                    }
+                }
+            };
+            self.buf.truncate(len + n);
+            self.eof = n == 0;
+        }
+
+        let (valid_len, invalid) = match std::str::from_utf8(&self.buf) {
+            Ok(text) => (text.len(), None),
+            // Unless a multi-byte character is cut by the chunk boundary.
+            Err(e) => (
+                e.valid_up_to(),
+                (e.error_len().is_some() || self.eof).then_some(e),
+            ),
+        };
+        let text = std::str::from_utf8(&self.buf[..valid_len]).unwrap();
+        self.links.extend(self.scanner.push(text).map(Into::into));
+        self.buf.drain(..valid_len);
+        if self.eof || invalid.is_some() {
+            // The valid text before invalid UTF-8 is searched first.
+            let links_len = self.links.len();
+            self.links.extend(self.scanner.finish().map(Into::into));
+            if let (Some(e), 0, true) = (invalid, valid_len, links_len == self.links.len()) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for HyperlinkReader<R> {
+    type Item = io::Result<(Cow<'static, str>, Cow<'static, str>, Cow<'static, str>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(link) = self.links.pop_front() {
+                return Some(Ok(link));
+            }
+            if self.eof && self.buf.is_empty() {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                self.eof = true;
+                self.buf.clear();
+                return Some(Err(e));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.links.len(), None)
+    }
+}
+
+impl<R: Read> FusedIterator for HyperlinkReader<R> {}
+
+/// Returns the URI scheme of `dest`, e.g. `https` for
+/// `https://example.org`, or `None` for a relative URL.
+fn uri_scheme(dest: &str) -> Option<&str> {
+    let (scheme, _) = dest.split_once(':')?;
+    let mut chars = scheme.chars();
+    (chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')))
+    .then_some(scheme)
+}
+
+/// Returns the position `(line, column)` of the byte `offset` in `input`.
+/// Lines and columns are counted from 1, columns in Unicode scalar values
+/// (`char`). A line ends with `\n`.
+/// ```
+/// use parse_hyperlinks::iterator::line_column;
+///
+/// assert_eq!(line_column("abc\r\näbc", 0), (1, 1));
+/// assert_eq!(line_column("abc\r\näbc", 8), (2, 3));
+/// ```
+pub fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_offset = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_offset..].chars().count() + 1;
+    (line, column)
 }
 
 /// Iterator over the hyperlinks (with markup) in the `input`-text.
@@ -441,7 +1112,7 @@ impl<'a> Iterator for Hyperlink<'a> {
                 Status::DirectSearch(input) => {
                     // We stay in direct mode.
                     if let Ok((remaining_input, (skipped, Link::Text2Dest(te, de, ti)))) =
-                        take_link(input)
+                        self.take_accepted_link(input)
                     {
                         let consumed = &input[skipped.len()..input.len() - remaining_input.len()];
                         // Assing output.
@@ -459,7 +1130,8 @@ impl<'a> Iterator for Hyperlink<'a> {
                     } else {
                         // We switch to resolving mode.
                         self.input = input;
-                        let mut hc = HyperlinkCollection::from(input, self.render_label);
+                        let mut hc =
+                            HyperlinkCollection::from(input, self.render_label, &self.config);
                         hc.resolve_label2label_references();
                         hc.resolve_text2label_references();
                         let mut resolved_links = Vec::new();
@@ -475,6 +1147,9 @@ impl<'a> Iterator for Hyperlink<'a> {
                         if let (input_offset, len, Link::Text2Dest(te, de, ti)) =
                             resolved_links.remove(0)
                         {
+                            if !self.accepts(&de) {
+                                continue;
+                            }
                             let skipped = &self.input
                                 [(self.last_output_offset + self.last_output_len)..input_offset];
                             let consumed = &self.input[input_offset..input_offset + len];
@@ -513,8 +1188,22 @@ impl<'a> Iterator for Hyperlink<'a> {
         swap(&mut status, &mut self.status);
         output
     }
+
+    /// Every link takes at least one byte of the remaining input. Once the
+    /// links are resolved, at most all of them remain.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.status {
+            Status::Init => (0, Some(self.input.len())),
+            Status::DirectSearch(input) => (0, Some(input.len())),
+            Status::ResolvedLinks(resolved_links) => (0, Some(resolved_links.len())),
+            Status::End => (0, Some(0)),
+        }
+    }
 }
 
+/// Once `None` is returned, the state machine stays in `Status::End`.
+impl FusedIterator for Hyperlink<'_> {}
+
 /// Recognizes hyperlinks in all supported markup languages
 /// and returns the first hyperlink found as tuple:
 /// `Some((link_text, link_destination, link_title))`.
@@ -540,9 +1229,347 @@ pub fn first_hyperlink(i: &str) -> Option<(Cow<str>, Cow<str>, Cow<str>)> {
     }
 }
 
+/// Iterator over the hyperlinks of a Markdown document, same as
+/// `Hyperlink::with_format(input, render_label, Format::Markdown)`. Inline HTML
+/// links are included.
+///
+/// ```
+/// use parse_hyperlinks::iterator::MarkdownLinks;
+/// use std::borrow::Cow;
+///
+/// let i = "abc [text1](dest1) abc `text2 <dest2>`__ abc";
+///
+/// let mut iter = MarkdownLinks::new(i, false);
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("text1"), Cow::from("dest1"), Cow::from("")));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct MarkdownLinks<'a>(Hyperlink<'a>);
+
+impl<'a> MarkdownLinks<'a> {
+    /// Constructor for the iterator, see `Hyperlink::new()`.
+    #[inline]
+    pub fn new(input: &'a str, render_label: bool) -> Self {
+        Self(Hyperlink::with_format(
+            input,
+            render_label,
+            Format::Markdown,
+        ))
+    }
+}
+
+impl<'a> Iterator for MarkdownLinks<'a> {
+    type Item = <Hyperlink<'a> as Iterator>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedIterator for MarkdownLinks<'_> {}
+
+/// Iterator over the hyperlinks of a reStructuredText document, same as
+/// `Hyperlink::with_format(input, render_label, Format::ReStructuredText)`.
+///
+/// ```
+/// use parse_hyperlinks::iterator::RstLinks;
+/// use std::borrow::Cow;
+///
+/// let i = "abc `text1 <dest1>`__ abc [text2](dest2) abc";
+///
+/// let mut iter = RstLinks::new(i, false);
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("text1"), Cow::from("dest1"), Cow::from("")));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct RstLinks<'a>(Hyperlink<'a>);
+
+impl<'a> RstLinks<'a> {
+    /// Constructor for the iterator, see `Hyperlink::new()`.
+    #[inline]
+    pub fn new(input: &'a str, render_label: bool) -> Self {
+        Self(Hyperlink::with_format(
+            input,
+            render_label,
+            Format::ReStructuredText,
+        ))
+    }
+}
+
+impl<'a> Iterator for RstLinks<'a> {
+    type Item = <Hyperlink<'a> as Iterator>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedIterator for RstLinks<'_> {}
+
+/// Iterator over the hyperlinks of a HTML document, same as
+/// `Hyperlink::with_format(input, render_label, Format::Html)`.
+///
+/// ```
+/// use parse_hyperlinks::iterator::HtmlLinks;
+/// use std::borrow::Cow;
+///
+/// let i = "abc <a href=\"dest1\">text1</a> abc [text2](dest2) abc";
+///
+/// let mut iter = HtmlLinks::new(i, false);
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("text1"), Cow::from("dest1"), Cow::from("")));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct HtmlLinks<'a>(Hyperlink<'a>);
+
+impl<'a> HtmlLinks<'a> {
+    /// Constructor for the iterator, see `Hyperlink::new()`.
+    #[inline]
+    pub fn new(input: &'a str, render_label: bool) -> Self {
+        Self(Hyperlink::with_format(input, render_label, Format::Html))
+    }
+}
+
+impl<'a> Iterator for HtmlLinks<'a> {
+    type Item = <Hyperlink<'a> as Iterator>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedIterator for HtmlLinks<'_> {}
+
+/// Iterator over the definitions of link labels and targets in the `input`,
+/// e.g. to build a cross-reference table. Each item is the byte offset of the
+/// definition in `input`, see `line_column()`, and the definition as
+/// `parser::ParsedLink`. The items are ordered by offset. Recognized are:
+/// * _link reference definitions_, e.g. `[label]: destination` or
+///   `.. _label: destination`, and reStructuredText _embedded URIs_
+///   `` `label <destination>`_ `` with the `kind` `LinkKind::Definition`,
+/// * _reference aliases_, e.g. `.. _alias: label_`, also with the `kind`
+///   `LinkKind::Definition`, where the `destination` is the label the alias
+///   refers to,
+/// * reStructuredText _inline internal targets_ `` _`target` `` and the `id`
+///   and `name` attributes of HTML elements, with the `kind`
+///   `LinkKind::Target` and an empty `destination`.
+///
+/// Anonymous definitions are skipped, as they have no label. As in
+/// `parser::parse::take_link()`, reStructuredText comments and literal
+/// directives, e.g. `.. code::`, and HTML comments are not searched.
+///
+/// ```
+/// use parse_hyperlinks::iterator::Definitions;
+/// use parse_hyperlinks::parser::LinkKind;
+///
+/// let i = r#"abc [text1][label1] abc _`target2` abc
+/// [label1]: dest1 "title1"
+/// <h2 id="target3">abc</h2>
+/// "#;
+///
+/// let mut iter = Definitions::new(i);
+/// let (offset, def) = iter.next().unwrap();
+/// assert_eq!((offset, def.name.as_ref(), def.kind), (24, "target2", LinkKind::Target));
+/// let (offset, def) = iter.next().unwrap();
+/// assert_eq!((offset, def.name.as_ref(), def.kind), (39, "label1", LinkKind::Definition));
+/// assert_eq!(def.destination, "dest1");
+/// let (offset, def) = iter.next().unwrap();
+/// assert_eq!((offset, def.name.as_ref(), def.kind), (64, "target3", LinkKind::Target));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Definitions<'a> {
+    /// The definitions found, ordered by offset.
+    definitions: std::vec::IntoIter<(usize, parser::ParsedLink<'a>)>,
+}
+
+impl<'a> Definitions<'a> {
+    /// Constructor for the iterator. Searches the whole `input`.
+    pub fn new(input: &'a str) -> Self {
+        let mut definitions = Vec::new();
+
+        let mut i = input;
+        while let Ok((j, (skipped, link))) = take_link(i) {
+            let offset = input.len() - i.len() + skipped.len();
+            let source = &i[skipped.len()..i.len() - j.len()];
+            i = j;
+            let mut def = match link {
+                Link::Label2Dest(la, de, ti) | Link::TextLabel2Dest(la, de, ti) if la != "_" => {
+                    parser::ParsedLink::new(la, de, ti)
+                }
+                Link::Label2Label(al, la) if al != "_" => parser::ParsedLink::new(al, la, ""),
+                _ => continue,
+            };
+            def.kind = parser::LinkKind::Definition;
+            def.source = Cow::Borrowed(source);
+            definitions.push((offset, def));
+        }
+
+        let skipped = skipped_ranges(input);
+
+        // Inline internal targets start at the input start or after a
+        // whitespace, possibly followed by one of `([<'"`.
+        for (offset, _) in input.match_indices("_`") {
+            if skipped.iter().any(|r| r.contains(&offset)) {
+                continue;
+            }
+            let before = &input[..offset];
+            let before = before
+                .strip_suffix(['(', '[', '<', '\'', '"'])
+                .unwrap_or(before);
+            if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+                continue;
+            }
+            if let Ok((j, target)) = rst_inline_target(&input[offset..]) {
+                let mut def = parser::ParsedLink::new(target, "", "");
+                def.kind = parser::LinkKind::Target;
+                def.source = Cow::Borrowed(&input[offset..input.len() - j.len()]);
+                definitions.push((offset, def));
+            }
+        }
+
+        let mut i = input;
+        while let Some(idx) = i.find('<') {
+            i = &i[idx..];
+            let offset = input.len() - i.len();
+            if let Some(r) = skipped.iter().find(|r| r.contains(&offset)) {
+                i = &input[r.end..];
+                continue;
+            }
+            if let Ok((j, targets)) = html_anchor_target(i) {
+                for target in targets {
+                    let mut def = parser::ParsedLink::new(target, "", "");
+                    def.kind = parser::LinkKind::Target;
+                    def.source = Cow::Borrowed(&i[..i.len() - j.len()]);
+                    definitions.push((offset, def));
+                }
+            }
+            i = &i[1..];
+        }
+
+        definitions.sort_by_key(|(offset, _)| *offset);
+        Self {
+            definitions: definitions.into_iter(),
+        }
+    }
+}
+
+/// Byte ranges of `input`, which `parser::parse::take_link()` skips without
+/// searching for links: reStructuredText comments and literal directives
+/// starting a line, and HTML comments.
+fn skipped_ranges(input: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut i = input;
+    let mut line_start = true;
+    while !i.is_empty() {
+        let offset = input.len() - i.len();
+        if line_start {
+            if let Ok((j, markup)) = rst_explicit_markup(i) {
+                if markup.is_literal() {
+                    ranges.push(offset..input.len() - j.len());
+                    i = j;
+                    line_start = false;
+                    continue;
+                }
+            }
+        }
+        match (i.find('\n'), i.find("<!--")) {
+            (Some(lf), c) if c.is_none_or(|c| lf < c) => {
+                i = &i[lf + 1..];
+                line_start = true;
+            }
+            (_, Some(c)) => {
+                i = &i[c..];
+                let offset = input.len() - i.len();
+                if let Ok((j, _)) = html_comment(i) {
+                    ranges.push(offset..input.len() - j.len());
+                    i = j;
+                } else {
+                    i = &i[1..];
+                }
+                line_start = false;
+            }
+            _ => break,
+        }
+    }
+    ranges
+}
+
+impl<'a> Iterator for Definitions<'a> {
+    type Item = (usize, parser::ParsedLink<'a>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.definitions.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.definitions.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Definitions<'_> {}
+
+impl FusedIterator for Definitions<'_> {}
+
+/// Extension trait making the iterator `Hyperlink` and `first_hyperlink()`
+/// available as methods of `str` and `String`.
+///
+/// ```
+/// use parse_hyperlinks::iterator::StrHyperlinks;
+/// use std::borrow::Cow;
+///
+/// let i = "abc [text1](dest1) abc [text2](dest2)";
+///
+/// assert_eq!(i.hyperlinks().count(), 2);
+/// assert_eq!(
+///     i.to_string().first_hyperlink(),
+///     Some((Cow::from("text1"), Cow::from("dest1"), Cow::from("")))
+/// );
+/// ```
+pub trait StrHyperlinks {
+    /// Same as `Hyperlink::new(self, false)`.
+    fn hyperlinks(&self) -> Hyperlink<'_>;
+
+    /// Same as `first_hyperlink(self)`.
+    fn first_hyperlink(&self) -> Option<(Cow<'_, str>, Cow<'_, str>, Cow<'_, str>)>;
+}
+
+impl StrHyperlinks for str {
+    #[inline]
+    fn hyperlinks(&self) -> Hyperlink<'_> {
+        Hyperlink::new(self, false)
+    }
+
+    #[inline]
+    fn first_hyperlink(&self) -> Option<(Cow<'_, str>, Cow<'_, str>, Cow<'_, str>)> {
+        first_hyperlink(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::LinkKind;
 
     #[test]
     fn test_populate_collection() {
@@ -561,7 +1588,7 @@ abc `rst text6`__abc
 abc `rst text_label7 <rst_destination7>`_abc
 "#;
 
-        let hc = HyperlinkCollection::from(i, false);
+        let hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
 
         let expected = r#"[
     (
@@ -686,7 +1713,7 @@ abc `rst text_label7 <rst_destination7>`_abc
   .. _label3: label2_
 "#;
 
-        let mut hc = HyperlinkCollection::from(i, false);
+        let mut hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
         hc.resolve_label2label_references();
         //eprintln!("{:#?}", hc);
         assert_eq!(hc.label2label.len(), 1);
@@ -725,7 +1752,7 @@ abc `rst text_label7 <rst_destination7>`_abc
         label4_
         "#;
 
-        let mut hc = HyperlinkCollection::from(i, false);
+        let mut hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
         //eprintln!("{:#?}", hc);
         hc.resolve_label2label_references();
         //eprintln!("{:#?}", hc);
@@ -792,7 +1819,7 @@ abc text5__ abc
   __ destination5
         "#;
 
-        let mut hc = HyperlinkCollection::from(i, false);
+        let mut hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
         //eprintln!("{:#?}", hc);
         hc.resolve_label2label_references();
         //eprintln!("{:#?}", hc);
@@ -842,7 +1869,7 @@ abc
 [my homepage]: https://getreu.net
 abc"#;
 
-        let mut hc = HyperlinkCollection::from(i, false);
+        let mut hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
         eprintln!("{:#?}", hc);
         hc.resolve_label2label_references();
         //eprintln!("{:#?}", hc);
@@ -861,6 +1888,109 @@ abc"#;
         assert_eq!(hc.text2dest_label, expected);
     }
 
+    #[test]
+    fn test_resolve_text2label_references_normalized() {
+        let i = r#"abc `Python
+Home`_ abc
+.. _python  home: python_
+.. _PYTHON: https://www.python.org
+"#;
+
+        let mut hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
+        hc.resolve_label2label_references();
+        hc.resolve_text2label_references();
+
+        let expected = vec![(
+            4,
+            14,
+            Link::Text2Dest(
+                Cow::from("Python\nHome"),
+                Cow::from("https://www.python.org"),
+                Cow::from(""),
+            ),
+        )];
+        assert_eq!(hc.text2dest_label, expected);
+    }
+
+    #[test]
+    fn test_next_anonymous_short_form() {
+        let i = r#"abc `text1`__ abc text2__ abc `text3`__
+.. __: https://example.org/1
+__ https://example.org/
+   2
+__ `Python`_
+.. _Python: https://www.python.org
+"#;
+
+        let res: Vec<_> = Hyperlink::new(i, false).map(|(_, l)| l).collect();
+        let expected = vec![
+            (
+                Cow::from("text1"),
+                Cow::from("https://example.org/1"),
+                Cow::from(""),
+            ),
+            (
+                Cow::from("text2"),
+                Cow::from("https://example.org/2"),
+                Cow::from(""),
+            ),
+            (
+                Cow::from("text3"),
+                Cow::from("https://www.python.org"),
+                Cow::from(""),
+            ),
+        ];
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_positions() {
+        let i = r#"abc[text1][label1]abc
+abc `text2 <dest2>`__
+  [label1]: dest1
+ ä<a href="dest3">text3</a>"#;
+
+        let res: Vec<_> = Hyperlink::new(i, false)
+            .positions()
+            .map(|(pos, (span, _))| (pos, span.1))
+            .collect();
+        let expected = vec![
+            ((1, 4), "[text1][label1]"),
+            ((2, 5), "`text2 <dest2>`__"),
+            ((4, 3), "<a href=\"dest3\">text3</a>"),
+        ];
+        assert_eq!(res, expected);
+        for ((line, column), span) in expected {
+            let offset = i.find(span).unwrap();
+            assert_eq!(line_column(i, offset), (line, column));
+        }
+    }
+
+    #[test]
+    fn test_populate_collection_xref() {
+        let i = "abc<<section>>abc xref:doc.adoc#part[text2]abc";
+
+        let hc = HyperlinkCollection::from(i, false, &ParserConfig::new());
+
+        let expected = vec![
+            (
+                3,
+                11,
+                Link::Text2Dest(Cow::from("section"), Cow::from("#section"), Cow::from("")),
+            ),
+            (
+                18,
+                25,
+                Link::Text2Dest(
+                    Cow::from("text2"),
+                    Cow::from("doc.adoc#part"),
+                    Cow::from(""),
+                ),
+            ),
+        ];
+        assert_eq!(hc.text2dest_label, expected);
+    }
+
     #[test]
     fn test_next() {
         let i = r#"abc[text0](destination0)abc
@@ -926,4 +2056,272 @@ label4_
         //eprintln!("item: {:#?}", item);
         assert_eq!(item, expected);
     }
+
+    /// Reader returning at most 3 bytes per `read()`.
+    struct SlowReader<'a>(&'a [u8]);
+
+    impl Read for SlowReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let i = "abc [täxt1](dest1) abc\n\nabc `text2\n<dest2>`__ [text3][label3]\r\n\r\n\
+                 [label3]: dest3\nabc <a href=\"dest4\" title=\"title4\">text4</a>";
+        let res: Vec<_> = Hyperlink::from_reader(SlowReader(i.as_bytes()), Format::Unknown)
+            .map(|l| l.unwrap())
+            .collect();
+        let expected = vec![
+            (Cow::from("täxt1"), Cow::from("dest1"), Cow::from("")),
+            (Cow::from("text2"), Cow::from("dest2"), Cow::from("")),
+            (Cow::from("text4"), Cow::from("dest4"), Cow::from("title4")),
+        ];
+        assert_eq!(res, expected);
+
+        let mut iter = Hyperlink::from_reader(&b"abc [text1](dest1)\n\n\xff"[..], Format::Unknown);
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            (Cow::from("text1"), Cow::from("dest1"), Cow::from(""))
+        );
+        assert_eq!(
+            iter.next().unwrap().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_from_reader_no_blank_line() {
+        let i = "abc [text](dest) abc\n".repeat(4 * MAX_BLOCK_LEN / 22);
+        let mut iter = Hyperlink::from_reader(i.as_bytes(), Format::Unknown);
+        let mut n = 0;
+        while let Some(l) = iter.next() {
+            assert_eq!(
+                l.unwrap(),
+                (Cow::from("text"), Cow::from("dest"), Cow::from(""))
+            );
+            assert!(iter.scanner.buf.len() <= MAX_BLOCK_LEN);
+            n += 1;
+        }
+        assert_eq!(n, 4 * MAX_BLOCK_LEN / 22);
+    }
+
+    #[test]
+    fn test_link_scanner() {
+        let i = "abc [täxt1](dest1) abc\r\n\r\nabc `text2\n<dest2>`__ [text3][label3]\n\n\
+                 [label3]: dest3\nabc https://dest4 abc";
+        let mut scanner = LinkScanner::new(Format::Unknown);
+        let mut res = Vec::new();
+        for piece in i.split_inclusive(['a', '\n']) {
+            res.extend(scanner.push(piece).map(|l| (l.name, l.destination, l.kind)));
+        }
+        assert_eq!(res.len(), 2);
+        res.extend(scanner.finish().map(|l| (l.name, l.destination, l.kind)));
+        let expected = vec![
+            (
+                Cow::from("täxt1"),
+                Cow::from("dest1"),
+                parser::LinkKind::Inline,
+            ),
+            (
+                Cow::from("text2"),
+                Cow::from("dest2"),
+                parser::LinkKind::Inline,
+            ),
+            (
+                Cow::from("https://dest4"),
+                Cow::from("https://dest4"),
+                parser::LinkKind::Autolink,
+            ),
+        ];
+        assert_eq!(res, expected);
+        assert_eq!(scanner.finish().count(), 0);
+    }
+
+    #[test]
+    fn test_link_scanner_no_blank_line() {
+        // Lines, but no blank line.
+        let chunk = "abc [text](dest) abc\n".repeat(READ_CHUNK_LEN / 22);
+        let mut scanner = LinkScanner::new(Format::Unknown);
+        let mut n = 0;
+        for _ in 0..64 {
+            n += scanner.push(&chunk).count();
+            assert!(scanner.buf.len() <= MAX_BLOCK_LEN);
+        }
+        n += scanner.finish().count();
+        assert_eq!(n, 64 * (READ_CHUNK_LEN / 22));
+
+        // One single line.
+        let chunk = "abc [text](dest) äbc ".repeat(READ_CHUNK_LEN / 22);
+        let mut n = 0;
+        for _ in 0..64 {
+            n += scanner.push(&chunk).count();
+            assert!(scanner.buf.len() <= MAX_BLOCK_LEN);
+        }
+        n += scanner.finish().count();
+        assert_eq!(n, 64 * (READ_CHUNK_LEN / 22));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_hyperlinks() {
+        let i = "abc [text1](dest1) abc\r\n\r\nabc `text2\n<dest2>`__ abc\n\näbc https://dest3\n"
+            .repeat(READ_CHUNK_LEN / 20);
+        let res = par_hyperlinks(&i, Format::Unknown);
+        let mut scanner = LinkScanner::new(Format::Unknown);
+        let mut expected: Vec<_> = scanner.push(&i).collect();
+        expected.extend(scanner.finish());
+        assert_eq!(res.len(), 3 * (READ_CHUNK_LEN / 20));
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_unique_by_destination() {
+        let i = r#"abc [text1][label1] abc [text2](dest2) abc [text3](dest1)
+abc [text4][label1] abc [text5](dest2)
+[label1]: dest1
+"#;
+        let mut links: Vec<_> = Hyperlink::new(i, false).unique_by_destination().collect();
+        let res: Vec<_> = links.iter().map(|l| l.1 .0.clone()).collect();
+        assert_eq!(res, vec![Cow::from("text1"), Cow::from("text2")]);
+
+        links.reverse();
+        sort_by_position(&mut links);
+        assert_eq!(links[0].1 .0, Cow::from("text1"));
+        sort_by_destination(&mut links);
+        assert_eq!(links[0].1 .0, Cow::from("text1"));
+        assert_eq!(links[1].1 .0, Cow::from("text2"));
+    }
+
+    #[test]
+    fn test_schemes() {
+        let i = r#"abc [text1](mailto:a@b) abc [text2][label2] abc [text3](ftp://dest3)
+abc [text4](dest4) abc [text5](Http://dest5)
+[label2]: https://dest2
+"#;
+        let mut iter = Hyperlink::new(i, false).schemes(&["http", "https", "mailto"]);
+        let link = iter.next().unwrap();
+        assert_eq!(link.1 .1, Cow::from("mailto:a@b"));
+        let link = iter.next().unwrap();
+        assert_eq!(link.1 .1, Cow::from("https://dest2"));
+        let link = iter.next().unwrap();
+        assert_eq!(
+            link.0 .0,
+            " abc [text3](ftp://dest3)\nabc [text4](dest4) abc "
+        );
+        assert_eq!(link.1 .1, Cow::from("Http://dest5"));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(uri_scheme("https://dest"), Some("https"));
+        assert_eq!(uri_scheme("a+b.c-d:dest"), Some("a+b.c-d"));
+        assert_eq!(uri_scheme("dest:"), Some("dest"));
+        assert_eq!(uri_scheme("1a:dest"), None);
+        assert_eq!(uri_scheme("./a:b"), None);
+        assert_eq!(uri_scheme("dest"), None);
+    }
+
+    #[test]
+    fn test_context() {
+        let i = r#"abc [text1][label1] äbc
+[text2](dest2)
+abc `text3
+<dest3>`__ abc
+[label1]: dest1
+"#;
+        let res: Vec<_> = Hyperlink::new(i, false).context(4).map(|l| l.0).collect();
+        let expected = vec![("abc ", " äbc"), ("äbc\n", "\nabc"), ("abc ", " abc")];
+        assert_eq!(res, expected);
+
+        let res: Vec<_> = Hyperlink::new(i, false).context(0).map(|l| l.0).collect();
+        assert_eq!(res, vec![("", ""); 3]);
+
+        let res: Vec<_> = Hyperlink::new(i, false).context(100).map(|l| l.0).collect();
+        assert_eq!(res[0].0, "abc ");
+        assert!(res[0].1.ends_with("dest1\n"));
+
+        let res: Vec<_> = Hyperlink::new(i, false)
+            .line_context()
+            .map(|l| l.0)
+            .collect();
+        let expected = vec![
+            "abc [text1][label1] äbc",
+            "[text2](dest2)",
+            "abc `text3\n<dest3>`__ abc",
+        ];
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_iterator_traits() {
+        let i = r#"abc [text1](dest1) abc [text2][label2] abc
+[label2]: dest2
+"#;
+        let mut iter = Hyperlink::new(i, false);
+        assert_eq!(iter.size_hint(), (0, Some(i.len())));
+        let restart = iter.clone();
+        assert_eq!(iter.next().unwrap().1 .1, Cow::from("dest1"));
+        assert_eq!(iter.size_hint().1, Some(i.len() - 18));
+        assert_eq!(iter.next().unwrap().1 .1, Cow::from("dest2"));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let res: Vec<_> = restart.map(|l| l.1 .1).collect();
+        assert_eq!(res, vec![Cow::from("dest1"), Cow::from("dest2")]);
+    }
+
+    #[test]
+    fn test_definitions() {
+        let i = r#"abc `text1 <dest1>`_ abc _`target2`, abc x_`no` abc `text3`__
+.. _label4: dest4
+.. __: anonymous
+.. _alias5: label4_
+<!-- <a name="no"> -->
+<a id="target6" name="target7" href="dest8">text8</a>
+"#;
+        let res: Vec<_> = Definitions::new(i)
+            .map(|(offset, def)| (offset, def.name, def.destination, def.kind))
+            .collect();
+        let expected = vec![
+            (
+                4,
+                Cow::from("text1"),
+                Cow::from("dest1"),
+                LinkKind::Definition,
+            ),
+            (25, Cow::from("target2"), Cow::from(""), LinkKind::Target),
+            (
+                62,
+                Cow::from("label4"),
+                Cow::from("dest4"),
+                LinkKind::Definition,
+            ),
+            (
+                97,
+                Cow::from("alias5"),
+                Cow::from("label4"),
+                LinkKind::Definition,
+            ),
+            (140, Cow::from("target6"), Cow::from(""), LinkKind::Target),
+            (140, Cow::from("target7"), Cow::from(""), LinkKind::Target),
+        ];
+        assert_eq!(res, expected);
+        assert_eq!(Definitions::new(i).len(), 6);
+    }
+
+    #[test]
+    fn test_definitions_skipped() {
+        let i = "abc\n.. code-block:: rst\n\n   _`no1` <a id=\"no2\">\n\nabc\n\
+                 .. A comment _`no3`\n<!-- _`no4` -->\nabc _`target1`\n";
+        let res: Vec<_> = Definitions::new(i)
+            .map(|(offset, def)| (offset, def.name))
+            .collect();
+        assert_eq!(res, vec![(93, Cow::from("target1"))]);
+    }
 }
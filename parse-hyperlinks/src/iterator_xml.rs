@@ -0,0 +1,118 @@
+//! Module providing an iterator over the URI attributes found in XML input.
+//! The iterator does not know any XML vocabulary: it recognizes the
+//! attributes `href`, `xlink:href` and `src` of any element.
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::take_xml_uri_attributes;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+#[derive(Debug, PartialEq)]
+/// Iterator over the URI attributes in the XML formatted `input` text.
+/// This struct holds the iterator's state as an advancing pointer into the
+/// `input` text and the URI attributes of the current element, which have
+/// not been returned yet.
+/// The iterator's `next()` method returns a tuple with 2 tuples inside:
+/// `Some(((input_split)(uri_attribute)))`.
+///
+/// Each tuple has the following parts:
+/// * `input_split = (skipped_characters, consumed_characters, remaining_characters)`
+/// * `uri_attribute = (element_name, attribute_name, attribute_value)`
+///
+/// `consumed_characters` is the attribute, e.g. `href="dest1"`, in the source
+/// text.
+///
+/// # Input split
+///
+/// ```
+/// use parse_hyperlinks::iterator_xml::XmlUriAttribute;
+/// use std::borrow::Cow;
+///
+/// let i = r#"abc<a href="dest1">abc</a><img src="dest2"/>xyz"#;
+///
+/// let mut iter = XmlUriAttribute::new(i);
+/// assert_eq!(iter.next().unwrap().0,
+///            ("abc<a ", r#"href="dest1""#, r#">abc</a><img src="dest2"/>xyz"#));
+/// assert_eq!(iter.next().unwrap().0,
+///            (">abc</a><img ", r#"src="dest2""#, "/>xyz"));
+/// assert_eq!(iter.next(), None);
+/// ```
+/// # Link content
+///
+/// ```
+/// use parse_hyperlinks::iterator_xml::XmlUriAttribute;
+/// use std::borrow::Cow;
+///
+/// let i = r#"<svg><a href="dest1"><image xlink:href="dest2" src="dest3"/></a></svg>"#;
+///
+/// let mut iter = XmlUriAttribute::new(i);
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("a"), Cow::from("href"), Cow::from("dest1")));
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("image"), Cow::from("xlink:href"), Cow::from("dest2")));
+/// assert_eq!(iter.next().unwrap().1, (Cow::from("image"), Cow::from("src"), Cow::from("dest3")));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub struct XmlUriAttribute<'a> {
+    /// The remaining text input.
+    input: &'a str,
+    /// URI attributes of the current element, not returned yet:
+    /// `(start, end, element_name, attribute_name, attribute_value)`.
+    /// `start` and `end` are byte offsets into `input`.
+    pending: VecDeque<(usize, usize, &'a str, &'a str, Cow<'a, str>)>,
+}
+
+/// Constructor for the `XmlUriAttribute` struct.
+impl<'a> XmlUriAttribute<'a> {
+    /// Constructor for the iterator. `input` is the text with URI attributes
+    /// to be extracted.
+    #[inline]
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// Iterator over the URI attributes in the `input`-text.
+/// The iterator's `next()` method returns a tuple with 2 tuples inside:
+/// * `Some(((input_split)(uri_attribute)))`
+///
+/// Each tuple has the following parts:
+/// * `input_split = (skipped_characters, consumed_characters, remaining_characters)`
+/// * `uri_attribute = (element_name, attribute_name, attribute_value)`
+///
+impl<'a> Iterator for XmlUriAttribute<'a> {
+    type Item = (
+        (&'a str, &'a str, &'a str),
+        (Cow<'a, str>, Cow<'a, str>, Cow<'a, str>),
+    );
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() {
+            let (_, (_, element_name, attributes)) = take_xml_uri_attributes(self.input).ok()?;
+            self.pending = attributes
+                .into_iter()
+                .map(|(start, end, name, value)| (start, end, element_name, name, value))
+                .collect();
+        }
+
+        let (start, end, element_name, attribute_name, value) = self.pending.pop_front()?;
+        let skipped = &self.input[..start];
+        let consumed = &self.input[start..end];
+        let remaining_input = &self.input[end..];
+        // Offsets of the other pending attributes are relative to the old input.
+        for p in self.pending.iter_mut() {
+            p.0 -= end;
+            p.1 -= end;
+        }
+        self.input = remaining_input;
+
+        Some((
+            (skipped, consumed, remaining_input),
+            (
+                Cow::Borrowed(element_name),
+                Cow::Borrowed(attribute_name),
+                value,
+            ),
+        ))
+    }
+}
@@ -7,14 +7,38 @@
 //! [Asciidoctor User Manual, chapter 26](https://asciidoctor.org/docs/user-manual/#url) (date 2020-12-03),
 //! the
 //! [HTML 5.2: section 4.5.](https://www.w3.org/TR/html52/textlevel-semantics.html#the-a-element)
-//! specification
-//! and the [Wikitext v1.0.0](https://www.mediawiki.org/wiki/Specs/wikitext/1.0.0)
-//! specification.
+//! specification,
+//! the [Wikitext v1.0.0](https://www.mediawiki.org/wiki/Specs/wikitext/1.0.0)
+//! specification, [Org mode external links](https://orgmode.org/manual/External-Links.html),
+//! [WikiCreole 1.0 links](http://www.wikicreole.org/wiki/Creole1.0#section-Creole1.0-Links),
+//! [DokuWiki links](https://www.dokuwiki.org/wiki:syntax#links),
+//! [gemtext link lines](https://geminiprotocol.net/docs/gemtext-specification.gmi),
+//! [POD links](https://perldoc.perl.org/perlpod#Formatting-Codes),
+//! [Texinfo `@uref`](https://www.gnu.org/software/texinfo/manual/texinfo/html_node/_0040url.html),
+//! the [groff_man URL macros](https://man7.org/linux/man-pages/man7/groff_man.7.html),
+//! [DocBook links](https://tdg.docbook.org/tdg/5.2/link.html),
+//! the `<link>` elements of [RSS 2.0](https://www.rssboard.org/rss-specification)
+//! and [Atom](https://www.rfc-editor.org/rfc/rfc4287#section-4.2.7) feeds,
+//! [OPML](https://opml.org/spec2.opml) subscription lists,
+//! [XML sitemaps](https://www.sitemaps.org/protocol.html),
+//! [CSS URLs](https://www.w3.org/TR/css-values-4/#urls),
+//! [SVG links](https://www.w3.org/TR/SVG2/linking.html),
+//! URLs in [JSON](https://www.rfc-editor.org/rfc/rfc8259) strings,
+//! [YAML](https://yaml.org/spec/1.2.2/) scalars and
+//! [TOML](https://toml.io/en/v1.0.0) strings, bare URLs in plain text
+//! and email bodies, hyperlinks in the Markdown cells of
+//! [Jupyter notebooks](https://nbformat.readthedocs.io/),
+//! RTF `HYPERLINK` fields, iCalendar `URL` and `ATTACH` properties, vCard
+//! `URL` and `PHOTO` properties,
+//! Javadoc `{@link}` and `@see` tags, Doxygen `\link` and `\ref` commands
+//! and [rustdoc intra-doc links](https://doc.rust-lang.org/rustdoc/write-documentation/linking-to-items-by-name.html).
 #![allow(dead_code)]
 
 pub mod iterator;
+pub mod iterator_xml;
 pub mod parser;
 pub mod renderer;
+pub mod resolver;
 
 use nom::error::Error;
 use nom::error::ErrorKind;
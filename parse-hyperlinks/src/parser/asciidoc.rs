@@ -35,7 +35,10 @@ pub fn adoc_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 ///
 /// When ist starts at a whitespace no further guarantee is required.
 ///
-/// `link_title` is always the empty `Cow::Borrowed("")`.
+/// When the bracketed text is an attribute list, the first positional
+/// attribute is the _link text_ and the `title` attribute the _link title_.
+/// Otherwise `link_title` is the empty `Cow::Borrowed("")`. See
+/// `adoc_link_attributes()` for details.
 /// ```
 /// use parse_hyperlinks::parser::Link;
 /// use parse_hyperlinks::parser::asciidoc::adoc_text2dest;
@@ -53,6 +56,10 @@ pub fn adoc_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 ///   adoc_text2dest("https://destination abc"),
 ///   Ok((" abc", (Cow::from("https://destination"), Cow::from("https://destination"), Cow::from(""))))
 /// );
+/// assert_eq!(
+///   adoc_text2dest(r#"https://destination["name, more",title="title",window=_blank]abc"#),
+///   Ok(("abc", (Cow::from("name, more"), Cow::from("https://destination"), Cow::from("title"))))
+/// );
 /// ```
 pub fn adoc_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
     let (i, (link_destination, link_text)) = nom::sequence::preceded(
@@ -63,17 +70,18 @@ pub fn adoc_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<st
         ),
     )(i)?;
 
-    let link_text = if let Some(lt) = link_text {
+    let (link_text, link_title) = if let Some(lt) = link_text {
+        let (lt, ti) = adoc_link_attributes(lt);
         if lt.is_empty() {
-            link_destination.clone()
+            (link_destination.clone(), ti)
         } else {
-            lt
+            (lt, ti)
         }
     } else {
-        link_destination.clone()
+        (link_destination.clone(), Cow::Borrowed(""))
     };
 
-    Ok((i, (link_text, link_destination, Cow::Borrowed(""))))
+    Ok((i, (link_text, link_destination, link_title)))
 }
 
 /// Wrapper around `adoc_label2dest()` that packs the result in
@@ -131,6 +139,101 @@ pub fn adoc_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<s
     ))
 }
 
+/// Wrapper around `adoc_text2xref()` that packs the result in
+/// `Link::Text2Xref`.
+pub fn adoc_text2xref_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, xr)) = adoc_text2xref(i)?;
+    Ok((i, Link::Text2Xref(te, xr)))
+}
+
+/// Parses an Asciidoc _cross reference_.
+///
+/// A cross reference `xref:target[text]` or `<<target,text>>` points to an
+/// anchor in the same document, or, when `target` contains `#` or ends with
+/// `.adoc`, to another document. It returns either
+/// `Ok((i, (link_text, xref_target)))` or some error. When no _link text_
+/// is given, the function returns an empty _link text_ `""`.
+///
+/// The parser expects to start at `x` or at the first `<` to succeed.
+/// ```rust
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::asciidoc::adoc_text2xref;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   adoc_text2xref("xref:document.adoc#section[link text]abc"),
+///   Ok(("abc", (Cow::from("link text"), Cow::from("document.adoc#section"))))
+/// );
+/// assert_eq!(
+///   adoc_text2xref("<<section,link text>>abc"),
+///   Ok(("abc", (Cow::from("link text"), Cow::from("section"))))
+/// );
+/// assert_eq!(
+///   adoc_text2xref("<<section>>abc"),
+///   Ok(("abc", (Cow::from(""), Cow::from("section"))))
+/// );
+/// ```
+pub fn adoc_text2xref(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
+    alt((
+        nom::combinator::map(
+            nom::sequence::pair(
+                nom::sequence::preceded(
+                    tag("xref:"),
+                    nom::bytes::complete::take_till1(|c: char| c == '[' || c.is_whitespace()),
+                ),
+                adoc_link_text,
+            ),
+            |(xref_target, link_text)| {
+                (
+                    adoc_link_attributes(link_text).0,
+                    Cow::Borrowed(xref_target),
+                )
+            },
+        ),
+        nom::combinator::map(
+            nom::sequence::delimited(
+                tag("<<"),
+                nom::sequence::pair(
+                    nom::combinator::verify(
+                        nom::bytes::complete::take_till1(|c| c == ',' || c == '>' || c == '\n'),
+                        |s: &str| !s.trim().is_empty(),
+                    ),
+                    nom::combinator::opt(nom::sequence::preceded(
+                        char(','),
+                        nom::bytes::complete::take_till(|c| c == '>' || c == '\n'),
+                    )),
+                ),
+                tag(">>"),
+            ),
+            |(xref_target, link_text): (&str, Option<&str>)| {
+                (
+                    Cow::Borrowed(link_text.unwrap_or_default().trim()),
+                    Cow::Borrowed(xref_target.trim()),
+                )
+            },
+        ),
+    ))(i)
+}
+
+/// Returns the _link destination_ of the cross reference `xref_target`, as
+/// returned by `adoc_text2xref()`. A target without document part refers to
+/// an anchor in this document, it gets a leading `#`.
+/// ```rust
+/// use parse_hyperlinks::parser::asciidoc::adoc_xref_destination;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(adoc_xref_destination(Cow::from("section")), "#section");
+/// assert_eq!(adoc_xref_destination(Cow::from("document.adoc")), "document.adoc");
+/// assert_eq!(adoc_xref_destination(Cow::from("document#section")), "document#section");
+/// ```
+pub fn adoc_xref_destination(xref_target: Cow<str>) -> Cow<str> {
+    if xref_target.contains('#') || xref_target.ends_with(".adoc") {
+        xref_target
+    } else {
+        Cow::Owned(format!("#{}", xref_target))
+    }
+}
+
 /// Wrapper around `adoc_text2label()` that packs the result in
 /// `Link::Text2Label`.
 pub fn adoc_text2label_link(i: &str) -> nom::IResult<&str, Link> {
@@ -189,6 +292,46 @@ pub fn adoc_text2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
     Ok((i, (link_text, link_label)))
 }
 
+/// Wrapper around `adoc_img()` that packs the result in
+/// `Link::Image`.
+pub fn adoc_img_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (alt, src)) = adoc_img(i)?;
+    Ok((i, Link::Image(alt, src)))
+}
+
+/// Parses an Asciidoc _image macro_.
+///
+/// Both, the block image macro `image::src[alt]` and the inline image macro
+/// `image:src[alt]` are recognized. It returns either
+/// `Ok((i, (img_alt, img_src)))` or some error. `img_alt` can be empty,
+/// `img_src` not.
+///
+/// The parser expects to start at the link start (`i`) to succeed.
+/// ```rust
+/// use parse_hyperlinks::parser::asciidoc::adoc_img;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   adoc_img("image::images/sunset.jpg[Sunset,300,200]abc"),
+///   Ok(("abc", (Cow::from("Sunset"), Cow::from("images/sunset.jpg"))))
+/// );
+/// assert_eq!(
+///   adoc_img("image:https://example.org/logo.png[alt=\"Logo, small\"]abc"),
+///   Ok(("abc", (Cow::from("Logo, small"), Cow::from("https://example.org/logo.png"))))
+/// );
+/// ```
+pub fn adoc_img(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
+    let (i, (img_src, img_alt)) = nom::sequence::pair(
+        nom::sequence::preceded(
+            alt((tag("image::"), tag("image:"))),
+            nom::bytes::complete::take_till1(|c: char| c == '[' || c.is_whitespace()),
+        ),
+        adoc_link_text,
+    )(i)?;
+
+    Ok((i, (adoc_image_attributes(img_alt), Cow::Borrowed(img_src))))
+}
+
 /// Parses the link label. To succeed the first letter must be `[` and the
 /// last letter `]`. A sequence of whitespaces including newlines, will be
 /// replaced by one space. There must be not contain more than one newline
@@ -197,6 +340,116 @@ fn adoc_link_text(i: &str) -> nom::IResult<&str, Cow<str>> {
     nom::sequence::delimited(char('['), remove_newline_take_till(']'), char(']'))(i)
 }
 
+/// Interprets the bracketed text of a link macro as attribute list and
+/// returns `(link_text, link_title)`.
+///
+/// Like Asciidoctor, the text is only parsed as attribute list, when it
+/// contains an equal sign `=`. Then the attributes are separated by commas,
+/// the first positional attribute is the _link text_ and the value of the
+/// `title` attribute the _link title_. Values can be enclosed in double or
+/// single quotes to include commas. All other attributes are ignored. A
+/// trailing `^` in the _link text_, a shorthand for `window=_blank`, is
+/// removed.
+fn adoc_link_attributes(i: Cow<str>) -> (Cow<str>, Cow<str>) {
+    fn parse(i: &str) -> (&str, &str) {
+        if !i.contains('=') {
+            return (i.strip_suffix('^').unwrap_or(i), "");
+        }
+
+        let mut link_text = "";
+        let mut link_title = "";
+        for (n, attribute) in adoc_split_attributes(i).into_iter().enumerate() {
+            let is_quoted = attribute.starts_with('"') || attribute.starts_with('\'');
+            match attribute.split_once('=') {
+                Some((name, value)) if !is_quoted && name.trim() == "title" => {
+                    link_title = adoc_unquote(value);
+                }
+                Some(_) if !is_quoted => {}
+                _ if n == 0 => {
+                    link_text = adoc_unquote(attribute);
+                    link_text = link_text.strip_suffix('^').unwrap_or(link_text);
+                }
+                _ => {}
+            }
+        }
+        (link_text, link_title)
+    }
+
+    match i {
+        Cow::Borrowed(s) => {
+            let (te, ti) = parse(s);
+            (Cow::Borrowed(te), Cow::Borrowed(ti))
+        }
+        Cow::Owned(s) => {
+            let (te, ti) = parse(&s);
+            (Cow::Owned(te.to_string()), Cow::Owned(ti.to_string()))
+        }
+    }
+}
+
+/// Interprets the bracketed text of an image macro as attribute list and
+/// returns the _image alt text_.
+///
+/// Unlike link macros, the attribute list of an image macro is always
+/// parsed: the first positional attribute is the _image alt text_, the
+/// following ones are width and height. The named attribute `alt` overrides
+/// the positional one.
+fn adoc_image_attributes(i: Cow<str>) -> Cow<str> {
+    fn parse(i: &str) -> &str {
+        let mut img_alt = "";
+        for (n, attribute) in adoc_split_attributes(i).into_iter().enumerate() {
+            let is_quoted = attribute.starts_with('"') || attribute.starts_with('\'');
+            match attribute.split_once('=') {
+                Some((name, value)) if !is_quoted && name.trim() == "alt" => {
+                    return adoc_unquote(value);
+                }
+                Some(_) if !is_quoted => {}
+                _ if n == 0 => img_alt = adoc_unquote(attribute),
+                _ => {}
+            }
+        }
+        img_alt
+    }
+
+    match i {
+        Cow::Borrowed(s) => Cow::Borrowed(parse(s)),
+        Cow::Owned(s) => Cow::Owned(parse(&s).to_string()),
+    }
+}
+
+/// Splits an attribute list at commas outside quotes. The attributes are
+/// trimmed.
+fn adoc_split_attributes(i: &str) -> Vec<&str> {
+    let mut attributes = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, c) in i.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, ',') => {
+                attributes.push(i[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    attributes.push(i[start..].trim());
+    attributes
+}
+
+/// Strips enclosing quotes of an attribute value.
+fn adoc_unquote(s: &str) -> &str {
+    let s = s.trim();
+    if s.len() >= 2
+        && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')))
+    {
+        &s[1..s.len() - 1]
+    } else {
+        s
+    }
+}
+
 /// Takes all characters until the character `<pat>`. The escaped character
 /// `\<pat>` is taken as normal character. Then parser replaces the escaped character
 /// `\<pat>` with `<pat>`. A sequence of whitespaces including one newline, is
@@ -411,6 +664,88 @@ mod tests {
     use nom::error::ErrorKind;
     use std::matches;
 
+    #[test]
+    fn test_adoc_text2xref() {
+        assert_eq!(
+            adoc_text2xref("xref:section[]abc"),
+            Ok(("abc", (Cow::from(""), Cow::from("section"))))
+        );
+        assert_eq!(
+            adoc_text2xref("xref:other.adoc[Other document,role=ext]abc"),
+            Ok((
+                "abc",
+                (Cow::from("Other document"), Cow::from("other.adoc"))
+            ))
+        );
+        assert_eq!(
+            adoc_text2xref("<< section , link text >>abc"),
+            Ok(("abc", (Cow::from("link text"), Cow::from("section"))))
+        );
+        assert_eq!(
+            adoc_text2xref("<<Section Title>>abc"),
+            Ok(("abc", (Cow::from(""), Cow::from("Section Title"))))
+        );
+        assert_eq!(
+            adoc_text2xref("xref:section abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "xref:section abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            adoc_text2xref("<<>>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ">>abc",
+                ErrorKind::TakeTill1
+            )))
+        );
+        assert_eq!(
+            adoc_text2xref("<<section\n>>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\n>>abc",
+                ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn test_adoc_img() {
+        assert_eq!(
+            adoc_img("image:sunset.jpg[]abc"),
+            Ok(("abc", (Cow::from(""), Cow::from("sunset.jpg"))))
+        );
+        assert_eq!(
+            adoc_img("image::sunset.jpg[Sunset]abc"),
+            Ok(("abc", (Cow::from("Sunset"), Cow::from("sunset.jpg"))))
+        );
+        assert_eq!(
+            adoc_img("image:sunset.jpg[\"Sunset, red\",300]abc"),
+            Ok(("abc", (Cow::from("Sunset, red"), Cow::from("sunset.jpg"))))
+        );
+        assert_eq!(
+            adoc_img("image:sunset.jpg[width=300,alt=Sunset]abc"),
+            Ok(("abc", (Cow::from("Sunset"), Cow::from("sunset.jpg"))))
+        );
+        assert_eq!(
+            adoc_img("image:sunset.jpg[Sunset\nat sea]abc"),
+            Ok(("abc", (Cow::from("Sunset at sea"), Cow::from("sunset.jpg"))))
+        );
+        assert_eq!(
+            adoc_img("image:[Sunset]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "[Sunset]abc",
+                ErrorKind::TakeTill1
+            )))
+        );
+        assert_eq!(
+            adoc_img("image:sunset.jpg abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " abc",
+                ErrorKind::Char
+            )))
+        );
+    }
+
     #[test]
     fn test_adoc_text2dest() {
         assert_eq!(
@@ -461,6 +796,66 @@ mod tests {
             ))
         );
 
+        assert_eq!(
+            adoc_text2dest("http://getreu.net[My blog^]abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("My blog"),
+                    Cow::from("http://getreu.net"),
+                    Cow::from("")
+                )
+            ))
+        );
+
+        assert_eq!(
+            adoc_text2dest("http://getreu.net[My blog, my rules]abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("My blog, my rules"),
+                    Cow::from("http://getreu.net"),
+                    Cow::from("")
+                )
+            ))
+        );
+
+        assert_eq!(
+            adoc_text2dest(r#"http://getreu.net[My blog,window=_blank,title="Jens' blog"]abc"#),
+            Ok((
+                "abc",
+                (
+                    Cow::from("My blog"),
+                    Cow::from("http://getreu.net"),
+                    Cow::from("Jens' blog")
+                )
+            ))
+        );
+
+        assert_eq!(
+            adoc_text2dest(r#"link:http://getreu.net['My, blog',role=ext]abc"#),
+            Ok((
+                "abc",
+                (
+                    Cow::from("My, blog"),
+                    Cow::from("http://getreu.net"),
+                    Cow::from("")
+                )
+            ))
+        );
+
+        assert_eq!(
+            adoc_text2dest("http://getreu.net[window=_blank]abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("http://getreu.net"),
+                    Cow::from("http://getreu.net"),
+                    Cow::from("")
+                )
+            ))
+        );
+
         assert_eq!(
             adoc_text2dest("http://getreu.net[My\n    blog]abc"),
             Ok((
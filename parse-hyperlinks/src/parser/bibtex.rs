@@ -0,0 +1,255 @@
+//! This module implements parsers to extract hyperlinks from BibTeX entries.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::is_not;
+use nom::character::complete::char;
+use nom::character::complete::multispace0;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Resolver for _Digital Object Identifiers_.
+const DOI_RESOLVER: &str = "https://doi.org/";
+
+/// Wrapper around `bibtex_entry()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn bibtex_entry_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = bibtex_entry(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Parses a BibTeX entry and extracts the hyperlinks of its `url` and `doi`
+/// fields.
+///
+/// It returns either `Ok((i, links))` or some error, where every item of
+/// `links` is a tuple `(link_text, link_destination, link_title)`. The
+/// _link text_ is the citation key of the entry, the _link title_ the
+/// value of its `title` field. A DOI is resolved to `https://doi.org/<doi>`.
+/// The links appear in the same order as their fields. When the entry has
+/// neither a `url` nor a `doi` field, `links` is empty.
+///
+/// The entry must be enclosed in braces. Field values can be enclosed in
+/// braces or in double quotes, or consist of a number. Braces
+/// inside the value are removed, as are the backslashes of the escaped
+/// characters `\_`, `\%`, `\&`, `\#` and `\$`. Entries of type `@string`,
+/// `@preamble` and `@comment` are not recognized.
+///
+/// The parser expects to start at the entry start (`@`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::bibtex::bibtex_entry;
+/// use std::borrow::Cow;
+///
+/// let i = r#"@article{knuth84,
+///   author = "Donald E. Knuth",
+///   title  = {Literate {P}rogramming},
+///   doi    = {10.1093/comjnl/27.2.97},
+///   url    = {https://example.org/lp\_paper.pdf},
+/// }abc"#;
+///
+/// assert_eq!(
+///   bibtex_entry(i),
+///   Ok(("abc", vec![
+///       (Cow::from("knuth84"),
+///        Cow::from("https://doi.org/10.1093/comjnl/27.2.97"),
+///        Cow::from("Literate Programming")),
+///       (Cow::from("knuth84"),
+///        Cow::from("https://example.org/lp_paper.pdf"),
+///        Cow::from("Literate Programming")),
+///   ]))
+/// );
+/// ```
+pub fn bibtex_entry(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let (i, (_, key)) = nom::sequence::pair(
+        nom::combinator::verify(
+            nom::sequence::preceded(char('@'), nom::character::complete::alpha1),
+            |s: &str| {
+                !s.eq_ignore_ascii_case("string")
+                    && !s.eq_ignore_ascii_case("preamble")
+                    && !s.eq_ignore_ascii_case("comment")
+            },
+        ),
+        nom::sequence::preceded(
+            nom::sequence::pair(multispace0, char('{')),
+            nom::sequence::delimited(multispace0, is_not(",} \t\r\n"), multispace0),
+        ),
+    )(i)?;
+    let (i, fields) = nom::multi::many0(nom::sequence::preceded(
+        nom::sequence::pair(char(','), multispace0),
+        bibtex_field,
+    ))(i)?;
+    let (i, _) =
+        nom::sequence::tuple((nom::combinator::opt(char(',')), multispace0, char('}')))(i)?;
+
+    let mut title = Cow::Borrowed("");
+    let mut links = Vec::new();
+    for (name, value) in fields {
+        if name.eq_ignore_ascii_case("title") {
+            title = Cow::Owned(value.split_whitespace().collect::<Vec<&str>>().join(" "));
+        } else if name.eq_ignore_ascii_case("url") {
+            links.push(value);
+        } else if name.eq_ignore_ascii_case("doi") {
+            links.push(bibtex_resolve_doi(value));
+        }
+    }
+
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|dest| (Cow::Borrowed(key), dest, title.clone()))
+            .collect(),
+    ))
+}
+
+/// Parses one field `name = value` and returns `(name, value)`. The value
+/// is cleaned up as described in `bibtex_entry()`.
+fn bibtex_field(i: &str) -> nom::IResult<&str, (&str, Cow<str>)> {
+    let (i, (name, value)) = nom::sequence::separated_pair(
+        nom::bytes::complete::take_while1(|c: char| c.is_alphanumeric() || "_-:.".contains(c)),
+        nom::sequence::tuple((multispace0, char('='), multispace0)),
+        nom::multi::separated_list1(
+            nom::sequence::tuple((multispace0, char('#'), multispace0)),
+            alt((
+                nom::sequence::delimited(char('{'), bibtex_braced, char('}')),
+                nom::sequence::delimited(char('"'), bibtex_quoted, char('"')),
+                nom::character::complete::alphanumeric1,
+            )),
+        ),
+    )(i)?;
+    let (i, _) = multispace0(i)?;
+
+    let value = value.concat();
+    let value = if value.contains(['{', '}', '\\']) {
+        let mut cleaned = String::with_capacity(value.len());
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' => {}
+                '\\' if chars.peek().is_some_and(|n| "_%&#$".contains(*n)) => {}
+                c => cleaned.push(c),
+            }
+        }
+        cleaned
+    } else {
+        value
+    };
+
+    Ok((i, (name, Cow::Owned(value))))
+}
+
+/// Takes all characters until the closing brace, which balances the opening
+/// brace before. The enclosing braces are not part of the result.
+fn bibtex_braced(i: &str) -> nom::IResult<&str, &str> {
+    let mut depth = 0;
+    for (idx, c) in i.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Ok((&i[idx..], &i[..idx])),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        i,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Takes all characters until the next `"` that is not enclosed in braces.
+fn bibtex_quoted(i: &str) -> nom::IResult<&str, &str> {
+    let mut depth = 0;
+    for (idx, c) in i.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            '"' if depth == 0 => return Ok((&i[idx..], &i[..idx])),
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        i,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Prepends the DOI resolver unless the DOI is given as URL already. A
+/// `doi:` prefix is removed.
+fn bibtex_resolve_doi(doi: Cow<str>) -> Cow<str> {
+    let doi = doi.trim();
+    if doi.starts_with("http://") || doi.starts_with("https://") {
+        return Cow::Owned(doi.to_string());
+    }
+    let doi = doi.strip_prefix("doi:").map_or(doi, str::trim_start);
+    Cow::Owned(format!("{}{}", DOI_RESOLVER, doi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bibtex_entry() {
+        let expected = (
+            "abc",
+            vec![(
+                Cow::from("key"),
+                Cow::from("https://doi.org/10.1000/182"),
+                Cow::from("A title"),
+            )],
+        );
+        assert_eq!(
+            bibtex_entry("@Book{key, DOI=\"doi:10.1000/182\",Title=\"A\n  title\"}abc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            bibtex_entry("@misc { key ,\n  doi = {https://doi.org/10.1000/182},\n  title = \"A \" # \"title\"\n}abc").unwrap(),
+            expected
+        );
+
+        let expected = ("abc", vec![]);
+        assert_eq!(
+            bibtex_entry("@misc{key, year = 2020, note = {{n}ested {b}races}}abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            bibtex_entry("@string{ieee = \"IEEE\"}"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "@string{ieee = \"IEEE\"}",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            bibtex_entry("@misc{key, url = {https://example.org}"),
+            Err(nom::Err::Error(nom::error::Error::new("", ErrorKind::Char)))
+        );
+    }
+
+    #[test]
+    fn test_bibtex_field() {
+        assert_eq!(
+            bibtex_field("url = {https://example.org/a\\_b{\\%}20c} ,"),
+            Ok((",", ("url", Cow::from("https://example.org/a_b%20c"))))
+        );
+        assert_eq!(
+            bibtex_field("url=\"https://example.org/{\"}x\"abc"),
+            Ok(("abc", ("url", Cow::from("https://example.org/\"x"))))
+        );
+        assert_eq!(
+            bibtex_field("url = {https://example.org"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "{https://example.org",
+                ErrorKind::AlphaNumeric
+            )))
+        );
+    }
+}
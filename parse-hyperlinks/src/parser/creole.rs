@@ -0,0 +1,221 @@
+//! This module implements parsers for WikiCreole hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::is_not;
+use nom::bytes::complete::tag;
+use std::borrow::Cow;
+
+/// URL schemes recognized in _raw links_.
+const URL_SCHEMES: [&str; 5] = ["ftp://", "http://", "https://", "irc://", "mailto:"];
+
+/// Wrapper around `creole_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn creole_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = creole_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a WikiCreole _link_.
+///
+/// A [link](http://www.wikicreole.org/wiki/Creole1.0#section-Creole1.0-Links)
+/// is enclosed in double square brackets. The _link destination_ is either
+/// an internal page name or an URL. An optional _link text_ follows after a
+/// `|`. The link must not span more than one line. It returns either
+/// `Ok((i, (link_text, link_destination, Cow::from(""))))` or some error.
+/// When no _link text_ is given, the _link text_ is the same as the _link
+/// destination_.
+///
+/// The parser expects to start at the link start (`[[`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::creole::creole_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     creole_text2dest("[[http://www.wikicreole.org/|Visit the WikiCreole website]]abc"),
+///     Ok(("abc", (Cow::from("Visit the WikiCreole website"),
+///                 Cow::from("http://www.wikicreole.org/"),
+///                 Cow::from(""))))
+/// );
+/// assert_eq!(
+///     creole_text2dest("[[Go to my page]]abc"),
+///     Ok(("abc", (Cow::from("Go to my page"), Cow::from("Go to my page"), Cow::from(""))))
+/// );
+/// ```
+pub fn creole_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, (link_destination, link_text)) = nom::sequence::delimited(
+        tag("[["),
+        nom::sequence::pair(
+            nom::combinator::map(
+                nom::combinator::verify(is_not("|]\n\r"), |s: &str| !s.trim().is_empty()),
+                str::trim,
+            ),
+            nom::combinator::opt(nom::sequence::preceded(
+                tag("|"),
+                nom::combinator::map(
+                    nom::combinator::verify(nom::bytes::complete::take_until("]]"), |s: &str| {
+                        !s.contains(['\n', '\r'])
+                    }),
+                    str::trim,
+                ),
+            )),
+        ),
+        tag("]]"),
+    )(i)?;
+    let link_text = match link_text {
+        Some(t) if !t.is_empty() => t,
+        _ => link_destination,
+    };
+    Ok((
+        i,
+        (
+            Cow::from(link_text),
+            Cow::from(link_destination),
+            Cow::from(""),
+        ),
+    ))
+}
+
+/// Wrapper around `creole_rawlink()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn creole_rawlink_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = creole_rawlink(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a WikiCreole _raw link_, a free standing URL.
+///
+/// The URL ends before whitespace. According to the specification, a single
+/// punctuation character `,.?!:;"'` at the end of the URL is not considered
+/// to be part of the URL. It returns either
+/// `Ok((i, (link_text, link_destination, Cow::from(""))))` or some error.
+/// The _link text_ is the same as the _link destination_.
+///
+/// A raw link preceded by the escape character `~` must not be parsed as
+/// link. As this parser only sees the URL, this is up to the caller.
+///
+/// The parser expects to start at the first letter of the URL to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::creole::creole_rawlink;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     creole_rawlink("http://www.wikicreole.org/. abc"),
+///     Ok((". abc", (Cow::from("http://www.wikicreole.org/"),
+///                   Cow::from("http://www.wikicreole.org/"),
+///                   Cow::from(""))))
+/// );
+/// ```
+pub fn creole_rawlink(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (_, link) = is_not(" \t\r\n")(i)?;
+    let scheme = match URL_SCHEMES
+        .iter()
+        .find(|p| link.starts_with(*p) && link.len() > p.len())
+    {
+        Some(p) => p,
+        None => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                i,
+                nom::error::ErrorKind::Verify,
+            )))
+        }
+    };
+    let link = match link.strip_suffix(|c| ",.?!:;\"'".contains(c)) {
+        Some(l) if l.len() > scheme.len() => l,
+        _ => link,
+    };
+
+    Ok((
+        &i[link.len()..],
+        (Cow::Borrowed(link), Cow::Borrowed(link), Cow::Borrowed("")),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+
+    #[test]
+    fn test_creole_text2dest() {
+        let expected = ("abc", (Cow::from("Page"), Cow::from("Page"), Cow::from("")));
+        assert_eq!(creole_text2dest("[[Page]]abc").unwrap(), expected);
+        assert_eq!(creole_text2dest("[[ Page |]]abc").unwrap(), expected);
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("{{logo.png}}"),
+                Cow::from("https://example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            creole_text2dest("[[https://example.org|{{logo.png}}]]abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            creole_text2dest("[[Page|my\nlabel]]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "|my\nlabel]]abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            creole_text2dest("[[ |label]]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " |label]]abc",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_creole_rawlink() {
+        let expected = (
+            "",
+            (
+                Cow::from("ftp://example.org/file.txt"),
+                Cow::from("ftp://example.org/file.txt"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            creole_rawlink("ftp://example.org/file.txt").unwrap(),
+            expected
+        );
+
+        // Only one trailing punctuation character is removed.
+        let expected = (
+            ". abc",
+            (
+                Cow::from("https://example.org/?"),
+                Cow::from("https://example.org/?"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            creole_rawlink("https://example.org/?. abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            creole_rawlink("www.example.org abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "www.example.org abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            creole_rawlink("http:// abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "http:// abc",
+                ErrorKind::Verify
+            )))
+        );
+    }
+}
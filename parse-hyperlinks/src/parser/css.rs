@@ -0,0 +1,248 @@
+//! This module implements parsers for URL references in CSS stylesheets.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::char;
+use nom::character::complete::multispace0;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `css_url()` that packs the result in `Link::Text2Dest`.
+pub fn css_url_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = css_url(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a CSS `url(...)` token or an `@import` rule.
+///
+/// The [URL](https://www.w3.org/TR/css-values-4/#urls) can be quoted,
+/// `url("image.png")`, or unquoted, `url(image.png)`. An `@import` rule
+/// references the stylesheet either with a `url(...)` token or with a
+/// string: `@import "style.css"`. Only `@import` and the URL are consumed,
+/// media queries following the URL are not. Keywords are case insensitive.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The _link text_ is the same as the _link destination_.
+/// [Escape sequences](https://www.w3.org/TR/css-syntax-3/#escaping), e.g.
+/// `\)` or `\20 `, are resolved.
+///
+/// The parser expects to start at the token start (`u` or `@`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::css::css_url;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   css_url("url( 'images/bg image.png' );abc"),
+///   Ok((";abc", (Cow::from("images/bg image.png"), Cow::from("images/bg image.png"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   css_url(r#"url(images/bg\ image.png);abc"#),
+///   Ok((";abc", (Cow::from("images/bg image.png"), Cow::from("images/bg image.png"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   css_url(r#"@import "print.css" print;abc"#),
+///   Ok((" print;abc", (Cow::from("print.css"), Cow::from("print.css"), Cow::from(""))))
+/// );
+/// ```
+pub fn css_url(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, link_destination) = alt((
+        css_url_token,
+        nom::sequence::preceded(
+            nom::sequence::pair(tag_no_case("@import"), multispace0),
+            alt((css_url_token, css_string)),
+        ),
+    ))(i)?;
+    let link_destination = css_unescape(link_destination);
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+
+    Ok((
+        i,
+        (
+            link_destination.clone(),
+            link_destination,
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+/// Parses a `url(...)` token and returns its raw, still escaped, content.
+fn css_url_token(i: &str) -> nom::IResult<&str, &str> {
+    nom::sequence::delimited(
+        nom::sequence::pair(tag_no_case("url("), multispace0),
+        alt((css_string, css_unquoted_url)),
+        nom::sequence::pair(multispace0, char(')')),
+    )(i)
+}
+
+/// Parses a string enclosed in double or single quotes and returns its raw,
+/// still escaped, content. Unescaped newlines are not allowed.
+fn css_string(i: &str) -> nom::IResult<&str, &str> {
+    let (j, quote) = alt((char('"'), char('\'')))(i)?;
+    let mut chars = j.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '\n' | '\r' | '\x0C' => break,
+            c if c == quote => return Ok((&j[idx + 1..], &j[..idx])),
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        j,
+        ErrorKind::EscapedTransform,
+    )))
+}
+
+/// Parses the content of an unquoted `url(...)` token and returns it raw,
+/// still escaped. Whitespace, quotes and parentheses must be escaped.
+fn css_unquoted_url(i: &str) -> nom::IResult<&str, &str> {
+    nom::combinator::recognize(nom::multi::many0_count(alt((
+        nom::bytes::complete::is_not(" \t\r\n\x0C\"'()\\"),
+        // A hexadecimal escape sequence can be terminated by a whitespace.
+        nom::combinator::recognize(nom::sequence::tuple((
+            char('\\'),
+            nom::bytes::complete::take_while_m_n(1, 6, |c: char| c.is_ascii_hexdigit()),
+            nom::combinator::opt(alt((
+                nom::bytes::complete::tag("\r\n"),
+                nom::bytes::complete::take_while_m_n(1, 1, |c| " \t\r\n\x0C".contains(c)),
+            ))),
+        ))),
+        nom::combinator::recognize(nom::sequence::pair(
+            char('\\'),
+            nom::character::complete::none_of("\r\n\x0C"),
+        )),
+    ))))(i)
+}
+
+/// Resolves the escape sequences of a CSS string or URL. A backslash
+/// followed by up to six hexadecimal digits and an optional whitespace
+/// stands for the code point with this number, a backslash followed by a
+/// newline is removed and a backslash followed by any other character
+/// stands for this character.
+fn css_unescape(i: &str) -> Cow<str> {
+    if !i.contains('\\') {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut rest = i;
+    while let Some(idx) = rest.find('\\') {
+        s.push_str(&rest[..idx]);
+        rest = &rest[idx + 1..];
+        let hex_len = rest
+            .chars()
+            .take(6)
+            .take_while(char::is_ascii_hexdigit)
+            .count();
+        if hex_len > 0 {
+            let c = u32::from_str_radix(&rest[..hex_len], 16)
+                .ok()
+                .filter(|&n| n != 0)
+                .and_then(char::from_u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER);
+            s.push(c);
+            rest = &rest[hex_len..];
+            // One whitespace terminates the escape sequence.
+            rest = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix([' ', '\t', '\n', '\r', '\x0C']))
+                .unwrap_or(rest);
+            continue;
+        }
+        match rest.chars().next() {
+            Some('\r') => rest = rest.strip_prefix("\r\n").unwrap_or(&rest[1..]),
+            Some(c @ ('\n' | '\x0C')) => rest = &rest[c.len_utf8()..],
+            Some(c) => {
+                s.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+            None => {}
+        }
+    }
+    s.push_str(rest);
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_url() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("fonts/a \"b\".woff"),
+                Cow::from("fonts/a \"b\".woff"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            css_url("URL(\"fonts/a\\20\\\"b\\\".woff\")abc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            css_url("url(\n  fonts/a\\000020\\22 b\\\".woff\n)abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            ";abc",
+            (
+                Cow::from("https://example.org/style.css"),
+                Cow::from("https://example.org/style.css"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            css_url("@IMPORT url('https://example.org/style.css');abc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            css_url("@import'https://example.org/\\\nstyle.css';abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            css_url("url(a b)abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "url(a b)abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            css_url("url('a\nb')abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "url('a\nb')abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            css_url("url()abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "abc",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_css_unescape() {
+        assert_eq!(css_unescape("abc"), Cow::from("abc"));
+        assert_eq!(css_unescape("a\\62\r\nc"), Cow::from("abc"));
+        assert_eq!(
+            css_unescape("\\0 \\110000\\"),
+            Cow::from("\u{FFFD}\u{FFFD}")
+        );
+    }
+}
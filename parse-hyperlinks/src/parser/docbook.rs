@@ -0,0 +1,147 @@
+//! This module implements parsers for DocBook hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::xml_element_content;
+use crate::parser::xml::xml_start_tag;
+use crate::parser::xml::xml_text;
+use crate::parser::Link;
+use nom::error::Error;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `docbook_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn docbook_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = docbook_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a DocBook _hyperlink element_.
+///
+/// Recognized are the DocBook 4 element
+/// [`<ulink url="...">`](https://tdg.docbook.org/tdg/4.5/ulink.html) and the
+/// DocBook 5 element
+/// [`<link xlink:href="...">`](https://tdg.docbook.org/tdg/5.2/link.html).
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ is the element content converted to plain
+/// text: nested markup is removed, entities are decoded and whitespace is
+/// normalized. For empty elements, the _link text_ is the URL. The _link
+/// title_ is the value of the `xlink:title` attribute, if any. A `<link>`
+/// element without `xlink:href`, e.g. an internal `<link linkend="...">`,
+/// is not recognized.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::docbook::docbook_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   docbook_text2dest(r#"<ulink url="https://docbook.org/">The <emphasis>DocBook</emphasis> site</ulink>abc"#),
+///   Ok(("abc", (Cow::from("The DocBook site"), Cow::from("https://docbook.org/"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   docbook_text2dest(r#"<link xlink:href="https://docbook.org/" xlink:title="Home"/>abc"#),
+///   Ok(("abc", (Cow::from("https://docbook.org/"), Cow::from("https://docbook.org/"), Cow::from("Home"))))
+/// );
+/// ```
+pub fn docbook_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, attributes, is_empty)) = xml_start_tag(i)?;
+    let href_name = match name {
+        "ulink" => "url",
+        "link" => "xlink:href",
+        _ => return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag))),
+    };
+
+    let mut link_destination = Cow::Borrowed("");
+    let mut link_title = Cow::Borrowed("");
+    for (name, value) in attributes {
+        if name == href_name {
+            link_destination = value;
+        } else if name == "xlink:title" {
+            link_title = value;
+        }
+    }
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+
+    let (j, content) = if is_empty {
+        (j, "")
+    } else {
+        xml_element_content(j, name)?
+    };
+    let link_text = match xml_text(content) {
+        t if t.is_empty() => link_destination.clone(),
+        t => t,
+    };
+
+    Ok((j, (link_text, link_destination, link_title)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docbook_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("DocBook & more"),
+                Cow::from("https://docbook.org/?a=1&b=2"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            docbook_text2dest(
+                "<link xlink:href=\"https://docbook.org/?a=1&amp;b=2\">\n  DocBook &amp; more\n</link>abc"
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            docbook_text2dest(
+                "<ulink url='https://docbook.org/?a=1&amp;b=2'>DocBook <link linkend=\"x\">&amp;</link> more</ulink>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://docbook.org/"),
+                Cow::from("https://docbook.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            docbook_text2dest("<ulink url=\"https://docbook.org/\"></ulink>abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            docbook_text2dest("<link linkend=\"intro\">Introduction</link>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<link linkend=\"intro\">Introduction</link>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            docbook_text2dest("<a href=\"https://docbook.org/\">DocBook</a>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<a href=\"https://docbook.org/\">DocBook</a>abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            docbook_text2dest("<ulink url=\"https://docbook.org/\">DocBook abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "DocBook abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! This module implements parsers for DokuWiki hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::is_not;
+use nom::bytes::complete::tag;
+use std::borrow::Cow;
+
+/// Wrapper around `dokuwiki_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn dokuwiki_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = dokuwiki_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a DokuWiki _link_.
+///
+/// A [link](https://www.dokuwiki.org/wiki:syntax#links) is enclosed in
+/// double square brackets. An optional _link text_ follows the _link
+/// destination_ after a `|`. The link must not span more than one line. It
+/// returns either `Ok((i, (link_text, link_destination, Cow::from(""))))` or
+/// some error.
+///
+/// Unlike MediaWiki, DokuWiki distinguishes two kinds of destinations:
+/// * An _external link_ contains `://`, e.g. `[[https://example.org|label]]`.
+///   The _link destination_ is returned unchanged. Without _link text_, the
+///   _link text_ is the URL.
+/// * Otherwise, the destination is an _internal link_ to a page, whose
+///   namespaces are separated by colons, e.g. `[[wiki:syntax#links]]`.
+///   Like DokuWiki, the page name is converted to lower case and spaces are
+///   replaced by `_`. Without _link text_, the _link text_ is the page name
+///   without namespaces, or the section name, if there is no page name.
+///
+/// The parser expects to start at the link start (`[[`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::dokuwiki::dokuwiki_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     dokuwiki_text2dest("[[https://example.org|Example]]abc"),
+///     Ok(("abc", (Cow::from("Example"), Cow::from("https://example.org"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     dokuwiki_text2dest("[[Wiki:Syntax Page#links]]abc"),
+///     Ok(("abc", (Cow::from("syntax_page"), Cow::from("wiki:syntax_page#links"), Cow::from(""))))
+/// );
+/// ```
+pub fn dokuwiki_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, (link_destination, link_text)) = nom::sequence::delimited(
+        tag("[["),
+        nom::sequence::pair(
+            nom::combinator::map(
+                nom::combinator::verify(is_not("|]\n\r"), |s: &str| !s.trim().is_empty()),
+                str::trim,
+            ),
+            nom::combinator::opt(nom::sequence::preceded(
+                tag("|"),
+                nom::combinator::map(
+                    nom::combinator::verify(nom::bytes::complete::take_until("]]"), |s: &str| {
+                        !s.contains(['\n', '\r'])
+                    }),
+                    str::trim,
+                ),
+            )),
+        ),
+        tag("]]"),
+    )(i)?;
+    let link_text = link_text.filter(|t| !t.is_empty());
+
+    // External link.
+    if link_destination.contains("://") {
+        return Ok((
+            i,
+            (
+                Cow::Borrowed(link_text.unwrap_or(link_destination)),
+                Cow::Borrowed(link_destination),
+                Cow::Borrowed(""),
+            ),
+        ));
+    }
+
+    // Internal link.
+    let (page, section) = match link_destination.split_once('#') {
+        Some((p, s)) => (p.trim(), Some(s.trim())),
+        None => (link_destination, None),
+    };
+    let page = page.to_lowercase().replace(' ', "_");
+    let link_text = match link_text {
+        Some(t) => Cow::Borrowed(t),
+        None => match page.rsplit(':').next() {
+            Some(name) if !name.is_empty() => Cow::Owned(name.to_string()),
+            _ => Cow::Borrowed(section.unwrap_or_default()),
+        },
+    };
+    let link_destination = match section {
+        Some(s) => format!("{}#{}", page, s),
+        None => page,
+    };
+
+    Ok((
+        i,
+        (link_text, Cow::Owned(link_destination), Cow::Borrowed("")),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+
+    #[test]
+    fn test_dokuwiki_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/a b"),
+                Cow::from("https://example.org/a b"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            dokuwiki_text2dest("[[ https://example.org/a b |]]abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (Cow::from("page"), Cow::from("ns1:ns2:page"), Cow::from("")),
+        );
+        assert_eq!(dokuwiki_text2dest("[[ns1:ns2:page]]abc").unwrap(), expected);
+
+        let expected = (
+            "abc",
+            (Cow::from("{{logo.png}}"), Cow::from("start"), Cow::from("")),
+        );
+        assert_eq!(
+            dokuwiki_text2dest("[[Start|{{logo.png}}]]abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (Cow::from("intro"), Cow::from("#intro"), Cow::from("")),
+        );
+        assert_eq!(dokuwiki_text2dest("[[#intro]]abc").unwrap(), expected);
+
+        assert_eq!(
+            dokuwiki_text2dest("[[page|my\nlabel]]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "|my\nlabel]]abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            dokuwiki_text2dest("[[]]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "]]abc",
+                ErrorKind::IsNot
+            )))
+        );
+    }
+}
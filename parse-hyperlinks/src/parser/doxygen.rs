@@ -0,0 +1,245 @@
+//! This module implements parsers for Doxygen hyperlinks in C/C++ comments.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::html::html_text2dest;
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::multispace1;
+use nom::character::complete::one_of;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `doxygen_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn doxygen_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = doxygen_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a Doxygen `\link ... \endlink` or `\ref` command, or an HTML
+/// anchor `<a href="url">text</a>` in a documentation comment.
+///
+/// The [commands](https://www.doxygen.nl/manual/commands.html) start with
+/// `\` or `@`:
+/// * `\link target link text \endlink`: the _link text_ may span several
+///   lines,
+/// * `\ref target "link text"`: the quoted _link text_ is optional.
+///
+/// The target is a name, e.g. `MyClass::method(int)`, a file name or the
+/// label of a section. A full stop ending the target of `\ref` is not part of
+/// the target.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link destination_ is the target or the URL of the
+/// anchor. Without explicit _link text_, the _link text_ is the target. The
+/// _link title_ is the command name: `link`, `ref` or `a` for the anchor.
+/// Comment decorations at the beginning of the continuation lines of the
+/// _link text_ (`*`, `///`, `//!`) are removed and whitespace is normalized.
+///
+/// The parser expects to start at the command start (`\`, `@` or `<`) to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::doxygen::doxygen_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   doxygen_text2dest("\\link Stack::push() pushing\n * elements \\endlink abc"),
+///   Ok((" abc", (Cow::from("pushing elements"), Cow::from("Stack::push()"), Cow::from("link"))))
+/// );
+/// assert_eq!(
+///   doxygen_text2dest("@ref install_guide \"Installation\" abc"),
+///   Ok((" abc", (Cow::from("Installation"), Cow::from("install_guide"), Cow::from("ref"))))
+/// );
+/// assert_eq!(
+///   doxygen_text2dest("<a href=\"https://www.doxygen.nl/\">Doxygen</a> abc"),
+///   Ok((" abc", (Cow::from("Doxygen"), Cow::from("https://www.doxygen.nl/"), Cow::from("a"))))
+/// );
+/// ```
+pub fn doxygen_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    if i.starts_with('<') {
+        let (j, (link_text, link_destination, _)) = html_text2dest(i)?;
+        return Ok((j, (link_text, link_destination, Cow::Borrowed("a"))));
+    }
+
+    let (j, (prefix, name)) = nom::sequence::terminated(
+        nom::sequence::pair(one_of("\\@"), alt((tag("link"), tag("ref")))),
+        multispace1,
+    )(i)?;
+    let (j, target) = doxygen_target(j)?;
+
+    if name == "link" {
+        let end_tag = if prefix == '\\' {
+            "\\endlink"
+        } else {
+            "@endlink"
+        };
+        let end = j
+            .find(end_tag)
+            .ok_or_else(|| nom::Err::Error(nom::error::Error::new(j, ErrorKind::TakeUntil)))?;
+        let link_text = doxygen_clean_text(&j[..end]);
+        let link_text = if link_text.is_empty() {
+            Cow::Borrowed(target)
+        } else {
+            link_text
+        };
+        return Ok((
+            &j[end + end_tag.len()..],
+            (link_text, Cow::Borrowed(target), Cow::Borrowed(name)),
+        ));
+    }
+
+    // A full stop ends the sentence, not the target.
+    let (target, j) = match target.strip_suffix('.') {
+        Some(t) if !t.is_empty() => (t, &i[i.len() - j.len() - 1..]),
+        _ => (target, j),
+    };
+    let link_text: nom::IResult<&str, &str> = nom::sequence::preceded(
+        nom::character::complete::space1,
+        nom::sequence::delimited(
+            tag("\""),
+            nom::bytes::complete::take_till1(|c| c == '"' || c == '\n' || c == '\r'),
+            tag("\""),
+        ),
+    )(j);
+    match link_text {
+        Ok((j, link_text)) => Ok((
+            j,
+            (
+                doxygen_clean_text(link_text),
+                Cow::Borrowed(target),
+                Cow::Borrowed(name),
+            ),
+        )),
+        Err(_) => Ok((
+            j,
+            (
+                Cow::Borrowed(target),
+                Cow::Borrowed(target),
+                Cow::Borrowed(name),
+            ),
+        )),
+    }
+}
+
+/// Parses the target of a command: a word, which may contain a parameter
+/// list in balanced parentheses, e.g. `func(int, char *)`.
+fn doxygen_target(i: &str) -> nom::IResult<&str, &str> {
+    let mut depth = 0;
+    let mut end = i.len();
+    for (idx, c) in i.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = idx;
+                break;
+            }
+            ')' => depth -= 1,
+            c if depth == 0 && (c.is_whitespace() || c == '"' || c == '\\') => {
+                end = idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || end == 0 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+    Ok((&i[end..], &i[..end]))
+}
+
+/// Removes the comment decorations at the beginning of every line and
+/// replaces whitespace sequences by one space.
+fn doxygen_clean_text(i: &str) -> Cow<str> {
+    let i = i.trim();
+    if !i.contains(['\n', '\r', '\t', ' ']) {
+        return Cow::Borrowed(i);
+    }
+    let text = i
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("///")
+                .or_else(|| line.strip_prefix("//!"))
+                .or_else(|| line.strip_prefix('*').filter(|l| !l.starts_with('/')))
+                .unwrap_or(line)
+        })
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Cow::Owned(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doxygen_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("the push method"),
+                Cow::from("Stack<T>::push(const T &)"),
+                Cow::from("link"),
+            ),
+        );
+        assert_eq!(
+            doxygen_text2dest(
+                "@link Stack<T>::push(const T &) the\n/// push\n//!  method @endlinkabc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            ". abc",
+            (
+                Cow::from("main.cpp"),
+                Cow::from("main.cpp"),
+                Cow::from("ref"),
+            ),
+        );
+        assert_eq!(doxygen_text2dest("\\ref main.cpp. abc").unwrap(), expected);
+
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("fn(int)"),
+                Cow::from("fn(int)"),
+                Cow::from("link"),
+            ),
+        );
+        assert_eq!(
+            doxygen_text2dest("\\link fn(int)\\endlink\nabc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            doxygen_text2dest("\\link fn(int text \\endlink"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "fn(int text \\endlink",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            doxygen_text2dest("\\link Stack text @endlink"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " text @endlink",
+                ErrorKind::TakeUntil
+            )))
+        );
+        assert_eq!(
+            doxygen_text2dest("\\refitem Stack"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "item Stack",
+                ErrorKind::MultiSpace
+            )))
+        );
+    }
+}
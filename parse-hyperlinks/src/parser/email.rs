@@ -0,0 +1,239 @@
+//! This module implements parsers to extract hyperlinks from plain text
+//! email bodies.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::plaintext::plaintext_url;
+use crate::parser::Link;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Characters allowed in the local part of an email address, besides
+/// letters and digits.
+const EMAIL_LOCAL_PART_CHARS: &str = ".!#$%&'*+/=?^_`{|}~-";
+
+/// Wrapper around `email_body()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn email_body_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = email_body(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Scans a plain text email body and extracts bare URLs and email
+/// addresses.
+///
+/// The body is expected to be in the format of
+/// [RFC 5322](https://www.rfc-editor.org/rfc/rfc5322), i.e. without header.
+/// Quoted lines, starting with `>`, are skipped: their links belong to the
+/// quoted message. The scan ends at the signature separator line `-- `
+/// ([RFC 3676](https://www.rfc-editor.org/rfc/rfc3676#section-4.3)); also
+/// accepted is `--` as some mail clients strip trailing whitespace.
+///
+/// It returns `Ok((i, links))`, where `i` is the signature block, including
+/// the separator line, or empty if there is none. Every item of `links` is
+/// a tuple `(link_text, link_destination, Cow::from(""))`. URLs are
+/// recognized as described in `plaintext::plaintext_url()`. For email
+/// addresses, see `email_address()`. The links appear in the order of the
+/// body.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::email::email_body;
+/// use std::borrow::Cow;
+///
+/// let i = "Hi,\n\
+///          > see https://old.example.org/\n\
+///          the new site is https://example.org/. Questions: <info@example.org>\n\
+///          -- \n\
+///          Jane, www.example.org\n";
+///
+/// assert_eq!(
+///   email_body(i),
+///   Ok(("-- \nJane, www.example.org\n", vec![
+///       (Cow::from("https://example.org/"), Cow::from("https://example.org/"), Cow::from("")),
+///       (Cow::from("info@example.org"), Cow::from("mailto:info@example.org"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn email_body(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let mut links = Vec::new();
+    let mut rest = i;
+    while !rest.is_empty() {
+        let line_len = rest.find('\n').map_or(rest.len(), |idx| idx + 1);
+        let line = &rest[..line_len];
+        let content = line.trim_end_matches(['\r', '\n']);
+        if content == "-- " || content == "--" {
+            break;
+        }
+        rest = &rest[line_len..];
+        if content.trim_start().starts_with('>') {
+            continue;
+        }
+
+        let mut preceding: Option<char> = None;
+        let mut k = content;
+        while let Some(c) = k.chars().next() {
+            let is_boundary = match preceding {
+                None => true,
+                Some(p) => p.is_whitespace() || "*_~(\"'<[,;:".contains(p),
+            };
+            if is_boundary {
+                if let Ok((r, link)) = plaintext_url(k).or_else(|_| email_address(k)) {
+                    links.push(link);
+                    preceding = content[..content.len() - r.len()].chars().next_back();
+                    k = r;
+                    continue;
+                }
+            }
+            preceding = Some(c);
+            k = &k[c.len_utf8()..];
+        }
+    }
+
+    Ok((rest, links))
+}
+
+/// Wrapper around `email_address()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn email_address_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = email_address(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a bare email address `local@domain`, optionally preceded by
+/// `mailto:`.
+///
+/// The local part consists of letters, digits and ``.!#$%&'*+/=?^_`{|}~-``,
+/// the domain of at least two labels of letters, digits and `-`, separated
+/// by `.`. A trailing `.` is not part of the address.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The _link text_ is the address as written, the _link
+/// destination_ the address prefixed with `mailto:`, unless it is already.
+///
+/// The parser expects to start at the first letter of the address to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::email::email_address;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   email_address("jane.doe+list@mail.example.org. abc"),
+///   Ok((". abc", (Cow::from("jane.doe+list@mail.example.org"),
+///                 Cow::from("mailto:jane.doe+list@mail.example.org"),
+///                 Cow::from(""))))
+/// );
+/// ```
+pub fn email_address(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let error = || nom::Err::Error(nom::error::Error::new(i, ErrorKind::Verify));
+    let has_scheme = i
+        .get(..7)
+        .is_some_and(|s| s.eq_ignore_ascii_case("mailto:"));
+    let address = if has_scheme { &i[7..] } else { i };
+
+    let local_len = address
+        .find(|c: char| !c.is_ascii_alphanumeric() && !EMAIL_LOCAL_PART_CHARS.contains(c))
+        .unwrap_or(address.len());
+    let local = &address[..local_len];
+    let domain = address[local_len..].strip_prefix('@').ok_or_else(error)?;
+    if local.is_empty() || local.starts_with('.') || local.ends_with('.') {
+        return Err(error());
+    }
+    let domain_len = domain
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '.')
+        .unwrap_or(domain.len());
+    let domain = domain[..domain_len].trim_end_matches('.');
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2
+        || labels
+            .iter()
+            .any(|l| l.is_empty() || l.starts_with('-') || l.ends_with('-'))
+    {
+        return Err(error());
+    }
+
+    let len = address.len() - address[local_len + 1..].len() + domain.len();
+    let link_text = &address[..len];
+    let link_destination = if has_scheme {
+        Cow::Borrowed(&i[..len + 7])
+    } else {
+        Cow::Owned(format!("mailto:{}", link_text))
+    };
+
+    Ok((
+        &address[len..],
+        (
+            Cow::Borrowed(link_text),
+            link_destination,
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_body() {
+        let i = "\
+> https://quoted.example.org/\r
+  >> mailto:quoted@example.org\r
+Write to mailto:Jane@Example.org (or jane@example.org),\r
+not to x@y or bob@example.org-.\r
+--\r
+";
+        let expected = (
+            "--\r\n",
+            vec![
+                (
+                    Cow::from("Jane@Example.org"),
+                    Cow::from("mailto:Jane@Example.org"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("jane@example.org"),
+                    Cow::from("mailto:jane@example.org"),
+                    Cow::from(""),
+                ),
+            ],
+        );
+        assert_eq!(email_body(i).unwrap(), expected);
+
+        assert_eq!(email_body("").unwrap(), ("", vec![]));
+    }
+
+    #[test]
+    fn test_email_address() {
+        let expected = (
+            ">",
+            (
+                Cow::from("info@example.org"),
+                Cow::from("MAILTO:info@example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(email_address("MAILTO:info@example.org>").unwrap(), expected);
+
+        assert_eq!(
+            email_address(".jane@example.org"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".jane@example.org",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            email_address("jane@localhost"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "jane@localhost",
+                ErrorKind::Verify
+            )))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! This module implements parsers for hyperlinks in RSS and Atom feeds.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::xml_element_content;
+use crate::parser::xml::xml_start_tag;
+use crate::parser::xml::xml_text;
+use crate::parser::Link;
+use nom::error::Error;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `feed_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn feed_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = feed_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a `<link>` element of an RSS or Atom feed.
+///
+/// Two forms are recognized:
+/// * [RSS 2.0](https://www.rssboard.org/rss-specification): the URL is the
+///   element content, e.g. `<link>https://example.org/</link>`. CDATA
+///   sections are supported.
+/// * [Atom](https://www.rfc-editor.org/rfc/rfc4287#section-4.2.7): the URL
+///   is the value of the `href` attribute, e.g.
+///   `<link rel="self" href="https://example.org/feed.xml"/>`. This form is
+///   also recognized with the common namespace prefix: `<atom:link .../>`.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))`
+/// or some error. The _link title_ holds the link relation, the `rel`
+/// attribute. As required by the Atom specification, a missing `rel`
+/// attribute is interpreted as `alternate`. RSS links have no link
+/// relation: their _link title_ is empty. The _link text_ is the value of
+/// the Atom `title` attribute, or the URL if there is none.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::feed::feed_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   feed_text2dest("<link>https://example.org/</link>abc"),
+///   Ok(("abc", (Cow::from("https://example.org/"), Cow::from("https://example.org/"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   feed_text2dest(r#"<link rel="self" href="https://example.org/feed.xml"/>abc"#),
+///   Ok(("abc", (Cow::from("https://example.org/feed.xml"), Cow::from("https://example.org/feed.xml"), Cow::from("self"))))
+/// );
+/// ```
+pub fn feed_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, attributes, is_empty)) = xml_start_tag(i)?;
+    if name != "link" && name != "atom:link" {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    }
+
+    let mut href = None;
+    let mut rel = Cow::Borrowed("alternate");
+    let mut title = None;
+    for (name, value) in attributes {
+        match name {
+            "href" => href = Some(value),
+            "rel" => rel = value,
+            "title" => title = Some(value),
+            _ => {}
+        }
+    }
+
+    // Atom
+    if let Some(link_destination) = href {
+        let (j, _) = if is_empty {
+            (j, "")
+        } else {
+            xml_element_content(j, name)?
+        };
+        let link_text = title.unwrap_or_else(|| link_destination.clone());
+        return Ok((j, (link_text, link_destination, rel)));
+    }
+
+    // RSS
+    if is_empty {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let (j, content) = xml_element_content(j, name)?;
+    let link_destination = xml_text(content);
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+
+    Ok((
+        j,
+        (
+            link_destination.clone(),
+            link_destination,
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/?a=1&b=2"),
+                Cow::from("https://example.org/?a=1&b=2"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            feed_text2dest("<link>\n  https://example.org/?a=1&amp;b=2\n</link>abc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            feed_text2dest("<link><![CDATA[https://example.org/?a=1&b=2]]></link>abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("HTML version"),
+                Cow::from("https://example.org/"),
+                Cow::from("alternate"),
+            ),
+        );
+        assert_eq!(
+            feed_text2dest(
+                "<link type=\"text/html\" href=\"https://example.org/\" title=\"HTML version\"></link>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/feed.xml"),
+                Cow::from("https://example.org/feed.xml"),
+                Cow::from("self"),
+            ),
+        );
+        assert_eq!(
+            feed_text2dest(
+                "<atom:link href=\"https://example.org/feed.xml\" rel=\"self\" type=\"application/rss+xml\" />abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            feed_text2dest("<link/>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<link/>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            feed_text2dest("<link> </link>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<link> </link>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            feed_text2dest("<guid>https://example.org/</guid>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<guid>https://example.org/</guid>abc",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! This module implements parsers for Gemini gemtext link lines.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::is_not;
+use nom::bytes::complete::tag;
+use nom::character::complete::space0;
+use nom::character::complete::space1;
+use std::borrow::Cow;
+
+/// Wrapper around `gemtext_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn gemtext_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = gemtext_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a gemtext _link line_.
+///
+/// A [link line](https://geminiprotocol.net/docs/gemtext-specification.gmi)
+/// starts with `=>`, followed by optional whitespace, the URL and an
+/// optional, whitespace separated, user friendly _link text_ reaching to the
+/// end of the line. It returns either
+/// `Ok((i, (link_text, link_destination, Cow::from(""))))` or some error.
+/// When no _link text_ is given, the _link text_ is the same as the _link
+/// destination_. The line ending is not consumed.
+///
+/// The parser expects to start at the beginning of the line (`=>`) to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::gemtext::gemtext_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     gemtext_text2dest("=> gemini://example.org/path An example\nabc"),
+///     Ok(("\nabc", (Cow::from("An example"), Cow::from("gemini://example.org/path"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     gemtext_text2dest("=>/relative/path\nabc"),
+///     Ok(("\nabc", (Cow::from("/relative/path"), Cow::from("/relative/path"), Cow::from(""))))
+/// );
+/// ```
+pub fn gemtext_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, (link_destination, link_text)) = nom::sequence::preceded(
+        nom::sequence::pair(tag("=>"), space0),
+        nom::sequence::pair(
+            is_not(" \t\r\n"),
+            nom::combinator::opt(nom::sequence::preceded(
+                space1,
+                nom::combinator::map(is_not("\r\n"), str::trim_end),
+            )),
+        ),
+    )(i)?;
+    // Consume trailing whitespace, when there is no link text.
+    let (i, _) = space0(i)?;
+    let link_text = link_text.unwrap_or(link_destination);
+
+    Ok((
+        i,
+        (
+            Cow::Borrowed(link_text),
+            Cow::Borrowed(link_destination),
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+
+    #[test]
+    fn test_gemtext_text2dest() {
+        let expected = (
+            "",
+            (
+                Cow::from("Gemini \t home"),
+                Cow::from("gemini://geminiprotocol.net/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            gemtext_text2dest("=>\tgemini://geminiprotocol.net/ \tGemini \t home \t").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "\r\nabc",
+            (
+                Cow::from("https://example.org"),
+                Cow::from("https://example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            gemtext_text2dest("=> https://example.org  \r\nabc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            gemtext_text2dest("=> \nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\nabc",
+                ErrorKind::IsNot
+            )))
+        );
+        assert_eq!(
+            gemtext_text2dest(" => https://example.org"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " => https://example.org",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
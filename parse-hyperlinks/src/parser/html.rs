@@ -2,16 +2,126 @@
 #![allow(dead_code)]
 #![allow(clippy::type_complexity)]
 
+use crate::parser::parse::take_link;
 use crate::parser::Link;
 use html_escape::decode_html_entities;
 use nom::branch::alt;
 use nom::bytes::complete::is_not;
 use nom::bytes::complete::tag;
-use nom::character::complete::alphanumeric1;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace0;
+use nom::character::complete::multispace1;
 use nom::error::Error;
 use nom::error::ErrorKind;
 use std::borrow::Cow;
 
+/// Named character references, which browsers also recognize without the
+/// terminating `;`, e.g. `&amp` or `&copy`.
+const HTML_LEGACY_ENTITIES: &[&str] = &[
+    "AElig", "AMP", "Aacute", "Acirc", "Agrave", "Aring", "Atilde", "Auml", "COPY", "Ccedil",
+    "ETH", "Eacute", "Ecirc", "Egrave", "Euml", "GT", "Iacute", "Icirc", "Igrave", "Iuml", "LT",
+    "Ntilde", "Oacute", "Ocirc", "Ograve", "Oslash", "Otilde", "Ouml", "QUOT", "REG", "THORN",
+    "Uacute", "Ucirc", "Ugrave", "Uuml", "Yacute", "aacute", "acirc", "acute", "aelig", "agrave",
+    "amp", "aring", "atilde", "auml", "brvbar", "ccedil", "cedil", "cent", "copy", "curren", "deg",
+    "divide", "eacute", "ecirc", "egrave", "eth", "euml", "frac12", "frac14", "frac34", "gt",
+    "iacute", "icirc", "iexcl", "igrave", "iquest", "iuml", "laquo", "lt", "macr", "micro",
+    "middot", "nbsp", "not", "ntilde", "oacute", "ocirc", "ograve", "ordf", "ordm", "oslash",
+    "otilde", "ouml", "para", "plusmn", "pound", "quot", "raquo", "reg", "sect", "shy", "sup1",
+    "sup2", "sup3", "szlig", "thorn", "times", "uacute", "ucirc", "ugrave", "uml", "uuml",
+    "yacute", "yen", "yuml",
+];
+
+/// Code points replacing the numeric character references `&#128;` to
+/// `&#159;`, as in the Windows-1252 encoding.
+const HTML_C1_REPLACEMENTS: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// Block-level elements terminating an anchor without `</a>`.
+const HTML_BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Open Graph and Twitter card properties of `<meta>` elements with a URL as
+/// value.
+const HTML_META_URL_PROPERTIES: &[&str] = &[
+    "og:url",
+    "og:image",
+    "og:image:url",
+    "og:image:secure_url",
+    "og:video",
+    "og:video:url",
+    "og:video:secure_url",
+    "og:audio",
+    "og:audio:url",
+    "og:audio:secure_url",
+    "twitter:url",
+    "twitter:image",
+    "twitter:image:src",
+    "twitter:player",
+];
+
+/// Modes of `html_text2dest_mode()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlMode {
+    /// Malformed anchors are rejected, as by `html_text2dest()`.
+    #[default]
+    Strict,
+    /// Common mistakes in the start tag of anchors are tolerated and
+    /// reported as `HtmlWarning`.
+    Lenient,
+}
+
+/// A mistake in the start tag of an anchor, tolerated by
+/// `HtmlMode::Lenient`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlWarning<'a> {
+    /// The attribute with this name appears more than once. The first
+    /// occurrence counts.
+    DuplicateAttribute(&'a str),
+    /// The attribute with this name follows a quoted attribute value without
+    /// whitespace, e.g. `title` in `href="a"title="b"`.
+    MissingWhitespace(&'a str),
+    /// A `/` outside of attribute values, e.g. in `<a href="a"/>`, is
+    /// ignored.
+    StraySlash,
+}
+
 /// Wrapper around `html_text2dest()` that packs the result in
 /// `Link::Text2Dest`.
 pub fn html_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
@@ -22,6 +132,10 @@ pub fn html_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 /// Parse an HTML _inline hyperlink_.
 ///
 /// It returns either `Ok((i, (link_text, link_destination, link_title)))` or some error.
+/// Character references, e.g. `&amp;` or `&#x2F;`, are decoded the way a
+/// browser does. An anchor without closing tag `</a>` ends before the next
+/// `<a>` or before the next block-level element, e.g. `<p>`, whichever comes
+/// first.
 ///
 /// The parser expects to start at the link start (`<`) to succeed.
 /// ```
@@ -35,301 +149,2404 @@ pub fn html_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 /// );
 /// ```
 pub fn html_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
-    let (i, ((link_destination, link_title), link_text)) = nom::sequence::terminated(
-        nom::sequence::pair(
-            tag_a_opening,
-            alt((
-                nom::bytes::complete::take_until("</a>"),
-                nom::bytes::complete::take_until("</A>"),
-            )),
-        ),
-        // HTML is case insensitive. XHTML, that is being XML is case sensitive.
-        // Here we deal with HTML.
-        alt((tag("</a>"), tag("</A>"))),
-    )(i)?;
-    let link_text = decode_html_entities(link_text);
+    let (i, ((link_destination, link_title), link_text)) =
+        nom::sequence::pair(tag_a_opening, take_until_tag_a_closing)(i)?;
+    let link_text = html_decode_entities(link_text, false);
     Ok((i, (link_text, link_destination, link_title)))
 }
 
-/// Parses a `<a ...>` opening tag and returns
-/// either `Ok((i, (link_destination, link_title)))` or some error.
-fn tag_a_opening(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
-    nom::sequence::delimited(
-        // HTML is case insensitive. XHTML, that is being XML is case sensitive.
-        // Here we deal with HTML.
-        alt((tag("<a "), tag("<A "))),
-        nom::combinator::map_parser(is_not(">"), parse_attributes),
-        tag(">"),
-    )(i)
+/// Parses an HTML comment `<!-- ... -->` and returns its content.
+///
+/// A comment without `-->` is rejected, so that a stray `<!--` does not
+/// hide the rest of the input. Scanners use this parser to skip commented
+/// out links.
+/// ```
+/// use parse_hyperlinks::parser::html::html_comment;
+///
+/// assert_eq!(
+///   html_comment(r#"<!-- <a href="x">y</a> -->abc"#),
+///   Ok(("abc", r#" <a href="x">y</a> "#))
+/// );
+/// assert!(html_comment("<!-- abc").is_err());
+/// ```
+pub fn html_comment(i: &str) -> nom::IResult<&str, &str> {
+    let (j, _) = tag("<!--")(i)?;
+    let (k, comment) = nom::bytes::complete::take_until("-->")(j)?;
+    Ok((&k[3..], comment))
 }
 
-/// Parses attributes and returns `Ok((name, value))`.
-/// Boolean attributes are ignored, but silently consumed.
-fn attribute(i: &str) -> nom::IResult<&str, (&str, Cow<str>)> {
-    alt((
+/// Parse an HTML _inline hyperlink_ like `html_text2dest()` and also return
+/// all attributes of the `<a>` tag.
+///
+/// It returns either
+/// `Ok((i, ((link_text, link_destination, link_title), attributes)))` or some
+/// error. Every item of `attributes` is a tuple `(name, value)`, e.g.
+/// `("rel", "nofollow")`, in the order of appearance, including `href` and
+/// `title`. Boolean attributes, e.g. `download`, have an empty value.
+/// Attribute names are returned as written; HTML attribute names are case
+/// insensitive.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_text2dest_attributes;
+/// use std::borrow::Cow;
+///
+/// let (i, (link, attributes)) = html_text2dest_attributes(
+///     r#"<a href="file.pdf" rel="nofollow" target=_blank download>name</a>abc"#,
+/// )
+/// .unwrap();
+/// assert_eq!(i, "abc");
+/// assert_eq!(link, (Cow::from("name"), Cow::from("file.pdf"), Cow::from("")));
+/// assert_eq!(attributes, vec![
+///     ("href", Cow::from("file.pdf")),
+///     ("rel", Cow::from("nofollow")),
+///     ("target", Cow::from("_blank")),
+///     ("download", Cow::from("")),
+/// ]);
+/// ```
+pub fn html_text2dest_attributes(
+    i: &str,
+) -> nom::IResult<&str, ((Cow<str>, Cow<str>, Cow<str>), Vec<(&str, Cow<str>)>)> {
+    // Validates the tag and extracts `href` and `title`.
+    let (_, (link_destination, link_title)) = tag_a_opening(i)?;
+    let (i, attributes) = tag_opening(i, "a")?;
+    let (i, link_text) = take_until_tag_a_closing(i)?;
+    let link_text = html_decode_entities(link_text, false);
+    Ok((i, ((link_text, link_destination, link_title), attributes)))
+}
+
+/// Parse an HTML _inline hyperlink_ like `html_text2dest()` in the given
+/// `mode` and also return the mistakes tolerated.
+///
+/// It returns either `Ok((i, ((link_text, link_destination, link_title),
+/// warnings)))` or some error. `HtmlMode::Strict` behaves exactly like
+/// `html_text2dest()` and never returns warnings. `HtmlMode::Lenient`
+/// parses the start tag of the anchor like a browser, instead of rejecting
+/// the whole link:
+/// * attributes without whitespace in between, e.g. `href="a"title="b"`,
+///   are separated,
+/// * a stray `/`, e.g. in `<a/href="a">` or `<a href="a"/>`, is ignored,
+/// * of duplicated attributes, the first one counts,
+/// * attribute names may contain any character but whitespace, `/`, `=` and
+///   `>`.
+///
+/// Every tolerated mistake is reported in `warnings`. In both modes, tag and
+/// attribute names are case insensitive.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::{html_text2dest_mode, HtmlMode, HtmlWarning};
+/// use std::borrow::Cow;
+///
+/// let i = r#"<A HREF="a.html" href="b.html"title="t"/>text</A>abc"#;
+/// assert!(html_text2dest_mode(i, HtmlMode::Strict).is_err());
+/// assert_eq!(
+///   html_text2dest_mode(i, HtmlMode::Lenient),
+///   Ok(("abc", (
+///     (Cow::from("text"), Cow::from("a.html"), Cow::from("t")),
+///     vec![
+///       HtmlWarning::DuplicateAttribute("href"),
+///       HtmlWarning::MissingWhitespace("title"),
+///       HtmlWarning::StraySlash,
+///     ],
+///   )))
+/// );
+/// ```
+pub fn html_text2dest_mode(
+    i: &str,
+    mode: HtmlMode,
+) -> nom::IResult<&str, ((Cow<str>, Cow<str>, Cow<str>), Vec<HtmlWarning>)> {
+    if mode == HtmlMode::Strict {
+        let (i, link) = html_text2dest(i)?;
+        return Ok((i, (link, Vec::new())));
+    }
+
+    let (j, content) = nom::sequence::delimited(
         nom::sequence::pair(
-            nom::combinator::verify(alphanumeric1, |s: &str| {
-                nom::character::is_alphabetic(s.as_bytes()[0])
-            }),
-            alt((
-                nom::combinator::value(Cow::from(""), tag(r#"="""#)),
-                nom::combinator::value(Cow::from(""), tag(r#"=''"#)),
-                nom::combinator::map(
-                    nom::sequence::delimited(tag("=\""), is_not("\""), tag("\"")),
-                    |s: &str| decode_html_entities(s),
-                ),
-                nom::combinator::map(
-                    nom::sequence::delimited(tag("='"), is_not("'"), tag("'")),
-                    |s: &str| decode_html_entities(s),
-                ),
-                nom::combinator::map(nom::sequence::preceded(tag("="), is_not(" ")), |s: &str| {
-                    decode_html_entities(s)
-                }),
-            )),
-        ),
-        // Consume boolean attributes.
-        nom::combinator::value(
-            ("", Cow::from("")),
-            nom::combinator::verify(alphanumeric1, |s: &str| {
-                nom::character::is_alphabetic(s.as_bytes()[0])
-            }),
+            tag_no_case("<a"),
+            nom::combinator::peek(alt((multispace1, tag("/")))),
         ),
-    ))(i)
+        tag_content,
+        tag(">"),
+    )(i)?;
+    let (attributes, warnings) = lenient_attributes(content);
+    let link_destination = find_attribute(&attributes, "href");
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let link_title = find_attribute(&attributes, "title");
+    let (j, link_text) = take_until_tag_a_closing(j)?;
+    let link_text = html_decode_entities(link_text, false);
+    Ok((j, ((link_text, link_destination, link_title), warnings)))
 }
 
-/// Parses a whitespace separated list of attributes and returns a vector of (name, value).
-pub fn attribute_list<'a>(i: &'a str) -> nom::IResult<&'a str, Vec<(&'a str, Cow<str>)>> {
+/// Splits the content of a start tag into attributes the way browsers do and
+/// reports the mistakes found, see `html_text2dest_mode()`. Of duplicated
+/// attributes only the first is returned.
+fn lenient_attributes(i: &str) -> (Vec<(&str, Cow<str>)>, Vec<HtmlWarning>) {
+    let mut attributes: Vec<(&str, Cow<str>)> = Vec::new();
+    let mut warnings = Vec::new();
+    let mut k = i;
+    // Is the next attribute separated from the previous one?
+    let mut separated = true;
+    loop {
+        let rest = k.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        separated |= rest.len() != k.len();
+        k = rest;
+        if let Some(rest) = k.strip_prefix('/') {
+            warnings.push(HtmlWarning::StraySlash);
+            separated = true;
+            k = rest;
+            continue;
+        }
+        let end = k
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '=')
+            .unwrap_or(k.len());
+        if end == 0 {
+            match k.chars().next() {
+                // Skip a `=` without attribute name.
+                Some(c) => k = &k[c.len_utf8()..],
+                None => break,
+            }
+            continue;
+        }
+        let name = &k[..end];
+        k = &k[end..];
+        if !separated {
+            warnings.push(HtmlWarning::MissingWhitespace(name));
+        }
+        let value = match attribute_value(k) {
+            Ok((rest, value)) => {
+                k = rest;
+                separated = false;
+                value
+            }
+            Err(_) => Cow::Borrowed(""),
+        };
+        if attributes.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)) {
+            warnings.push(HtmlWarning::DuplicateAttribute(name));
+        } else {
+            attributes.push((name, value));
+        }
+    }
+    (attributes, warnings)
+}
+
+/// Wrapper around `html_text2dest_plain()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_text2dest_plain_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_text2dest_plain(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parse an HTML _inline hyperlink_ like `html_text2dest()`, but return the
+/// _link text_ as plain text.
+///
+/// Nested tags and comments in the _link text_ are removed, an image
+/// `<img alt="...">` is replaced by its alternative text. Character
+/// references are decoded and whitespace sequences are replaced by one
+/// space. Leading and trailing whitespace is removed.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::html::html_text2dest_plain;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_text2dest_plain(r#"<a href="destination"><strong>Bold</strong> name</a>abc"#),
+///   Ok(("abc", (Cow::from("Bold name"), Cow::from("destination"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   html_text2dest_plain(r#"<a href="destination"><img src="logo.png" alt="Logo"></a>abc"#),
+///   Ok(("abc", (Cow::from("Logo"), Cow::from("destination"), Cow::from(""))))
+/// );
+/// ```
+pub fn html_text2dest_plain(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, ((link_destination, link_title), link_text)) =
+        nom::sequence::pair(tag_a_opening, take_until_tag_a_closing)(i)?;
+    let link_text = html_plain_text(link_text);
+    Ok((i, (link_text, link_destination, link_title)))
+}
+
+/// Converts HTML into plain text: tags and comments are removed, images are
+/// replaced by their `alt` attribute, character references decoded and
+/// whitespace sequences replaced by one space.
+fn html_plain_text(i: &str) -> Cow<str> {
     let i = i.trim();
-    nom::multi::separated_list1(nom::character::complete::multispace1, attribute)(i)
+    if !i.contains(['<', '&', '\n', '\r', '\t', '\x0C']) && !i.contains("  ") {
+        return Cow::Borrowed(i);
+    }
+
+    let mut text = String::with_capacity(i.len());
+    let mut k = i;
+    while let Some(idx) = k.find('<') {
+        text.push_str(&html_decode_entities(&k[..idx], false));
+        let j = &k[idx..];
+        if let Ok((rest, _)) = html_comment(j) {
+            k = rest;
+            continue;
+        }
+        let is_tag = j[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        match nom::sequence::delimited(tag("<"), tag_content, tag(">"))(j) {
+            Ok((rest, content)) if is_tag => {
+                if let Ok((attributes, _)) = nom::sequence::pair(
+                    tag_no_case::<_, _, Error<&str>>("img"),
+                    multispace1,
+                )(content)
+                {
+                    if let Ok((_, attributes)) = attribute_list(attributes) {
+                        if let Some((_, alt)) = attributes
+                            .into_iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case("alt"))
+                        {
+                            text.push_str(&alt);
+                        }
+                    }
+                }
+                k = rest;
+            }
+            // Not a tag.
+            _ => {
+                text.push('<');
+                k = &j[1..];
+            }
+        }
+    }
+    text.push_str(&html_decode_entities(k, false));
+
+    Cow::Owned(text.split_whitespace().collect::<Vec<&str>>().join(" "))
 }
 
-/// Extracts the `href` and `title` attributes and returns
-/// `Ok((link_destination, link_title))`. `link_title` can be empty,
-/// `link_destination` not.
-fn parse_attributes(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
-    let (i, attributes) = attribute_list(i)?;
-    let mut href = Cow::Borrowed("");
-    let mut title = Cow::Borrowed("");
+/// Wrapper around `html_link_lossy()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn html_link_lossy_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = html_link_lossy(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
 
-    for (name, value) in attributes {
-        if name == "href" {
-            // Make sure `href` is empty, it can appear only
-            // once.
-            if !(&*href).is_empty() {
-                return Err(nom::Err::Error(Error::new(name, ErrorKind::ManyMN)));
+/// Scans an HTML document with a forgiving tokenizer and extracts all
+/// anchors `<a href="...">` that can be recovered, even from broken markup.
+///
+/// Unlike `html_text2dest()`, which matches a well-formed anchor as a
+/// whole, this scanner processes the document tag by tag, the way a browser
+/// does:
+/// * an anchor ends at its `</a>`, at the next `<a>` (anchors cannot be
+///   nested) or at the end of the input,
+/// * unbalanced or unknown tags are ignored,
+/// * start tags are parsed like in `HtmlMode::Lenient`,
+/// * a `<` which does not start a tag is text,
+/// * comments and the content of `<script>` and `<style>` are skipped.
+///
+/// It returns `Ok(("", links))`, where every item of `links` is a tuple
+/// `(link_text, link_destination, link_title)`. The _link text_ is plain
+/// text as in `html_text2dest_plain()`: tags are removed, images are
+/// replaced by their `alt` text and whitespace is collapsed. Anchors without
+/// `href` are skipped. The links appear in document order. The whole input
+/// is consumed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_link_lossy;
+/// use std::borrow::Cow;
+///
+/// let i = r#"<p><a href="a.html">one <b>bold</a></b>
+/// <li><a href="b.html" title="Two">two
+/// <li><a href="c.html"><img src="c.png" alt="three"></a>"#;
+///
+/// assert_eq!(
+///   html_link_lossy(i),
+///   Ok(("", vec![
+///       (Cow::from("one bold"), Cow::from("a.html"), Cow::from("")),
+///       (Cow::from("two"), Cow::from("b.html"), Cow::from("Two")),
+///       (Cow::from("three"), Cow::from("c.html"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn html_link_lossy(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let mut links = Vec::new();
+    // The text, the destination and the title of the open anchor.
+    let mut anchor: Option<(String, Cow<str>, Cow<str>)> = None;
+    fn close<'a>(
+        (text, link_destination, link_title): (String, Cow<'a, str>, Cow<'a, str>),
+    ) -> (Cow<'a, str>, Cow<'a, str>, Cow<'a, str>) {
+        let link_text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        (Cow::Owned(link_text), link_destination, link_title)
+    }
+
+    let mut k = i;
+    while !k.is_empty() {
+        let idx = k.find('<').unwrap_or(k.len());
+        if let Some((text, _, _)) = &mut anchor {
+            text.push_str(&html_decode_entities(&k[..idx], false));
+        }
+        k = &k[idx..];
+        if k.is_empty() {
+            break;
+        }
+
+        if let Ok((rest, _)) = html_comment(k) {
+            k = rest;
+            continue;
+        }
+        // Markup declarations and processing instructions, e.g. `<!DOCTYPE html>`.
+        if k.starts_with("<!") || k.starts_with("<?") {
+            k = k.find('>').map_or("", |end| &k[end + 1..]);
+            continue;
+        }
+        let (is_end_tag, rest) = match k[1..].strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, &k[1..]),
+        };
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .unwrap_or(rest.len());
+        let name = &rest[..len];
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // Not a tag.
+            if let Some((text, _, _)) = &mut anchor {
+                text.push('<');
             }
-            href = value;
-        } else if name == "title" {
-            // Make sure `title` is empty, it can appear only
-            // once.
-            if !(&*title).is_empty() {
-                return Err(nom::Err::Error(Error::new(name, ErrorKind::ManyMN)));
+            k = &k[1..];
+            continue;
+        }
+        // A tag without `>` reaches to the end of the input.
+        let rest = &rest[len..];
+        let (rest, content) = match rest.strip_prefix('>') {
+            Some(rest) => (rest, ""),
+            None => match tag_content(rest) {
+                Ok((rest, content)) => (&rest[1..], content),
+                Err(_) => ("", rest),
+            },
+        };
+        k = rest;
+
+        if is_end_tag {
+            if name.eq_ignore_ascii_case("a") {
+                links.extend(anchor.take().map(close));
             }
-            title = value;
+        } else if name.eq_ignore_ascii_case("a") {
+            links.extend(anchor.take().map(close));
+            let (attributes, _) = lenient_attributes(content);
+            let link_destination = find_attribute(&attributes, "href");
+            if !link_destination.is_empty() {
+                let link_title = find_attribute(&attributes, "title");
+                anchor = Some((String::new(), link_destination, link_title));
+            }
+        } else if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+            // Skip the raw text up to the end tag.
+            let mut end = k.len();
+            let mut j = k;
+            while let Some(idx) = j.find("</") {
+                j = &j[idx + 2..];
+                if j.get(..name.len())
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                {
+                    end = k.len() - j.len() - 2;
+                    break;
+                }
+            }
+            k = &k[end..];
+        } else if let Some((text, _, _)) = &mut anchor {
+            if name.eq_ignore_ascii_case("img") {
+                let (attributes, _) = lenient_attributes(content);
+                text.push_str(&find_attribute(&attributes, "alt"));
+            } else if name.eq_ignore_ascii_case("br") {
+                text.push(' ');
+            }
+        }
+    }
+    links.extend(anchor.map(close));
+
+    Ok(("", links))
+}
+
+/// Wrapper around `html_img()` that packs the result in `Link::Image`. The
+/// _image title_ is dropped.
+pub fn html_img_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (alt, src, _)) = html_img(i)?;
+    Ok((i, Link::Image(alt, src)))
+}
+
+/// Parses an HTML `<img>` element.
+///
+/// `<img>` is a void element: it has no end tag, a self-closing slash
+/// `<img ... />` is optional. It returns either
+/// `Ok((i, (img_alt, img_src, img_title)))` or some error. `img_alt` and
+/// `img_title` are the values of the `alt` and `title` attributes and can be
+/// empty, `img_src` is the value of the `src` attribute and not. When an
+/// attribute is given more than once, the first one counts, as in browsers.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_img;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_img(r#"<img src="logo.png" alt="Logo" title="Our logo"/>abc"#),
+///   Ok(("abc", (Cow::from("Logo"), Cow::from("logo.png"), Cow::from("Our logo"))))
+/// );
+/// ```
+pub fn html_img(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "img")?;
+    let img_src = find_attribute(&attributes, "src");
+    if img_src.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((
+        j,
+        (
+            find_attribute(&attributes, "alt"),
+            img_src,
+            find_attribute(&attributes, "title"),
+        ),
+    ))
+}
+
+/// Wrapper around `html_srcset()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn html_srcset_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = html_srcset(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Parses the start tag of an `<img>` or `<source>` element and splits the
+/// image candidates of its `srcset` attribute.
+///
+/// A responsive image declares several URLs with their
+/// [descriptors](https://html.spec.whatwg.org/multipage/images.html#srcset-attributes),
+/// a pixel density `2x` or a width `640w`, separated by commas:
+/// `srcset="a.jpg 1x, b.jpg 2x"`. The descriptor is optional. Commas inside
+/// a URL are allowed, as long as the URL does not end with a comma.
+///
+/// It returns either `Ok((i, links))` or some error. Every item of `links`
+/// is a tuple `(link_text, link_destination, link_title)` with one
+/// candidate: the _link text_ and the _link destination_ are the URL, the
+/// _link title_ is the descriptor or the empty string. The `src` attribute
+/// is not part of the result, see `html_img()`. An element without
+/// candidates is rejected.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_srcset;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_srcset(r#"<img src="a.jpg" srcset="a.jpg, b.jpg 2x">abc"#),
+///   Ok(("abc", vec![
+///     (Cow::from("a.jpg"), Cow::from("a.jpg"), Cow::from("")),
+///     (Cow::from("b.jpg"), Cow::from("b.jpg"), Cow::from("2x")),
+///   ]))
+/// );
+/// ```
+pub fn html_srcset(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let (j, attributes) = alt((|i| tag_opening(i, "img"), |i| tag_opening(i, "source")))(i)?;
+    let links: Vec<(Cow<str>, Cow<str>, Cow<str>)> = match find_attribute(&attributes, "srcset") {
+        Cow::Borrowed(srcset) => srcset_candidates(srcset)
+            .into_iter()
+            .map(|(url, descriptor)| {
+                (
+                    Cow::Borrowed(url),
+                    Cow::Borrowed(url),
+                    Cow::Borrowed(descriptor),
+                )
+            })
+            .collect(),
+        Cow::Owned(srcset) => srcset_candidates(&srcset)
+            .into_iter()
+            .map(|(url, descriptor)| {
+                (
+                    Cow::Owned(url.to_string()),
+                    Cow::Owned(url.to_string()),
+                    Cow::Owned(descriptor.to_string()),
+                )
+            })
+            .collect(),
+    };
+    if links.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((j, links))
+}
+
+/// Splits the value of a `srcset` attribute into `(url, descriptor)`
+/// tuples, following the parsing rules of the HTML standard. Descriptors
+/// end at the next comma outside of parentheses.
+fn srcset_candidates(i: &str) -> Vec<(&str, &str)> {
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let mut candidates = Vec::new();
+    let mut k = i;
+    loop {
+        k = k.trim_start_matches(|c: char| is_space(c) || c == ',');
+        if k.is_empty() {
+            break;
+        }
+        let end = k.find(is_space).unwrap_or(k.len());
+        let url = &k[..end];
+        k = &k[end..];
+
+        // A URL ending with a comma has no descriptor.
+        let descriptor = if url.ends_with(',') {
+            ""
+        } else {
+            let mut depth = 0;
+            let end = k
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' if depth > 0 => depth -= 1,
+                        ',' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                })
+                .map_or(k.len(), |(idx, _)| idx);
+            let descriptor = k[..end].trim_matches(is_space);
+            k = &k[end..];
+            descriptor
+        };
+        let url = url.trim_end_matches(',');
+        if !url.is_empty() {
+            candidates.push((url, descriptor));
+        }
+    }
+    candidates
+}
+
+/// Wrapper around `html_rel2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_rel2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_rel2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an HTML `<link>` element, as found in the document head, e.g.
+/// `<link rel="canonical" href="https://example.org/">`.
+///
+/// `<link>` is a void element: it has no end tag, a self-closing slash is
+/// optional. Both attributes, `rel` and `href`, are required. It returns
+/// either `Ok((i, (link_text, link_destination, link_title)))` or some
+/// error. The _link title_ is the value of `rel`, e.g. `stylesheet`,
+/// `canonical`, `icon` or `alternate`, with whitespace sequences replaced by
+/// one space, so that the caller can tell apart the kinds of links. The
+/// _link text_ is the value of the `title` attribute, e.g. the name of a
+/// feed, or the _link destination_ if there is none.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_rel2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_rel2dest(r#"<link rel="alternate" type="application/rss+xml" title="News" href="/feed.xml">abc"#),
+///   Ok(("abc", (Cow::from("News"), Cow::from("/feed.xml"), Cow::from("alternate"))))
+/// );
+/// assert_eq!(
+///   html_rel2dest(r#"<link rel="shortcut  icon" href="/favicon.ico" />abc"#),
+///   Ok(("abc", (Cow::from("/favicon.ico"), Cow::from("/favicon.ico"), Cow::from("shortcut icon"))))
+/// );
+/// ```
+pub fn html_rel2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "link")?;
+    let link_destination = find_attribute(&attributes, "href");
+    let rel = find_attribute(&attributes, "rel");
+    if link_destination.is_empty() || rel.trim().is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let normalized = rel.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let link_title = if normalized == rel {
+        rel
+    } else {
+        Cow::Owned(normalized)
+    };
+    let link_text = match find_attribute(&attributes, "title") {
+        title if title.is_empty() => link_destination.clone(),
+        title => title,
+    };
+    Ok((j, (link_text, link_destination, link_title)))
+}
+
+/// Wrapper around `html_area2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_area2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_area2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an HTML `<area>` element, a hyperlink of an image map `<map>`.
+///
+/// `<area>` is a void element: it has no end tag, a self-closing slash is
+/// optional. It returns either
+/// `Ok((i, (link_text, link_destination, link_title)))` or some error. The
+/// _link text_ is the value of the `alt` attribute, which replaces the
+/// image region as link text, the _link destination_ is the value of the
+/// `href` attribute and the _link title_ the value of the `title`
+/// attribute. An `<area>` without `href` is no hyperlink and is rejected.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_area2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_area2dest(r#"<area shape="rect" coords="0,0,82,126" href="sun.html" alt="Sun">abc"#),
+///   Ok(("abc", (Cow::from("Sun"), Cow::from("sun.html"), Cow::from(""))))
+/// );
+/// ```
+pub fn html_area2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "area")?;
+    let link_destination = find_attribute(&attributes, "href");
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((
+        j,
+        (
+            find_attribute(&attributes, "alt"),
+            link_destination,
+            find_attribute(&attributes, "title"),
+        ),
+    ))
+}
+
+/// Wrapper around `html_src()` that packs the result in `Link::Text2Dest`.
+pub fn html_src_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_src(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses the start tag of an element embedding an external resource with
+/// a `src` attribute: `<iframe>`, `<script>`, `<source>` or `<track>`.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link destination_ is the value of the `src` attribute,
+/// the _link title_ is the element name in lowercase, e.g. `script`. The
+/// _link text_ is the value of the `label` attribute of `<track>` or the
+/// value of the `title` attribute of the other elements, and the _link
+/// destination_ if there is none. Only the start tag is consumed, not the
+/// content or the end tag of `<iframe>` and `<script>`. Elements without
+/// `src`, e.g. inline scripts, are rejected.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_src;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_src(r#"<script src="js/app.js" defer></script>abc"#),
+///   Ok(("</script>abc", (Cow::from("js/app.js"), Cow::from("js/app.js"), Cow::from("script"))))
+/// );
+/// assert_eq!(
+///   html_src(r#"<track kind="captions" src="en.vtt" srclang="en" label="English">abc"#),
+///   Ok(("abc", (Cow::from("English"), Cow::from("en.vtt"), Cow::from("track"))))
+/// );
+/// ```
+pub fn html_src(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let mut res = Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    for name in ["iframe", "script", "source", "track"] {
+        if let Ok((j, attributes)) = tag_opening(i, name) {
+            res = Ok((j, name, attributes));
+            break;
         }
     }
+    let (j, name, attributes) = res?;
+
+    let link_destination = find_attribute(&attributes, "src");
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let link_text = find_attribute(&attributes, if name == "track" { "label" } else { "title" });
+    let link_text = if link_text.is_empty() {
+        link_destination.clone()
+    } else {
+        link_text
+    };
+    Ok((j, (link_text, link_destination, Cow::Borrowed(name))))
+}
+
+/// Wrapper around `html_form2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_form2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_form2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses the start tag of an HTML `<form>` element and returns its
+/// submission endpoint.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ and the _link destination_ are the value of
+/// the `action` attribute. The _link title_ is the submission method in
+/// lowercase: `get`, `post` or `dialog`. As in browsers, a missing or
+/// unknown `method` stands for `get`. A form without `action` submits to
+/// the document itself and is rejected. Only the start tag is consumed.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_form2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_form2dest(r#"<form action="/login" method="POST">abc"#),
+///   Ok(("abc", (Cow::from("/login"), Cow::from("/login"), Cow::from("post"))))
+/// );
+/// assert_eq!(
+///   html_form2dest(r#"<form action="https://example.org/search">abc"#),
+///   Ok(("abc", (Cow::from("https://example.org/search"),
+///               Cow::from("https://example.org/search"),
+///               Cow::from("get"))))
+/// );
+/// ```
+pub fn html_form2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "form")?;
+    let link_destination = find_attribute(&attributes, "action");
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let method = find_attribute(&attributes, "method");
+    let link_title = ["post", "dialog"]
+        .into_iter()
+        .find(|m| method.trim().eq_ignore_ascii_case(m))
+        .unwrap_or("get");
+    Ok((
+        j,
+        (
+            link_destination.clone(),
+            link_destination,
+            Cow::Borrowed(link_title),
+        ),
+    ))
+}
+
+/// Wrapper around `html_refresh2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_refresh2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_refresh2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a redirect `<meta http-equiv="refresh" content="5; url=...">`.
+///
+/// The `content` attribute is interpreted as in browsers, see the
+/// [declarative refresh](https://html.spec.whatwg.org/multipage/semantics.html#shared-declarative-refresh-steps)
+/// steps: the delay in seconds may have a fractional part, the separator
+/// between delay and URL can be `;`, `,` or whitespace, the `url=` prefix is
+/// optional and case insensitive and the URL may be quoted, e.g.
+/// `0,URL='target.html'`. Everything after a closing quote is ignored. A
+/// `content` without URL reloads the current page and is rejected.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ and the _link destination_ are the URL, the
+/// _link title_ is the delay in seconds without fractional part.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_refresh2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_refresh2dest(r#"<meta http-equiv="refresh" content="5; url=https://example.org/">abc"#),
+///   Ok(("abc", (Cow::from("https://example.org/"), Cow::from("https://example.org/"), Cow::from("5"))))
+/// );
+/// assert_eq!(
+///   html_refresh2dest(r#"<meta content="0;URL='new.html'" http-equiv="Refresh">abc"#),
+///   Ok(("abc", (Cow::from("new.html"), Cow::from("new.html"), Cow::from("0"))))
+/// );
+/// ```
+pub fn html_refresh2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "meta")?;
+    if !find_attribute(&attributes, "http-equiv")
+        .trim()
+        .eq_ignore_ascii_case("refresh")
+    {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    }
+    let res = match find_attribute(&attributes, "content") {
+        Cow::Borrowed(content) => {
+            refresh_content(content).map(|(delay, url)| (Cow::Borrowed(delay), Cow::Borrowed(url)))
+        }
+        Cow::Owned(content) => refresh_content(&content)
+            .map(|(delay, url)| (Cow::Owned(delay.to_string()), Cow::Owned(url.to_string()))),
+    };
+    let (link_title, link_destination) =
+        res.ok_or_else(|| nom::Err::Error(Error::new(i, ErrorKind::Verify)))?;
+    Ok((j, (link_destination.clone(), link_destination, link_title)))
+}
+
+/// Splits the `content` attribute of a refresh `<meta>` element into
+/// `(delay, url)`. Returns `None`, if the delay is missing or malformed or
+/// if there is no URL.
+fn refresh_content(i: &str) -> Option<(&str, &str)> {
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let k = i.trim_start_matches(is_space);
+    let digits = k.find(|c: char| !c.is_ascii_digit()).unwrap_or(k.len());
+    let (delay, k) = k.split_at(digits);
+    if delay.is_empty() && !k.starts_with('.') {
+        return None;
+    }
+    // The fractional part is ignored.
+    let k = k.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if !(k.is_empty() || k.starts_with(|c: char| is_space(c) || c == ';' || c == ',')) {
+        return None;
+    }
+    let k = k.trim_start_matches(is_space);
+    let k = k.strip_prefix([';', ',']).unwrap_or(k);
+    let k = k.trim_start_matches(is_space);
+
+    // The prefix `url=` is optional.
+    let k = k
+        .get(..3)
+        .filter(|p| p.eq_ignore_ascii_case("url"))
+        .and_then(|_| k[3..].trim_start_matches(is_space).strip_prefix('='))
+        .map_or(k, |k| k.trim_start_matches(is_space));
+
+    let url = match k.strip_prefix(['\'', '"']) {
+        Some(quoted) => {
+            let quote = &k[..1];
+            quoted.split(quote).next().unwrap_or_default()
+        }
+        None => k,
+    };
+    let url = url.trim_matches(|c: char| c <= ' ');
+    if url.is_empty() {
+        return None;
+    }
+    Some((if delay.is_empty() { "0" } else { delay }, url))
+}
+
+/// Wrapper around `html_meta2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn html_meta2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = html_meta2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an [Open Graph](https://ogp.me/) or Twitter card `<meta>` element
+/// with a URL, e.g. `<meta property="og:image" content="...">` or
+/// `<meta name="twitter:image" content="...">`.
+///
+/// Recognized properties are `og:url`, `og:image`, `og:video` and `og:audio`
+/// with their `:url` and `:secure_url` variants, `twitter:url`,
+/// `twitter:image`, `twitter:image:src` and `twitter:player`. As many sites
+/// mix them up, the property name is taken from the `property` or from the
+/// `name` attribute for both vocabularies.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ is the property name in lowercase, the _link
+/// destination_ is the value of the `content` attribute. The _link title_ is
+/// always the empty string.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_meta2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_meta2dest(r#"<meta property="og:image" content="https://example.org/a.png">abc"#),
+///   Ok(("abc", (Cow::from("og:image"), Cow::from("https://example.org/a.png"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   html_meta2dest(r#"<meta name="twitter:image" content="https://example.org/b.png" />abc"#),
+///   Ok(("abc", (Cow::from("twitter:image"), Cow::from("https://example.org/b.png"), Cow::from(""))))
+/// );
+/// ```
+pub fn html_meta2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, attributes) = tag_opening(i, "meta")?;
+    let link_text = ["property", "name"]
+        .into_iter()
+        .map(|name| find_attribute(&attributes, name))
+        .find_map(|property| {
+            HTML_META_URL_PROPERTIES
+                .iter()
+                .find(|p| property.trim().eq_ignore_ascii_case(p))
+        })
+        .ok_or_else(|| nom::Err::Error(Error::new(i, ErrorKind::Tag)))?;
+    let link_destination = find_attribute(&attributes, "content");
+    let link_destination = match link_destination.trim() {
+        trimmed if trimmed.len() == link_destination.len() => link_destination,
+        trimmed => Cow::Owned(trimmed.to_string()),
+    };
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((
+        j,
+        (
+            Cow::Borrowed(*link_text),
+            link_destination,
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+/// Parses the start tag of any HTML element and returns the internal link
+/// targets it defines: the value of the `id` attribute and, for `<a>`
+/// elements, the value of the `name` attribute.
+///
+/// These are the fragments that a link `#x` can point to within the same
+/// document. It returns either `Ok((i, targets))` or some error. `targets`
+/// lists the `id` first, then the `name`; a `name` equal to the `id` is not
+/// repeated. A start tag defining no target is rejected.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_anchor_target;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_anchor_target(r#"<a name="intro"></a>abc"#),
+///   Ok(("</a>abc", vec![Cow::from("intro")]))
+/// );
+/// assert_eq!(
+///   html_anchor_target(r#"<h2 class="title" id="usage">Usage</h2>"#),
+///   Ok(("Usage</h2>", vec![Cow::from("usage")]))
+/// );
+/// ```
+pub fn html_anchor_target(i: &str) -> nom::IResult<&str, Vec<Cow<str>>> {
+    let (_, name) = nom::sequence::preceded(
+        tag("<"),
+        nom::combinator::verify(
+            nom::bytes::complete::take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-'),
+            |s: &str| s.starts_with(|c: char| c.is_ascii_alphabetic()),
+        ),
+    )(i)?;
+    let (j, attributes) = tag_opening(i, name)?;
+
+    let mut targets = Vec::new();
+    let id = find_attribute(&attributes, "id");
+    if !id.is_empty() {
+        targets.push(id);
+    }
+    if name.eq_ignore_ascii_case("a") {
+        let name = find_attribute(&attributes, "name");
+        if !name.is_empty() && !targets.contains(&name) {
+            targets.push(name);
+        }
+    }
+    if targets.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((j, targets))
+}
+
+/// Parses an HTML `<base>` element and returns the value of its `href`
+/// attribute, the base URL of the document.
+///
+/// `<base>` is a void element: it has no end tag, a self-closing slash is
+/// optional. A `<base>` element without `href`, e.g. `<base target="_top">`,
+/// is rejected.
+///
+/// The parser expects to start at the tag start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::html::html_base;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   html_base(r#"<base href="https://example.org/docs/">abc"#),
+///   Ok(("abc", Cow::from("https://example.org/docs/")))
+/// );
+/// ```
+pub fn html_base(i: &str) -> nom::IResult<&str, Cow<str>> {
+    let (j, attributes) = tag_opening(i, "base")?;
+    let base = find_attribute(&attributes, "href");
+    if base.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    Ok((j, base))
+}
+
+/// Extracts all links of `input` with `parse::take_link()` and resolves the
+/// relative _link destinations_ and image sources against the base URL of
+/// the first `<base href="...">` element. Only links after the `<base>`
+/// element are resolved, `<base>` elements in HTML comments are ignored. See
+/// `html_resolve_url()` for details. Without `<base>` element, the links
+/// are returned unchanged.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::html::html_resolve_links;
+/// use std::borrow::Cow;
+///
+/// let i = r#"<a href="a.html">a</a><base href="https://example.org/docs/">
+/// <a href="b.html">b</a><a href="/c.html">c</a><a href="mailto:d@example.org">d</a>"#;
+///
+/// assert_eq!(
+///   html_resolve_links(i),
+///   vec![
+///     Link::Text2Dest(Cow::from("a"), Cow::from("a.html"), Cow::from("")),
+///     Link::Text2Dest(Cow::from("b"), Cow::from("https://example.org/docs/b.html"), Cow::from("")),
+///     Link::Text2Dest(Cow::from("c"), Cow::from("https://example.org/c.html"), Cow::from("")),
+///     Link::Text2Dest(Cow::from("d"), Cow::from("mailto:d@example.org"), Cow::from("")),
+///   ]
+/// );
+/// ```
+pub fn html_resolve_links(input: &str) -> Vec<Link> {
+    // Find the first `<base href>` outside of comments.
+    let mut base = None;
+    let mut j = input;
+    while let Some(idx) = j.find('<') {
+        j = &j[idx..];
+        if let Ok((k, _)) = html_comment(j) {
+            j = k;
+        } else if let Ok((k, href)) = html_base(j) {
+            base = Some((input.len() - k.len(), href));
+            break;
+        } else {
+            j = &j[1..];
+        }
+    }
+
+    let mut links = Vec::new();
+    let mut i = input;
+    while let Ok((j, (skipped, link))) = take_link(i) {
+        let link_offset = input.len() - i.len() + skipped.len();
+        i = j;
+        let base = match &base {
+            Some((base_offset, base)) if link_offset >= *base_offset => base,
+            _ => {
+                links.push(link);
+                continue;
+            }
+        };
+        links.push(match link {
+            Link::Text2Dest(te, de, ti) => Link::Text2Dest(te, html_resolve_url(base, de), ti),
+            Link::Label2Dest(la, de, ti) => Link::Label2Dest(la, html_resolve_url(base, de), ti),
+            Link::TextLabel2Dest(tl, de, ti) => {
+                Link::TextLabel2Dest(tl, html_resolve_url(base, de), ti)
+            }
+            Link::Image(alt, src) => Link::Image(alt, html_resolve_url(base, src)),
+            link => link,
+        });
+    }
+    links
+}
+
+/// Resolves the relative URL `dest` against the absolute URL `base`, as
+/// described in [RFC 3986, section 5.2](https://www.rfc-editor.org/rfc/rfc3986#section-5.2).
+///
+/// A `dest` with scheme, e.g. `https:` or `mailto:`, is returned unchanged.
+/// Dot segments `.` and `..` of the resolved path are removed. The URLs are
+/// not normalized otherwise.
+/// ```
+/// use parse_hyperlinks::parser::html::html_resolve_url;
+/// use std::borrow::Cow;
+///
+/// let base = "https://example.org/a/b/c?q#f";
+/// assert_eq!(html_resolve_url(base, Cow::from("../d")), "https://example.org/a/d");
+/// assert_eq!(html_resolve_url(base, Cow::from("//example.com/")), "https://example.com/");
+/// assert_eq!(html_resolve_url(base, Cow::from("#g")), "https://example.org/a/b/c?q#g");
+/// assert_eq!(html_resolve_url(base, Cow::from("ftp://x/y")), "ftp://x/y");
+/// ```
+pub fn html_resolve_url<'a>(base: &str, dest: Cow<'a, str>) -> Cow<'a, str> {
+    let (r_scheme, r_authority, r_path, r_query, r_fragment) = url_split(&dest);
+    if r_scheme.is_some() {
+        return dest;
+    }
+    let (b_scheme, b_authority, b_path, b_query, _) = url_split(base);
+
+    let (authority, path, query) = if r_authority.is_some() {
+        (r_authority, url_remove_dot_segments(r_path), r_query)
+    } else if r_path.is_empty() {
+        (b_authority, b_path.to_string(), r_query.or(b_query))
+    } else if r_path.starts_with('/') {
+        (b_authority, url_remove_dot_segments(r_path), r_query)
+    } else {
+        // Merge the paths.
+        let merged = if b_authority.is_some() && b_path.is_empty() {
+            format!("/{}", r_path)
+        } else {
+            let dir = b_path.rfind('/').map_or("", |idx| &b_path[..idx + 1]);
+            format!("{}{}", dir, r_path)
+        };
+        (b_authority, url_remove_dot_segments(&merged), r_query)
+    };
+
+    let mut s = String::with_capacity(base.len() + dest.len());
+    if let Some(scheme) = b_scheme {
+        s.push_str(scheme);
+        s.push(':');
+    }
+    if let Some(authority) = authority {
+        s.push_str("//");
+        s.push_str(authority);
+    }
+    s.push_str(&path);
+    if let Some(query) = query {
+        s.push('?');
+        s.push_str(query);
+    }
+    if let Some(fragment) = r_fragment {
+        s.push('#');
+        s.push_str(fragment);
+    }
+    Cow::Owned(s)
+}
+
+/// Splits an URL into `(scheme, authority, path, query, fragment)`, see
+/// [RFC 3986, appendix B](https://www.rfc-editor.org/rfc/rfc3986#appendix-B).
+fn url_split(i: &str) -> (Option<&str>, Option<&str>, &str, Option<&str>, Option<&str>) {
+    let (i, fragment) = match i.split_once('#') {
+        Some((i, fragment)) => (i, Some(fragment)),
+        None => (i, None),
+    };
+    let (i, query) = match i.split_once('?') {
+        Some((i, query)) => (i, Some(query)),
+        None => (i, None),
+    };
+    let (i, scheme) = match i.split_once(':') {
+        Some((scheme, rest))
+            if scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)) =>
+        {
+            (rest, Some(scheme))
+        }
+        _ => (i, None),
+    };
+    let (path, authority) = match i.strip_prefix("//") {
+        Some(rest) => {
+            let idx = rest.find('/').unwrap_or(rest.len());
+            (&rest[idx..], Some(&rest[..idx]))
+        }
+        None => (i, None),
+    };
+    (scheme, authority, path, query, fragment)
+}
+
+/// Removes the dot segments `.` and `..` of a path, see
+/// [RFC 3986, section 5.2.4](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4).
+fn url_remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let mut segments = path.split('/').peekable();
+    let absolute = path.starts_with('/');
+    if absolute {
+        segments.next();
+    }
+    while let Some(segment) = segments.next() {
+        let last = segments.peek().is_none();
+        match segment {
+            "." | ".." => {
+                if segment == ".." {
+                    output.pop();
+                }
+                // A trailing dot segment leaves a directory.
+                if last {
+                    output.push("");
+                }
+            }
+            segment => output.push(segment),
+        }
+    }
+    let s = output.join("/");
+    if absolute {
+        format!("/{}", s)
+    } else {
+        s
+    }
+}
+
+/// Parses a `<a ...>` opening tag and returns
+/// either `Ok((i, (link_destination, link_title)))` or some error.
+fn tag_a_opening(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
+    nom::sequence::delimited(
+        // HTML is case insensitive. XHTML, that is being XML is case sensitive.
+        // Here we deal with HTML.
+        nom::sequence::pair(tag_no_case("<a"), multispace1),
+        nom::combinator::map_parser(tag_content, parse_attributes),
+        tag(">"),
+    )(i)
+}
+
+/// Parses the start tag of the element `name`, e.g. `<img src="a.png">` or
+/// `<img src="a.png"/>`, and returns all its attributes as `(name, value)`
+/// in the order of appearance. Boolean attributes have an empty value. The
+/// element name is case insensitive.
+fn tag_opening<'a>(i: &'a str, name: &str) -> nom::IResult<&'a str, Vec<(&'a str, Cow<'a, str>)>> {
+    let (j, _) = nom::sequence::preceded(tag("<"), tag_no_case(name))(i)?;
+    if let Ok((k, _)) = alt((tag::<_, _, Error<&str>>(">"), tag("/>")))(j) {
+        return Ok((k, Vec::new()));
+    }
+    let (j, content) =
+        nom::sequence::delimited(multispace1, nom::combinator::opt(tag_content), tag(">"))(j)?;
+    let attributes = content
+        .and_then(|content| {
+            nom::multi::separated_list1(multispace1, attribute_or_boolean)(content.trim()).ok()
+        })
+        .map(|(_, attributes)| attributes)
+        .unwrap_or_default();
+    Ok((j, attributes))
+}
+
+/// Returns the value of the first attribute called `name`, ignoring case,
+/// or an empty string if there is none.
+fn find_attribute<'a>(attributes: &[(&str, Cow<'a, str>)], name: &str) -> Cow<'a, str> {
+    attributes
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default()
+}
+
+/// Consumes the input until the first `</a>` closing tag, in any case, and
+/// the closing tag itself. Returns `Ok((i, content))` with the content
+/// before the closing tag.
+///
+/// Anchors can contain block-level elements, but they can not be nested.
+/// Hence, when the next `<a>` start tag comes before `</a>` or when there is
+/// no `</a>` at all, the closing tag is missing. Then, the content ends
+/// before the first start tag of a block-level element, e.g. `<p>` or
+/// `<div>`, or before the next `<a>`, whichever comes first. This start tag
+/// is not consumed, so that the scanning can continue there.
+fn take_until_tag_a_closing(i: &str) -> nom::IResult<&str, &str> {
+    // The first block-level start tag.
+    let mut block = None;
+    let mut k = i;
+    while let Some(idx) = k.find('<') {
+        let j = &k[idx..];
+        if let Ok((rest, _)) = nom::sequence::tuple((
+            tag_no_case::<_, _, Error<&str>>("</a"),
+            nom::character::complete::multispace0,
+            tag(">"),
+        ))(j)
+        {
+            return Ok((rest, &i[..i.len() - j.len()]));
+        }
+        if is_start_tag(j, "a") {
+            let j = block.unwrap_or(j);
+            return Ok((j, &i[..i.len() - j.len()]));
+        }
+        if block.is_none() && HTML_BLOCK_ELEMENTS.iter().any(|name| is_start_tag(j, name)) {
+            block = Some(j);
+        }
+        k = &j[1..];
+    }
+    match block {
+        Some(j) => Ok((j, &i[..i.len() - j.len()])),
+        None => Err(nom::Err::Error(Error::new(i, ErrorKind::TakeUntil))),
+    }
+}
+
+/// Is `i` the beginning of a start tag of the element `name`?
+fn is_start_tag(i: &str, name: &str) -> bool {
+    i.strip_prefix('<')
+        .filter(|rest| {
+            rest.get(..name.len())
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+        .and_then(|rest| rest[name.len()..].chars().next())
+        .is_some_and(|c| c.is_ascii_whitespace() || c == '>' || c == '/')
+}
+
+/// Consumes the content of a tag up to the closing `>`. A `>` enclosed in
+/// double or single quotes, e.g. in `title='a > b'`, does not close the tag.
+/// Quotes inside unquoted attribute values, e.g. `title=it's`, are ordinary
+/// characters.
+fn tag_content(i: &str) -> nom::IResult<&str, &str> {
+    let mut quote = None;
+    // The last non-whitespace character outside quotes.
+    let mut prev = ' ';
+    for (idx, c) in i.char_indices() {
+        match (quote, c) {
+            (None, '>') => {
+                if idx == 0 {
+                    break;
+                }
+                return Ok((&i[idx..], &i[..idx]));
+            }
+            (None, '"' | '\'') if prev == '=' => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, c) if !c.is_whitespace() => prev = c,
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(Error::new(i, ErrorKind::IsNot)))
+}
+
+/// Parses attributes and returns `Ok((name, value))`.
+/// Boolean attributes are ignored, but silently consumed.
+fn attribute(i: &str) -> nom::IResult<&str, (&str, Cow<str>)> {
+    alt((
+        nom::sequence::pair(attribute_name, attribute_value),
+        // Consume boolean attributes.
+        nom::combinator::value(("", Cow::from("")), attribute_name),
+    ))(i)
+}
+
+/// Parses attributes and returns `Ok((name, value))`. Unlike `attribute()`,
+/// boolean attributes are returned with their name and an empty value.
+fn attribute_or_boolean(i: &str) -> nom::IResult<&str, (&str, Cow<str>)> {
+    nom::sequence::pair(
+        attribute_name,
+        nom::combinator::map(
+            nom::combinator::opt(attribute_value),
+            Option::unwrap_or_default,
+        ),
+    )(i)
+}
+
+/// Parses `=` followed by a double quoted, single quoted or unquoted
+/// attribute value and returns the value with decoded character references.
+fn attribute_value(i: &str) -> nom::IResult<&str, Cow<str>> {
+    nom::sequence::preceded(
+        // Whitespace around `=` is allowed.
+        nom::sequence::tuple((multispace0, tag("="), multispace0)),
+        alt((
+            nom::combinator::value(Cow::from(""), tag(r#""""#)),
+            nom::combinator::value(Cow::from(""), tag(r#"''"#)),
+            nom::combinator::map(
+                nom::sequence::delimited(tag("\""), is_not("\""), tag("\"")),
+                |s: &str| html_decode_entities(s, true),
+            ),
+            nom::combinator::map(
+                nom::sequence::delimited(tag("'"), is_not("'"), tag("'")),
+                |s: &str| html_decode_entities(s, true),
+            ),
+            // Unquoted values end at whitespace.
+            nom::combinator::map(is_not(" \t\r\n\x0C>"), |s: &str| {
+                html_decode_entities(s, true)
+            }),
+        )),
+    )(i)
+}
+
+/// Parses an attribute name. Besides letters and digits, `-`, `_`, `:` and
+/// `.` are allowed, e.g. `data-id`, `aria-label` or `xml:lang`. The first
+/// character must be a letter, `_` or `:`.
+fn attribute_name(i: &str) -> nom::IResult<&str, &str> {
+    nom::combinator::verify(
+        nom::bytes::complete::take_while1(|c: char| c.is_alphanumeric() || "-_:.".contains(c)),
+        |s: &str| s.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == ':'),
+    )(i)
+}
+
+/// Parses a whitespace separated list of attributes and returns a vector of (name, value).
+pub fn attribute_list<'a>(i: &'a str) -> nom::IResult<&'a str, Vec<(&'a str, Cow<str>)>> {
+    let i = i.trim();
+    nom::multi::separated_list1(nom::character::complete::multispace1, attribute)(i)
+}
+
+/// Extracts the `href` and `title` attributes and returns
+/// `Ok((link_destination, link_title))`. `link_title` can be empty,
+/// `link_destination` not.
+fn parse_attributes(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
+    let (i, attributes) = attribute_list(i)?;
+    let mut href = Cow::Borrowed("");
+    let mut title = Cow::Borrowed("");
+
+    for (name, value) in attributes {
+        if name.eq_ignore_ascii_case("href") {
+            // Make sure `href` is empty, it can appear only
+            // once.
+            if !(&*href).is_empty() {
+                return Err(nom::Err::Error(Error::new(name, ErrorKind::ManyMN)));
+            }
+            href = value;
+        } else if name.eq_ignore_ascii_case("title") {
+            // Make sure `title` is empty, it can appear only
+            // once.
+            if !(&*title).is_empty() {
+                return Err(nom::Err::Error(Error::new(name, ErrorKind::ManyMN)));
+            }
+            title = value;
+        }
+    }
+
+    // Assure that `href` is not empty.
+    if (&*href).is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Eof)));
+    };
+
+    Ok((i, (href, title)))
+}
+
+/// Decodes the character references of a text or, with `in_attribute`, of
+/// an attribute value the way a browser does.
+///
+/// Besides named references terminated by `;`, e.g. `&eacute;`, and numeric
+/// references, e.g. `&#x2F;`, this resolves the legacy named references
+/// without `;`, e.g. `&copy`. In attribute values, these are kept when
+/// followed by a letter, a digit or `=`, which preserves query strings like
+/// `?a=1&copy=2`. Numeric references to `0`, surrogates or beyond
+/// `U+10FFFF` are replaced by `U+FFFD`, the references `&#128;` to `&#159;`
+/// by the characters of the Windows-1252 encoding.
+fn html_decode_entities(i: &str, in_attribute: bool) -> Cow<str> {
+    if !i.contains('&') {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut k = i;
+    while let Some(idx) = k.find('&') {
+        s.push_str(&k[..idx]);
+        let j = &k[idx + 1..];
+        k = j;
+
+        // Numeric character reference.
+        if let Some(num) = j.strip_prefix('#') {
+            let (radix, digits) = match num.strip_prefix(['x', 'X']) {
+                Some(digits) => (16, digits),
+                None => (10, num),
+            };
+            let len = digits
+                .find(|c: char| !c.is_digit(radix))
+                .unwrap_or(digits.len());
+            if len > 0 {
+                let c = u32::from_str_radix(&digits[..len], radix)
+                    .ok()
+                    .and_then(|n| match n {
+                        0x80..=0x9F => Some(HTML_C1_REPLACEMENTS[n as usize - 0x80]),
+                        n => char::from_u32(n).filter(|&c| c != '\0'),
+                    })
+                    .unwrap_or(char::REPLACEMENT_CHARACTER);
+                s.push(c);
+                k = &digits[len..];
+                k = k.strip_prefix(';').unwrap_or(k);
+                continue;
+            }
+            s.push('&');
+            continue;
+        }
+
+        // Named character reference.
+        let len = j
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(j.len());
+        if j[len..].starts_with(';') {
+            let reference = &i[i.len() - j.len() - 1..i.len() - j.len() + len + 1];
+            let decoded = decode_html_entities(reference);
+            if decoded != reference {
+                s.push_str(&decoded);
+                k = &j[len + 1..];
+                continue;
+            }
+        }
+        // The longest legacy reference without `;`.
+        if let Some(name) = HTML_LEGACY_ENTITIES
+            .iter()
+            .filter(|name| j.starts_with(*name))
+            .max_by_key(|name| name.len())
+        {
+            let next = j[name.len()..].chars().next();
+            if !(in_attribute && next.is_some_and(|c| c.is_ascii_alphanumeric() || c == '=')) {
+                s.push_str(&decode_html_entities(&format!("&{};", name)));
+                k = &j[name.len()..];
+                continue;
+            }
+        }
+        s.push('&');
+    }
+    s.push_str(k);
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("W3Schools"),
+                Cow::from("https://www.w3schools.com/"),
+                Cow::from("W3S"),
+            ),
+        );
+        assert_eq!(
+            html_text2dest(r#"<a title="W3S" href="https://www.w3schools.com/">W3Schools</a>abc"#)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            html_text2dest(r#"<A title="W3S" href="https://www.w3schools.com/">W3Schools</A>abc"#)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            html_text2dest(r#"<A TITLE="W3S" Href="https://www.w3schools.com/">W3Schools</a >abc"#)
+                .unwrap(),
+            expected
+        );
+
+        let expected = ("abc", (Cow::from("<n>"), Cow::from("h"), Cow::from("t")));
+        assert_eq!(
+            html_text2dest(r#"<a title="t" href="h">&lt;n&gt;</a>abc"#).unwrap(),
+            expected
+        );
+
+        let expected = ("abc", (Cow::from("name"), Cow::from("url"), Cow::from("")));
+        assert_eq!(
+            html_text2dest(r#"<a href="url" title="" >name</a>abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (Cow::from("na</me"), Cow::from("url"), Cow::from("")),
+        );
+        assert_eq!(
+            html_text2dest(r#"<a href="url" title="" >na</me</A>abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc<a href=\"y\">u</a>",
+            (Cow::from("t"), Cow::from("x"), Cow::from("")),
+        );
+        assert_eq!(
+            html_text2dest(r#"<a href="x">t</A>abc<a href="y">u</a>"#).unwrap(),
+            expected
+        );
+
+        let expected = nom::Err::Error(nom::error::Error::new(
+            r#"<a href="url" title="" >name</a abc"#,
+            nom::error::ErrorKind::TakeWhile1,
+        ));
+        assert_eq!(
+            parse_attributes(r#"<a href="url" title="" >name</a abc"#).unwrap_err(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from(
+                    "<img src=\"w3html.gif\" alt=\"W3Schools.com \"width=\"100\" height=\"132\">",
+                ),
+                Cow::from("https://blog.getreu.net"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            html_text2dest(
+                "<a href=\"https://blog.getreu.net\">\
+                              <img src=\"w3html.gif\" alt=\"W3Schools.com \"\
+                              width=\"100\" height=\"132\">\
+                              </a>abc"
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_tag_a_opening() {
+        let expected = (
+            "abc",
+            (Cow::from("http://getreu.net"), Cow::from("My blog")),
+        );
+        assert_eq!(
+            tag_a_opening(r#"<a href="http://getreu.net" title="My blog">abc"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            tag_a_opening(r#"<A href="http://getreu.net" title="My blog">abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("http://getreu.net"),
+                Cow::from("My \"blog\" > yours"),
+            ),
+        );
+        assert_eq!(
+            tag_a_opening(r#"<a href='http://getreu.net' title='My "blog" &gt; yours'>abc"#)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            tag_a_opening(r#"<a href='http://getreu.net' title='My "blog" > yours'>abc"#).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_tag_content() {
+        assert_eq!(
+            tag_content(r#"href='a>b' title='say "hi"'>abc"#),
+            Ok((">abc", r#"href='a>b' title='say "hi"'"#))
+        );
+        assert_eq!(
+            tag_content(r#"href="a'>b">abc"#),
+            Ok((">abc", r#"href="a'>b""#))
+        );
+        assert_eq!(
+            tag_content(r#"href='a>abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"href='a>abc"#,
+                nom::error::ErrorKind::IsNot
+            )))
+        );
+    }
+
+    #[test]
+    fn test_html_text2dest_attribute_names() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("name"),
+                Cow::from("https://example.org/"),
+                Cow::from("title"),
+            ),
+        );
+        assert_eq!(
+            html_text2dest(
+                r#"<a data-foo="x" aria-label='y' xml:lang=en _x.y href="https://example.org/" title="title">name</a>abc"#
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_html_text2dest_whitespace() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("name"),
+                Cow::from("https://example.org/"),
+                Cow::from("title"),
+            ),
+        );
+        assert_eq!(
+            html_text2dest(
+                "<a\n  class = \"link\"\n  href = \"https://example.org/\"\n  title =\n'title'\n>name</a>abc"
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            html_text2dest("<a\thref= https://example.org/ download title ='title'>name</a>abc")
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_html_text2dest_unquoted() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("name"),
+                Cow::from("../a/b.html?x=1&y=2"),
+                Cow::from("it's"),
+            ),
+        );
+        assert_eq!(
+            html_text2dest("<a href=../a/b.html?x=1&amp;y=2\n   title=it's>name</a>abc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            html_text2dest("<a title=it's\thref=../a/b.html?x=1&amp;y=2>name</a>abc").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let expected = ("", (Cow::from("http://getreu.net"), Cow::from("My blog")));
+        assert_eq!(
+            parse_attributes(r#"abc href="http://getreu.net" abc title="My blog" abc"#).unwrap(),
+            expected
+        );
+
+        let expected = nom::Err::Error(nom::error::Error::new(
+            "href",
+            nom::error::ErrorKind::ManyMN,
+        ));
+        assert_eq!(
+            parse_attributes(r#" href="http://getreu.net" href="http://blog.getreu.net" "#)
+                .unwrap_err(),
+            expected
+        );
+
+        let expected = nom::Err::Error(nom::error::Error::new(
+            "title",
+            nom::error::ErrorKind::ManyMN,
+        ));
+        assert_eq!(
+            parse_attributes(r#" href="http://getreu.net" title="a" title="b" "#).unwrap_err(),
+            expected
+        );
+
+        let expected = nom::Err::Error(nom::error::Error::new("", nom::error::ErrorKind::Eof));
+        assert_eq!(
+            parse_attributes(r#" title="title" "#).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_attribute_list() {
+        let expected = (
+            "",
+            vec![
+                ("", Cow::from("")),
+                ("href", Cow::from("http://getreu.net")),
+                ("", Cow::from("")),
+                ("title", Cow::from("My blog")),
+                ("", Cow::from("")),
+            ],
+        );
+        assert_eq!(
+            attribute_list(r#"abc href="http://getreu.net" abc title="My blog" abc"#).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        let expected = (" abc", ("href", Cow::from("http://getreu.net")));
+        assert_eq!(
+            attribute(r#"href="http://getreu.net" abc"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            attribute(r#"href='http://getreu.net' abc"#).unwrap(),
+            expected
+        );
+        // Only allowed when no space in value.
+        assert_eq!(
+            attribute(r#"href=http://getreu.net abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (" abc", ("href", Cow::from("http://getreu.net/<>")));
+        assert_eq!(
+            attribute(r#"href="http://getreu.net/&lt;&gt;" abc"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            attribute(r#"href='http://getreu.net/&lt;&gt;' abc"#).unwrap(),
+            expected
+        );
+        // Only allowed when no space in value.
+        assert_eq!(
+            attribute(r#"href=http://getreu.net/&lt;&gt; abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (" abc", ("", Cow::from("")));
+        assert_eq!(attribute("bool abc").unwrap(), expected);
+        assert_eq!(attribute("data-bool abc").unwrap(), expected);
+
+        let expected = (" abc", ("xml:lang", Cow::from("en")));
+        assert_eq!(attribute("xml:lang=en abc").unwrap(), expected);
+
+        let expected = nom::Err::Error(nom::error::Error::new(
+            "1name",
+            nom::error::ErrorKind::Verify,
+        ));
+        assert_eq!(attribute("1name").unwrap_err(), expected);
+
+        let expected = nom::Err::Error(nom::error::Error::new(
+            r#"1name="http://getreu.net"#,
+            nom::error::ErrorKind::Verify,
+        ));
+        assert_eq!(
+            attribute(r#"1name="http://getreu.net"#).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_html_decode_entities() {
+        assert_eq!(html_decode_entities("abc", false), Cow::from("abc"));
+        assert_eq!(
+            html_decode_entities("&eacute;&#x2F;&#47;&amp;&nbsp;", false),
+            Cow::from("\u{e9}//&\u{a0}")
+        );
+        assert_eq!(
+            html_decode_entities("&amp x &copy2 &notit; &unknown; &#;", false),
+            Cow::from("& x \u{a9}2 \u{ac}it; &unknown; &#;")
+        );
+        assert_eq!(
+            html_decode_entities("?a=1&copy=2&amp;b=3&lt", true),
+            Cow::from("?a=1&copy=2&b=3<")
+        );
+        assert_eq!(
+            html_decode_entities("&#0;&#x110000;&#xD800;&#128;&#x9f", false),
+            Cow::from("\u{FFFD}\u{FFFD}\u{FFFD}\u{20AC}\u{178}")
+        );
+        // Overflowing numbers and leading zeros.
+        assert_eq!(
+            html_decode_entities("&#99999999999999999999;&#x0000000000002F;&#X2f&#x;", false),
+            Cow::from("\u{FFFD}//&#x;")
+        );
+    }
+
+    #[test]
+    fn test_html_text2dest_numeric_references() {
+        // An obfuscated `mailto:` link.
+        let expected = (
+            "abc",
+            (
+                Cow::from("john@example.org"),
+                Cow::from("mailto:john@example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            html_text2dest(
+                "<a href=\"&#109;&#97;&#x69;&#X6C;&#x74;&#111;&#58;john&#64;example&#x2E;org\">\
+john&#x40;example&#46;org</a>abc"
+            )
+            .unwrap(),
+            expected
+        );
+    }
 
-    // Assure that `href` is not empty.
-    if (&*href).is_empty() {
-        return Err(nom::Err::Error(Error::new(i, ErrorKind::Eof)));
-    };
-
-    Ok((i, (href, title)))
-}
+    #[test]
+    fn test_html_plain_text() {
+        assert_eq!(html_plain_text(" name "), Cow::from("name"));
+        assert_eq!(
+            html_plain_text(
+                "<em>a</em> &lt; <b\nclass='x>y'>b</b><!-- <i>c</i> -->\n  &amp; <br/>1 < 2"
+            ),
+            Cow::from("a < b & 1 < 2")
+        );
+        assert_eq!(
+            html_plain_text(r#"<IMG src="a.png" ALT="A &amp; B"> <img src="b.png">"#),
+            Cow::from("A & B")
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_tag_opening() {
+        assert_eq!(tag_opening("<IMG>abc", "img"), Ok(("abc", vec![])));
+        assert_eq!(tag_opening("<img />abc", "img"), Ok(("abc", vec![])));
+        assert_eq!(
+            tag_opening("<img\n  src = 'a.png' ISMAP\n/>abc", "img"),
+            Ok((
+                "abc",
+                vec![("src", Cow::from("a.png")), ("ISMAP", Cow::from(""))]
+            ))
+        );
+        assert_eq!(
+            tag_opening("<imgx src='a.png'>abc", "img"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "x src='a.png'>abc",
+                nom::error::ErrorKind::MultiSpace
+            )))
+        );
+    }
 
     #[test]
-    fn test_html_text2dest() {
+    fn test_html_img() {
         let expected = (
             "abc",
             (
-                Cow::from("W3Schools"),
-                Cow::from("https://www.w3schools.com/"),
-                Cow::from("W3S"),
+                Cow::from("A & B"),
+                Cow::from("https://example.org/a.png"),
+                Cow::from(""),
             ),
         );
         assert_eq!(
-            html_text2dest(r#"<a title="W3S" href="https://www.w3schools.com/">W3Schools</a>abc"#)
+            html_img("<IMG\n  SRC=https://example.org/a.png alt='A &amp; B' src='b.png'>abc")
                 .unwrap(),
             expected
         );
+
+        let expected = ("abc", (Cow::from(""), Cow::from("a.png"), Cow::from("")));
+        assert_eq!(html_img("<img src=a.png ismap />abc").unwrap(), expected);
+
         assert_eq!(
-            html_text2dest(r#"<A title="W3S" href="https://www.w3schools.com/">W3Schools</A>abc"#)
-                .unwrap(),
-            expected
+            html_img(r#"<img alt="Logo">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<img alt="Logo">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            html_img(r#"<image src="logo.png">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"image src="logo.png">abc"#,
+                nom::error::ErrorKind::Tag
+            )))
         );
+    }
 
-        let expected = ("abc", (Cow::from("<n>"), Cow::from("h"), Cow::from("t")));
+    #[test]
+    fn test_html_rel2dest() {
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("https://example.org/page"),
+                Cow::from("https://example.org/page"),
+                Cow::from("canonical"),
+            ),
+        );
         assert_eq!(
-            html_text2dest(r#"<a title="t" href="h">&lt;n&gt;</a>abc"#).unwrap(),
+            html_rel2dest("<LINK HREF=https://example.org/page REL=' canonical\n'>\nabc").unwrap(),
             expected
         );
 
-        let expected = ("abc", (Cow::from("name"), Cow::from("url"), Cow::from("")));
         assert_eq!(
-            html_text2dest(r#"<a href="url" title="" >name</a>abc"#).unwrap(),
-            expected
+            html_rel2dest(r#"<link href="style.css">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<link href="style.css">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
         );
+        assert_eq!(
+            html_rel2dest(r#"<link rel="preconnect">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<link rel="preconnect">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+    }
 
+    #[test]
+    fn test_html_area2dest() {
         let expected = (
             "abc",
-            (Cow::from("na</me"), Cow::from("url"), Cow::from("")),
+            (
+                Cow::from("Mercury & Venus"),
+                Cow::from("https://example.org/planets?a=1&b=2"),
+                Cow::from("Inner planets"),
+            ),
         );
         assert_eq!(
-            html_text2dest(r#"<a href="url" title="" >na</me</A>abc"#).unwrap(),
+            html_area2dest(
+                "<AREA SHAPE=circle COORDS=\"90,58,3\" HREF='https://example.org/planets?a=1&amp;b=2'\n ALT=\"Mercury &amp; Venus\" title=\"Inner planets\" />abc"
+            )
+            .unwrap(),
             expected
         );
 
-        let expected = nom::Err::Error(nom::error::Error::new(
-            r#"<a href="url" title="" >name</a abc"#,
-            nom::error::ErrorKind::AlphaNumeric,
-        ));
         assert_eq!(
-            parse_attributes(r#"<a href="url" title="" >name</a abc"#).unwrap_err(),
+            html_area2dest(r#"<area shape="default" nohref alt="">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<area shape="default" nohref alt="">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_html_base() {
+        assert_eq!(
+            html_base("<BASE target=_blank HREF='https://example.org/?a=1&amp;b=2' />abc"),
+            Ok(("abc", Cow::from("https://example.org/?a=1&b=2")))
+        );
+        assert_eq!(
+            html_base(r#"<base target="_blank">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<base target="_blank">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_html_resolve_links() {
+        let i = "<!-- <base href=\"http://wrong/\"> --><base href=\"http://a/b/c/d;p?q\">\
+<img src=\"e.png\" alt=\"e\"> <a href=\"?y\">f</a>\n[g]: ../g\n";
+        assert_eq!(
+            html_resolve_links(i),
+            vec![
+                Link::Text2Dest(
+                    Cow::from("f"),
+                    Cow::from("http://a/b/c/d;p?y"),
+                    Cow::from("")
+                ),
+                Link::Label2Dest(Cow::from("g"), Cow::from("http://a/b/g"), Cow::from("")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html_resolve_url() {
+        // Examples of RFC 3986, section 5.4.
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+        ];
+        for (dest, expected) in examples {
+            assert_eq!(
+                html_resolve_url(base, Cow::from(dest)),
+                expected,
+                "{}",
+                dest
+            );
+        }
+        assert_eq!(
+            html_resolve_url("http://a", Cow::from("g")),
+            Cow::from("http://a/g")
+        );
+    }
+
+    #[test]
+    fn test_html_src() {
+        let expected = (
+            "</IFRAME>abc",
+            (
+                Cow::from("Map & route"),
+                Cow::from("https://example.org/map?x=1&y=2"),
+                Cow::from("iframe"),
+            ),
+        );
+        assert_eq!(
+            html_src("<IFRAME allowfullscreen\n SRC='https://example.org/map?x=1&amp;y=2' title=\"Map &amp; route\"></IFRAME>abc")
+                .unwrap(),
             expected
         );
 
         let expected = (
             "abc",
             (
-                Cow::from(
-                    "<img src=\"w3html.gif\" alt=\"W3Schools.com \"width=\"100\" height=\"132\">",
-                ),
-                Cow::from("https://blog.getreu.net"),
-                Cow::from(""),
+                Cow::from("movie.webm"),
+                Cow::from("movie.webm"),
+                Cow::from("source"),
             ),
         );
         assert_eq!(
-            html_text2dest(
-                "<a href=\"https://blog.getreu.net\">\
-                              <img src=\"w3html.gif\" alt=\"W3Schools.com \"\
-                              width=\"100\" height=\"132\">\
-                              </a>abc"
-            )
-            .unwrap(),
+            html_src(r#"<source src="movie.webm" type="video/webm" />abc"#).unwrap(),
             expected
         );
+
+        assert_eq!(
+            html_src("<script>alert(1)</script>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<script>alert(1)</script>abc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            html_src(r#"<img src="a.png">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<img src="a.png">abc"#,
+                nom::error::ErrorKind::Tag
+            )))
+        );
     }
 
     #[test]
-    fn test_tag_a_opening() {
+    fn test_html_srcset() {
         let expected = (
             "abc",
-            (Cow::from("http://getreu.net"), Cow::from("My blog")),
+            vec![
+                (
+                    Cow::from("img/a,b.jpg"),
+                    Cow::from("img/a,b.jpg"),
+                    Cow::from("480w"),
+                ),
+                (
+                    Cow::from("img/c.jpg?w=800&h=600"),
+                    Cow::from("img/c.jpg?w=800&h=600"),
+                    Cow::from("800w"),
+                ),
+                (Cow::from("d.jpg"), Cow::from("d.jpg"), Cow::from("")),
+            ],
         );
         assert_eq!(
-            tag_a_opening(r#"<a href="http://getreu.net" title="My blog">abc"#).unwrap(),
+            html_srcset(
+                "<SOURCE media=\"(min-width: 600px)\"\n  SRCSET=\"img/a,b.jpg 480w,\n  img/c.jpg?w=800&amp;h=600 800w, d.jpg,,\">abc"
+            )
+            .unwrap(),
             expected
         );
+
         assert_eq!(
-            tag_a_opening(r#"<A href="http://getreu.net" title="My blog">abc"#).unwrap(),
-            expected
+            html_srcset(r#"<img src="a.jpg" srcset=" , ">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<img src="a.jpg" srcset=" , ">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
         );
     }
 
     #[test]
-    fn test_parse_attributes() {
-        let expected = ("", (Cow::from("http://getreu.net"), Cow::from("My blog")));
+    fn test_srcset_candidates() {
         assert_eq!(
-            parse_attributes(r#"abc href="http://getreu.net" abc title="My blog" abc"#).unwrap(),
-            expected
+            srcset_candidates("a.jpg 1x,b.jpg\t2x ,c.jpg, d.jpg foo(1, 2) 3x"),
+            vec![
+                ("a.jpg", "1x"),
+                ("b.jpg", "2x"),
+                ("c.jpg", ""),
+                ("d.jpg", "foo(1, 2) 3x")
+            ]
         );
+        assert_eq!(srcset_candidates(""), vec![]);
+    }
 
-        let expected = nom::Err::Error(nom::error::Error::new(
-            "href",
-            nom::error::ErrorKind::ManyMN,
-        ));
+    #[test]
+    fn test_html_form2dest() {
+        let expected = (
+            "\n<input name=q></FORM>",
+            (
+                Cow::from("/search?lang=en&safe=1"),
+                Cow::from("/search?lang=en&safe=1"),
+                Cow::from("get"),
+            ),
+        );
         assert_eq!(
-            parse_attributes(r#" href="http://getreu.net" href="http://blog.getreu.net" "#)
-                .unwrap_err(),
+            html_form2dest(
+                "<FORM id=f METHOD=put ACTION='/search?lang=en&amp;safe=1' novalidate>\n<input name=q></FORM>"
+            )
+            .unwrap(),
             expected
         );
 
-        let expected = nom::Err::Error(nom::error::Error::new(
-            "title",
-            nom::error::ErrorKind::ManyMN,
-        ));
         assert_eq!(
-            parse_attributes(r#" href="http://getreu.net" title="a" title="b" "#).unwrap_err(),
-            expected
+            html_form2dest(r#"<form method="post">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<form method="post">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
         );
+    }
 
-        let expected = nom::Err::Error(nom::error::Error::new("", nom::error::ErrorKind::Eof));
+    #[test]
+    fn test_html_refresh2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/?a=1&b=2"),
+                Cow::from("https://example.org/?a=1&b=2"),
+                Cow::from("3"),
+            ),
+        );
         assert_eq!(
-            parse_attributes(r#" title="title" "#).unwrap_err(),
+            html_refresh2dest(
+                "<META HTTP-EQUIV=REFRESH CONTENT=\"3.5 , Url = 'https://example.org/?a=1&amp;b=2' trailing\">abc"
+            )
+            .unwrap(),
             expected
         );
+
+        assert_eq!(
+            html_refresh2dest(r#"<meta http-equiv="refresh" content="30">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<meta http-equiv="refresh" content="30">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            html_refresh2dest(r#"<meta name="description" content="0; url=x">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<meta name="description" content="0; url=x">abc"#,
+                nom::error::ErrorKind::Tag
+            )))
+        );
     }
 
     #[test]
-    fn test_attribute_list() {
+    fn test_refresh_content() {
+        assert_eq!(refresh_content("5;url=a.html"), Some(("5", "a.html")));
+        assert_eq!(refresh_content(" 5 a.html "), Some(("5", "a.html")));
+        assert_eq!(refresh_content(".5,\"a.html\"b"), Some(("0", "a.html")));
+        assert_eq!(refresh_content("0; urlx.html"), Some(("0", "urlx.html")));
+        assert_eq!(refresh_content("0; URL=\"a.html"), Some(("0", "a.html")));
+        assert_eq!(refresh_content("5x; url=a.html"), None);
+        assert_eq!(refresh_content("url=a.html"), None);
+        assert_eq!(refresh_content("0; url=''"), None);
+    }
+
+    #[test]
+    fn test_html_meta2dest() {
         let expected = (
-            "",
-            vec![
-                ("", Cow::from("")),
-                ("href", Cow::from("http://getreu.net")),
-                ("", Cow::from("")),
-                ("title", Cow::from("My blog")),
-                ("", Cow::from("")),
-            ],
+            "abc",
+            (
+                Cow::from("og:image:secure_url"),
+                Cow::from("https://example.org/a.png?w=1&h=2"),
+                Cow::from(""),
+            ),
         );
         assert_eq!(
-            attribute_list(r#"abc href="http://getreu.net" abc title="My blog" abc"#).unwrap(),
+            html_meta2dest(
+                "<META NAME=\"OG:Image:Secure_URL\" CONTENT=' https://example.org/a.png?w=1&amp;h=2\n'>abc"
+            )
+            .unwrap(),
             expected
         );
+
+        assert_eq!(
+            html_meta2dest(r#"<meta property="og:title" content="Title">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<meta property="og:title" content="Title">abc"#,
+                nom::error::ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            html_meta2dest(r#"<meta property="og:url" content="">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<meta property="og:url" content="">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
     }
 
     #[test]
-    fn test_attribute() {
-        let expected = (" abc", ("href", Cow::from("http://getreu.net")));
-        assert_eq!(
-            attribute(r#"href="http://getreu.net" abc"#).unwrap(),
-            expected
+    fn test_html_text2dest_mode() {
+        let i = r#"<a/href="h"title='t' TITLE="u" / >na&amp;me</a >abc"#;
+        let expected = (
+            "abc",
+            (
+                (Cow::from("na&me"), Cow::from("h"), Cow::from("t")),
+                vec![
+                    HtmlWarning::StraySlash,
+                    HtmlWarning::MissingWhitespace("title"),
+                    HtmlWarning::DuplicateAttribute("TITLE"),
+                    HtmlWarning::StraySlash,
+                ],
+            ),
+        );
+        assert_eq!(html_text2dest_mode(i, HtmlMode::Lenient).unwrap(), expected);
+        assert!(html_text2dest_mode(i, HtmlMode::Strict).is_err());
+
+        // Well-formed anchors give the same result in both modes.
+        let i = r#"<a href="h" title="t">name</a>abc"#;
+        let expected = (
+            "abc",
+            ((Cow::from("name"), Cow::from("h"), Cow::from("t")), vec![]),
         );
+        assert_eq!(html_text2dest_mode(i, HtmlMode::Lenient).unwrap(), expected);
+        assert_eq!(html_text2dest_mode(i, HtmlMode::Strict).unwrap(), expected);
+
         assert_eq!(
-            attribute(r#"href='http://getreu.net' abc"#).unwrap(),
-            expected
+            html_text2dest_mode(r#"<a title="t" href=>name</a>abc"#, HtmlMode::Lenient),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<a title="t" href=>name</a>abc"#,
+                nom::error::ErrorKind::Verify
+            )))
         );
-        // Only allowed when no space in value.
         assert_eq!(
-            attribute(r#"href=http://getreu.net abc"#).unwrap(),
-            expected
+            html_text2dest_mode(r#"<abbr href="h">name</a>abc"#, HtmlMode::Lenient),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"bbr href="h">name</a>abc"#,
+                nom::error::ErrorKind::Tag
+            )))
         );
+    }
 
-        let expected = (" abc", ("href", Cow::from("http://getreu.net/<>")));
+    #[test]
+    fn test_html_anchor_target() {
         assert_eq!(
-            attribute(r#"href="http://getreu.net/&lt;&gt;" abc"#).unwrap(),
-            expected
+            html_anchor_target("<A ID='top' NAME=\"caf&eacute;\" href=#x>abc"),
+            Ok(("abc", vec![Cow::from("top"), Cow::from("café")]))
         );
         assert_eq!(
-            attribute(r#"href='http://getreu.net/&lt;&gt;' abc"#).unwrap(),
-            expected
+            html_anchor_target(r#"<a id="x" name="x"/>abc"#),
+            Ok(("abc", vec![Cow::from("x")]))
         );
-        // Only allowed when no space in value.
         assert_eq!(
-            attribute(r#"href=http://getreu.net/&lt;&gt; abc"#).unwrap(),
-            expected
+            html_anchor_target(r#"<section data-x id=s-1>abc"#),
+            Ok(("abc", vec![Cow::from("s-1")]))
+        );
+        // `name` defines a target only on anchors.
+        assert_eq!(
+            html_anchor_target(r#"<input name="q">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"<input name="q">abc"#,
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            html_anchor_target(r#"</div id="x">abc"#),
+            Err(nom::Err::Error(nom::error::Error::new(
+                r#"/div id="x">abc"#,
+                nom::error::ErrorKind::TakeWhile1
+            )))
         );
+    }
 
-        let expected = (" abc", ("", Cow::from("")));
-        assert_eq!(attribute("bool abc").unwrap(), expected);
+    #[test]
+    fn test_html_link_lossy() {
+        let i = "<html><body><div><a HREF=a.html TITLE=\"A &amp; B\"/>A &lt;\n <em>&amp; B</div>\
+<a name=top>no link</a><!-- <a href=c.html>c</a> -->\
+<script>document.write('<a href=\"d.html\">d</a>')</SCRIPT>\
+<a href=\"e.html\">1 < 2<br>3</A><a href=\"f.html\"";
+        let expected = (
+            "",
+            vec![
+                (
+                    Cow::from("A < & B"),
+                    Cow::from("a.html"),
+                    Cow::from("A & B"),
+                ),
+                (Cow::from("1 < 2 3"), Cow::from("e.html"), Cow::from("")),
+                (Cow::from(""), Cow::from("f.html"), Cow::from("")),
+            ],
+        );
+        assert_eq!(html_link_lossy(i).unwrap(), expected);
 
-        let expected = nom::Err::Error(nom::error::Error::new(
-            "1name",
-            nom::error::ErrorKind::Verify,
-        ));
-        assert_eq!(attribute("1name").unwrap_err(), expected);
+        assert_eq!(html_link_lossy("no links <a"), Ok(("", vec![])));
+    }
 
-        let expected = nom::Err::Error(nom::error::Error::new(
-            r#"1name="http://getreu.net"#,
-            nom::error::ErrorKind::Verify,
-        ));
+    #[test]
+    fn test_take_until_tag_a_closing() {
         assert_eq!(
-            attribute(r#"1name="http://getreu.net"#).unwrap_err(),
-            expected
+            take_until_tag_a_closing("a<b>b</b></A >abc"),
+            Ok(("abc", "a<b>b</b>"))
+        );
+        // Anchors can contain block-level elements.
+        assert_eq!(
+            take_until_tag_a_closing("<div>a</div></a>abc"),
+            Ok(("abc", "<div>a</div>"))
+        );
+        // Missing `</a>`.
+        assert_eq!(
+            take_until_tag_a_closing("a<abbr>b</abbr>\n<A href=x>c</a>abc"),
+            Ok(("<A href=x>c</a>abc", "a<abbr>b</abbr>\n"))
+        );
+        assert_eq!(
+            take_until_tag_a_closing("a<li>b<a href=x>c</a>abc"),
+            Ok(("<li>b<a href=x>c</a>abc", "a"))
+        );
+        assert_eq!(
+            take_until_tag_a_closing("a<pre>b<p>c"),
+            Ok(("<pre>b<p>c", "a"))
+        );
+        assert_eq!(
+            take_until_tag_a_closing("a<param>b"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "a<param>b",
+                nom::error::ErrorKind::TakeUntil
+            )))
         );
     }
 }
@@ -0,0 +1,332 @@
+//! This module implements parsers for hyperlinks in iCalendar files.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `icalendar_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn icalendar_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = icalendar_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an iCalendar `URL` or `ATTACH` property.
+///
+/// The [properties](https://www.rfc-editor.org/rfc/rfc5545#section-3.8.4.6)
+/// are content lines `URL:https://example.org/` or
+/// `ATTACH;FMTTYPE=application/pdf:https://example.org/agenda.pdf`. Long
+/// content lines are folded: every continuation line starts with a space or
+/// a tab. An `ATTACH` property with `VALUE=BINARY` holds inline data and is
+/// no link.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link destination_ is the unfolded value. The _link text_
+/// is the value of the parameter `FILENAME` or `X-FILENAME`, if present,
+/// otherwise the _link destination_. The _link title_ is the property name,
+/// `URL` or `ATTACH`. Names are case insensitive. The line ending of the
+/// content line is not consumed.
+///
+/// The parser expects to start at the beginning of the content line to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::icalendar::icalendar_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   icalendar_text2dest("URL:https://example.org/events/\r\n 2024\r\nEND:VEVENT"),
+///   Ok(("\r\nEND:VEVENT", (Cow::from("https://example.org/events/2024"),
+///                          Cow::from("https://example.org/events/2024"),
+///                          Cow::from("URL"))))
+/// );
+/// assert_eq!(
+///   icalendar_text2dest("ATTACH;FILENAME=agenda.pdf:https://example.org/a.pdf\r\nabc"),
+///   Ok(("\r\nabc", (Cow::from("agenda.pdf"),
+///                   Cow::from("https://example.org/a.pdf"),
+///                   Cow::from("ATTACH"))))
+/// );
+/// ```
+pub fn icalendar_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, params, value)) = icalendar_content_line(i)?;
+    let link_title = if name.eq_ignore_ascii_case("URL") {
+        "URL"
+    } else if name.eq_ignore_ascii_case("ATTACH") {
+        "ATTACH"
+    } else {
+        return Err(nom::Err::Error(nom::error::Error::new(i, ErrorKind::Tag)));
+    };
+
+    let param = |wanted: &str| {
+        params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(wanted))
+            .map(|(_, v)| v.clone())
+    };
+    if value.is_empty()
+        || param("VALUE").is_some_and(|v| v.eq_ignore_ascii_case("BINARY"))
+        || param("ENCODING").is_some_and(|v| v.eq_ignore_ascii_case("BASE64"))
+    {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+    let link_text = param("FILENAME")
+        .or_else(|| param("X-FILENAME"))
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| value.clone());
+
+    Ok((j, (link_text, value, Cow::Borrowed(link_title))))
+}
+
+/// Parses a content line `name;param=value;...:value` as used by iCalendar
+/// and vCard.
+///
+/// It returns either `Ok((i, (name, parameters, value)))` or some error.
+/// Folded lines are unfolded first: a line ending followed by a space or a
+/// tab is removed. The name may be prefixed by a group, e.g. `item1.URL`,
+/// which is part of the returned name. Every item of `parameters` is a
+/// tuple `(name, value)`. Quotes around parameter values are removed and
+/// the [circumflex encoding](https://www.rfc-editor.org/rfc/rfc6868) is
+/// resolved. Multiple parameter values, e.g. `TYPE=work,pref`, are returned
+/// as one string. A parameter without name, e.g. `TEL;WORK:...` in vCard
+/// 2.1, is returned as `TYPE` parameter. The value is returned verbatim. The
+/// line ending is not consumed.
+/// ```
+/// use parse_hyperlinks::parser::icalendar::icalendar_content_line;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   icalendar_content_line("DESCRIPTION;LANGUAGE=en:Meet\r\n ing\r\nabc"),
+///   Ok(("\r\nabc", (Cow::from("DESCRIPTION"),
+///                   vec![(Cow::from("LANGUAGE"), Cow::from("en"))],
+///                   Cow::from("Meeting"))))
+/// );
+/// ```
+pub fn icalendar_content_line(
+    i: &str,
+) -> nom::IResult<&str, (Cow<str>, Vec<(Cow<str>, Cow<str>)>, Cow<str>)> {
+    // Find the end of the logical line.
+    let mut end = i.len();
+    let mut k = 0;
+    while let Some(idx) = i[k..].find('\n') {
+        let idx = k + idx;
+        if !i[idx + 1..].starts_with([' ', '\t']) {
+            end = if i[..idx].ends_with('\r') {
+                idx - 1
+            } else {
+                idx
+            };
+            break;
+        }
+        k = idx + 1;
+    }
+    let (line, rest) = i.split_at(end);
+
+    let error = || nom::Err::Error(nom::error::Error::new(i, ErrorKind::Verify));
+    match icalendar_unfold(line) {
+        Cow::Borrowed(line) => {
+            let (name, params, value) = icalendar_parse_line(line).ok_or_else(error)?;
+            let params = params
+                .into_iter()
+                .map(|(n, v)| (Cow::Borrowed(n), v))
+                .collect();
+            Ok((rest, (Cow::Borrowed(name), params, Cow::Borrowed(value))))
+        }
+        Cow::Owned(line) => {
+            let (name, params, value) = icalendar_parse_line(&line).ok_or_else(error)?;
+            Ok((
+                rest,
+                (
+                    Cow::Owned(name.to_string()),
+                    params
+                        .into_iter()
+                        .map(|(n, v)| (Cow::Owned(n.to_string()), Cow::Owned(v.into_owned())))
+                        .collect(),
+                    Cow::Owned(value.to_string()),
+                ),
+            ))
+        }
+    }
+}
+
+/// Splits an unfolded content line into name, parameters and value.
+fn icalendar_parse_line(line: &str) -> Option<(&str, Vec<(&str, Cow<str>)>, &str)> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.';
+    let end = line.find(|c: char| !is_name_char(c)).unwrap_or(line.len());
+    let name = &line[..end];
+    if name.is_empty() || name.starts_with('.') || name.ends_with('.') {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut k = &line[end..];
+    while let Some(param) = k.strip_prefix(';') {
+        let end = param
+            .find(|c: char| !is_name_char(c))
+            .unwrap_or(param.len());
+        let param_name = &param[..end];
+        if param_name.is_empty() {
+            return None;
+        }
+        let mut v = match param[end..].strip_prefix('=') {
+            Some(v) => v,
+            None => {
+                // vCard 2.1 allows `TYPE` values without parameter name.
+                params.push(("TYPE", Cow::Borrowed(param_name)));
+                k = &param[end..];
+                continue;
+            }
+        };
+        // Parameter values are separated by commas and can be quoted.
+        let start = v;
+        loop {
+            if let Some(quoted) = v.strip_prefix('"') {
+                v = &quoted[quoted.find('"')? + 1..];
+            } else {
+                v = &v[v.find([',', ';', ':', '"']).unwrap_or(v.len())..];
+            }
+            match v.strip_prefix(',') {
+                Some(r) => v = r,
+                None => break,
+            }
+        }
+        let raw = &start[..start.len() - v.len()];
+        params.push((param_name, icalendar_param_value(raw)));
+        k = v;
+    }
+
+    let value = k.strip_prefix(':')?;
+    Some((name, params, value))
+}
+
+/// Removes the quotes of a parameter value and resolves the circumflex
+/// encoding `^n`, `^^` and `^'`.
+fn icalendar_param_value(i: &str) -> Cow<str> {
+    if !i.contains(['"', '^']) {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut chars = i.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {}
+            '^' => match chars.peek() {
+                Some('n') => {
+                    s.push('\n');
+                    chars.next();
+                }
+                Some('^') => {
+                    s.push('^');
+                    chars.next();
+                }
+                Some('\'') => {
+                    s.push('"');
+                    chars.next();
+                }
+                _ => s.push('^'),
+            },
+            c => s.push(c),
+        }
+    }
+    Cow::Owned(s)
+}
+
+/// Removes every line ending followed by a space or a tab together with
+/// this whitespace character.
+fn icalendar_unfold(i: &str) -> Cow<str> {
+    if !i.contains('\n') {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut k = i;
+    while let Some(idx) = k.find('\n') {
+        s.push_str(k[..idx].strip_suffix('\r').unwrap_or(&k[..idx]));
+        // Remove the leading whitespace character of the continuation line.
+        k = &k[idx + 2..];
+    }
+    s.push_str(k);
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icalendar_text2dest() {
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("Agenda \"2024\".pdf"),
+                Cow::from("https://example.org/agenda.pdf"),
+                Cow::from("ATTACH"),
+            ),
+        );
+        assert_eq!(
+            icalendar_text2dest(
+                "attach;FMTTYPE=application/pdf;X-FILENAME=\"Agenda ^'20\n\t24^'.pdf\":https://\n example.org/agenda.pdf\nabc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "",
+            (
+                Cow::from("http://example.org/"),
+                Cow::from("http://example.org/"),
+                Cow::from("URL"),
+            ),
+        );
+        assert_eq!(
+            icalendar_text2dest("URL;VALUE=URI:http://example.org/").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            icalendar_text2dest("ATTACH;ENCODING=BASE64;VALUE=BINARY:VGhlIHF1aWNr\r\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "ATTACH;ENCODING=BASE64;VALUE=BINARY:VGhlIHF1aWNr\r\nabc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            icalendar_text2dest("URLS:http://example.org/\r\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "URLS:http://example.org/\r\nabc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            icalendar_text2dest("URL;VALUE=\"URI:http://example.org/\r\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "URL;VALUE=\"URI:http://example.org/\r\nabc",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_icalendar_content_line() {
+        let expected = (
+            "",
+            (
+                Cow::from("item1.TEL"),
+                vec![
+                    (Cow::from("TYPE"), Cow::from("WORK")),
+                    (Cow::from("TYPE"), Cow::from("VOICE")),
+                    (Cow::from("X-LABEL"), Cow::from("a,b;c")),
+                ],
+                Cow::from("+1-555-0100"),
+            ),
+        );
+        assert_eq!(
+            icalendar_content_line("item1.TEL;WORK;VOICE;X-LABEL=a,\"b;c\":+1-555-0100").unwrap(),
+            expected
+        );
+    }
+}
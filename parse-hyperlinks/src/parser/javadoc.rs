@@ -0,0 +1,191 @@
+//! This module implements parsers for Javadoc hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::html::html_text2dest;
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::space0;
+use nom::character::complete::space1;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `javadoc_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn javadoc_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = javadoc_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a Javadoc inline tag `{@link ...}` or `{@linkplain ...}`, or a
+/// block tag `@see ...`.
+///
+/// The [tags](https://docs.oracle.com/en/java/javase/21/docs/specs/javadoc/doc-comment-spec.html)
+/// reference a program element, e.g. `{@link java.util.List#add(int, Object)
+/// add}` or `@see String#equals(Object) equals`, followed by an optional
+/// label. `@see` also accepts an HTML anchor: `@see <a href="url">label</a>`.
+/// The form `@see "string"`, which is no link, is not recognized.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link destination_ is the reference as written,
+/// `package.Class#member(Type, Type)`, or the URL of the anchor. The _link
+/// text_ is the label, or the reference when there is none. The _link
+/// title_ is the tag name without `@`: `link`, `linkplain` or `see`. The
+/// label of `@see` reaches to the end of the line, the line ending is not
+/// consumed. The leading `*` of doc comment lines is part of the label,
+/// so strip it before parsing multi-line inline tags.
+///
+/// The parser expects to start at the tag start (`{` or `@`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::javadoc::javadoc_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   javadoc_text2dest("{@link java.util.List#add(int, Object) add} abc"),
+///   Ok((" abc", (Cow::from("add"), Cow::from("java.util.List#add(int, Object)"), Cow::from("link"))))
+/// );
+/// assert_eq!(
+///   javadoc_text2dest("@see <a href=\"https://openjdk.org/\">OpenJDK</a>\nabc"),
+///   Ok(("\nabc", (Cow::from("OpenJDK"), Cow::from("https://openjdk.org/"), Cow::from("see"))))
+/// );
+/// ```
+pub fn javadoc_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    // Inline tag.
+    if let Ok((j, name)) = nom::sequence::delimited(
+        tag::<_, _, nom::error::Error<&str>>("{@"),
+        alt((tag("linkplain"), tag("link"))),
+        nom::character::complete::multispace1,
+    )(i)
+    {
+        let end = j
+            .find('}')
+            .ok_or_else(|| nom::Err::Error(nom::error::Error::new(j, ErrorKind::TakeUntil)))?;
+        let (reference, label) = javadoc_reference(&j[..end])
+            .ok_or_else(|| nom::Err::Error(nom::error::Error::new(j, ErrorKind::Verify)))?;
+        return Ok((
+            &j[end + 1..],
+            (
+                javadoc_label(label, reference),
+                Cow::Borrowed(reference),
+                Cow::Borrowed(name),
+            ),
+        ));
+    }
+
+    // Block tag.
+    let (j, _) = nom::sequence::tuple((tag("@see"), space1))(i)?;
+    if let Ok((j, (link_text, link_destination, _))) = html_text2dest(j) {
+        let (j, _) = space0(j)?;
+        return Ok((j, (link_text, link_destination, Cow::Borrowed("see"))));
+    }
+    let end = j.find(['\r', '\n']).unwrap_or(j.len());
+    let (reference, label) = javadoc_reference(&j[..end])
+        .filter(|(r, _)| !r.starts_with(['"', '<']))
+        .ok_or_else(|| nom::Err::Error(nom::error::Error::new(j, ErrorKind::Verify)))?;
+
+    Ok((
+        &j[end..],
+        (
+            javadoc_label(label, reference),
+            Cow::Borrowed(reference),
+            Cow::Borrowed("see"),
+        ),
+    ))
+}
+
+/// Splits the tag content into the reference and the label. Whitespace in
+/// the parameter list of a method reference is part of the reference.
+/// Returns `None` if the reference is empty or its parentheses are not
+/// balanced.
+fn javadoc_reference(i: &str) -> Option<(&str, &str)> {
+    let i = i.trim();
+    let mut depth = 0;
+    for (idx, c) in i.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return None,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                return Some((&i[..idx], i[idx..].trim()));
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || i.is_empty() {
+        return None;
+    }
+    Some((i, ""))
+}
+
+/// Normalizes the whitespace of the label. An empty label is replaced by the
+/// reference.
+fn javadoc_label<'a>(label: &'a str, reference: &'a str) -> Cow<'a, str> {
+    if label.is_empty() {
+        Cow::Borrowed(reference)
+    } else if label.contains(['\n', '\r', '\t', ' ']) {
+        Cow::Owned(label.split_whitespace().collect::<Vec<&str>>().join(" "))
+    } else {
+        Cow::Borrowed(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_javadoc_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("#equals(Object)"),
+                Cow::from("#equals(Object)"),
+                Cow::from("linkplain"),
+            ),
+        );
+        assert_eq!(
+            javadoc_text2dest("{@linkplain  #equals(Object) }abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "\r\nabc",
+            (
+                Cow::from("the string class"),
+                Cow::from("java.lang.String#valueOf(char[], int)"),
+                Cow::from("see"),
+            ),
+        );
+        assert_eq!(
+            javadoc_text2dest(
+                "@see java.lang.String#valueOf(char[], int)  the  string class\r\nabc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            javadoc_text2dest("@see \"The Java Language Specification\"\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\"The Java Language Specification\"\nabc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            javadoc_text2dest("{@link List#add(int label}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "List#add(int label}abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            javadoc_text2dest("{@code List}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "{@code List}abc",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! This module implements parsers to extract URLs from JSON documents.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::character::complete::multispace0;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Maximum nesting depth of arrays and objects. Deeper nested documents are
+/// rejected to protect the stack.
+const JSON_MAX_DEPTH: usize = 128;
+
+/// Wrapper around `json_urls()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn json_urls_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = json_urls(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Parses a JSON value and extracts all string values that look like a URL.
+///
+/// A string looks like a URL when it starts with a scheme followed by
+/// `://`, e.g. `https://`, or with `mailto:`. Object keys are never
+/// extracted.
+///
+/// It returns either `Ok((i, links))` or some error, where every item of
+/// `links` is a tuple `(link_text, link_destination, Cow::from(""))`. The
+/// _link text_ is the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901)
+/// of the string value, e.g. `/items/0/url`. The links appear in document
+/// order. Escape sequences in strings are resolved. Leading and trailing
+/// whitespace is consumed.
+///
+/// The parser expects to start at the beginning of the value to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::json::json_urls;
+/// use std::borrow::Cow;
+///
+/// let i = r#"{"name": "example", "links": [
+///     {"rel": "home", "href": "https:\/\/example.org/"},
+///     {"rel": "mail", "href": "mailto:info@example.org"}
+/// ]}abc"#;
+///
+/// assert_eq!(
+///   json_urls(i),
+///   Ok(("abc", vec![
+///       (Cow::from("/links/0/href"), Cow::from("https://example.org/"), Cow::from("")),
+///       (Cow::from("/links/1/href"), Cow::from("mailto:info@example.org"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn json_urls(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let (i, strings) = json_strings(i)?;
+    let links = strings
+        .into_iter()
+        .filter(|(_, s)| json_looks_like_url(s))
+        .map(|(pointer, s)| (Cow::Owned(pointer), s, Cow::Borrowed("")))
+        .collect();
+    Ok((i, links))
+}
+
+/// Parses a JSON value and returns all its string values, object keys
+/// excluded, together with their
+/// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901).
+///
+/// It returns either `Ok((i, strings))` or some error, where every item of
+/// `strings` is a tuple `(json_pointer, value)`. The strings appear in
+/// document order. Escape sequences are resolved. Leading and trailing
+/// whitespace is consumed. This is the building block of `json_urls()`
+/// and other extractors working on JSON documents.
+/// ```
+/// use parse_hyperlinks::parser::json::json_strings;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   json_strings(r#"{"a": [1, "x\ty"], "b": null}abc"#),
+///   Ok(("abc", vec![(String::from("/a/1"), Cow::from("x\ty"))]))
+/// );
+/// ```
+pub fn json_strings(i: &str) -> nom::IResult<&str, Vec<(String, Cow<str>)>> {
+    let mut strings = Vec::new();
+    let mut pointer = String::new();
+    let (i, _) = json_value(i, &mut pointer, &mut strings, 0)?;
+    Ok((i, strings))
+}
+
+/// Parses a JSON value surrounded by optional whitespace. The string values
+/// found in the value are appended to `strings`. `pointer` is the JSON
+/// pointer of the value.
+fn json_value<'a>(
+    i: &'a str,
+    pointer: &mut String,
+    strings: &mut Vec<(String, Cow<'a, str>)>,
+    depth: usize,
+) -> nom::IResult<&'a str, ()> {
+    let (i, _) = multispace0(i)?;
+    let i = match i.chars().next() {
+        Some('"') => {
+            let (i, s) = json_string(i)?;
+            strings.push((pointer.clone(), s));
+            i
+        }
+        Some(c @ ('[' | '{')) => {
+            if depth >= JSON_MAX_DEPTH {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    i,
+                    ErrorKind::TooLarge,
+                )));
+            }
+            let closing = if c == '[' { ']' } else { '}' };
+            let (mut i, _) = nom::sequence::pair(char(c), multispace0)(i)?;
+            let pointer_len = pointer.len();
+            let mut index = 0;
+            while !i.starts_with(closing) {
+                if index > 0 {
+                    (i, _) = char(',')(i)?;
+                }
+                pointer.push('/');
+                if c == '[' {
+                    pointer.push_str(&index.to_string());
+                } else {
+                    let (j, key) = nom::sequence::delimited(
+                        multispace0,
+                        json_string,
+                        nom::sequence::pair(multispace0, char(':')),
+                    )(i)?;
+                    i = j;
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                }
+                (i, _) = json_value(i, pointer, strings, depth + 1)?;
+                pointer.truncate(pointer_len);
+                index += 1;
+            }
+            &i[1..]
+        }
+        _ => {
+            nom::combinator::recognize(alt((tag("true"), tag("false"), tag("null"), json_number)))(
+                i,
+            )?
+            .0
+        }
+    };
+    let (i, _) = multispace0(i)?;
+    Ok((i, ()))
+}
+
+/// Parses a JSON number.
+fn json_number(i: &str) -> nom::IResult<&str, &str> {
+    nom::combinator::recognize(nom::sequence::tuple((
+        nom::combinator::opt(char('-')),
+        nom::character::complete::digit1,
+        nom::combinator::opt(nom::sequence::pair(
+            char('.'),
+            nom::character::complete::digit1,
+        )),
+        nom::combinator::opt(nom::sequence::tuple((
+            nom::character::complete::one_of("eE"),
+            nom::combinator::opt(nom::character::complete::one_of("+-")),
+            nom::character::complete::digit1,
+        ))),
+    )))(i)
+}
+
+/// Parses a JSON string and resolves its escape sequences. Invalid
+/// surrogates are replaced by U+FFFD.
+fn json_string(i: &str) -> nom::IResult<&str, Cow<str>> {
+    let (j, _) = char('"')(i)?;
+    let end = {
+        let mut chars = j.char_indices();
+        loop {
+            match chars.next() {
+                Some((idx, '"')) => break idx,
+                Some((_, '\\')) => {
+                    chars.next();
+                }
+                Some((_, c)) if c < '\u{20}' => {
+                    return Err(nom::Err::Error(nom::error::Error::new(
+                        j,
+                        ErrorKind::EscapedTransform,
+                    )))
+                }
+                Some(_) => {}
+                None => {
+                    return Err(nom::Err::Error(nom::error::Error::new(
+                        j,
+                        ErrorKind::EscapedTransform,
+                    )))
+                }
+            }
+        }
+    };
+    let (raw, rest) = (&j[..end], &j[end + 1..]);
+    if !raw.contains('\\') {
+        return Ok((rest, Cow::Borrowed(raw)));
+    }
+
+    let error = || nom::Err::Error(nom::error::Error::new(raw, ErrorKind::EscapedTransform));
+    let hex4 = |s: &str| {
+        s.get(..4)
+            .filter(|h| h.chars().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+    };
+    let mut s = String::with_capacity(raw.len());
+    let mut k = raw;
+    while let Some(idx) = k.find('\\') {
+        s.push_str(&k[..idx]);
+        k = &k[idx + 1..];
+        let e = k.chars().next().ok_or_else(error)?;
+        k = &k[e.len_utf8()..];
+        match e {
+            '"' | '\\' | '/' => s.push(e),
+            'b' => s.push('\x08'),
+            'f' => s.push('\x0C'),
+            'n' => s.push('\n'),
+            'r' => s.push('\r'),
+            't' => s.push('\t'),
+            'u' => {
+                let mut n = hex4(k).ok_or_else(error)?;
+                k = &k[4..];
+                // Combine a surrogate pair.
+                if (0xD800..0xDC00).contains(&n) {
+                    if let Some(low) = k
+                        .strip_prefix("\\u")
+                        .and_then(hex4)
+                        .filter(|l| (0xDC00..0xE000).contains(l))
+                    {
+                        n = 0x10000 + ((n - 0xD800) << 10) + (low - 0xDC00);
+                        k = &k[6..];
+                    }
+                }
+                s.push(char::from_u32(n).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            _ => return Err(error()),
+        }
+    }
+    s.push_str(k);
+    Ok((rest, Cow::Owned(s)))
+}
+
+/// True when the string starts with a URL scheme followed by `://`, or with
+/// `mailto:`, and contains no whitespace. Other extractors of URLs in
+/// structured data, e.g. `yaml::yaml_urls()`, use the same heuristic.
+pub fn json_looks_like_url(s: &str) -> bool {
+    if s.len() > 7
+        && s.get(..7)
+            .is_some_and(|p| p.eq_ignore_ascii_case("mailto:"))
+    {
+        return true;
+    }
+    s.split_once("://").is_some_and(|(scheme, rest)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            && !rest.is_empty()
+            && !s.contains(char::is_whitespace)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_urls() {
+        let expected = (
+            "",
+            vec![
+                (
+                    Cow::from("/a~1b/m~0n/1"),
+                    Cow::from("https://example.org/\u{1F600}"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("/"),
+                    Cow::from("ftp://example.org/\"x\""),
+                    Cow::from(""),
+                ),
+            ],
+        );
+        assert_eq!(
+            json_urls(
+                " {\"a/b\": {\"m~n\": [1.5e-3, \"https://example.org/\\uD83D\\uDE00\", true]},\n \"https://key.org\": null, \"\": \"ftp://example.org/\\\"x\\\"\",\n \"no\": \"https:// x\"} "
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            json_urls("\"mailto:info@example.org\"abc").unwrap(),
+            (
+                "abc",
+                vec![(
+                    Cow::from(""),
+                    Cow::from("mailto:info@example.org"),
+                    Cow::from("")
+                )]
+            )
+        );
+        assert_eq!(json_urls("[]").unwrap(), ("", vec![]));
+
+        assert_eq!(
+            json_urls("[\"https://example.org/\" \"x\"]"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\"x\"]",
+                ErrorKind::Char
+            )))
+        );
+        assert_eq!(
+            json_urls("{\"a\": \"\\q\"}"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\\q",
+                ErrorKind::EscapedTransform
+            )))
+        );
+        assert_eq!(
+            json_urls("{\"a\": \"\\é\"}"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\\é",
+                ErrorKind::EscapedTransform
+            )))
+        );
+        assert_eq!(
+            json_urls("{\"a\": \"日本語テキスト\", \"b\": \"mailtö:x\"}").unwrap(),
+            ("", vec![])
+        );
+        // DEL and C1 controls need no escape.
+        assert_eq!(
+            json_urls("{\"a\": \"x\u{7f}y\u{85}\", \"u\": \"https://example.org/\"}").unwrap(),
+            (
+                "",
+                vec![(
+                    Cow::from("/u"),
+                    Cow::from("https://example.org/"),
+                    Cow::from("")
+                )]
+            )
+        );
+        let deep = "[".repeat(JSON_MAX_DEPTH + 1);
+        assert_eq!(
+            json_urls(&deep),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "[",
+                ErrorKind::TooLarge
+            )))
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! This module implements an extractor for hyperlinks in the Markdown cells
+//! of Jupyter notebooks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::iterator::Hyperlink;
+use crate::parser::json::json_strings;
+use crate::parser::parse::Format;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Parses a Jupyter notebook `.ipynb` and extracts the hyperlinks of its
+/// Markdown cells.
+///
+/// The notebook is a JSON document in the
+/// [nbformat 4](https://nbformat.readthedocs.io/en/latest/format_description.html)
+/// format. The `source` of a cell is either a string or an array of lines,
+/// which are concatenated. Only cells with `"cell_type": "markdown"` are
+/// scanned for Markdown links, including inline HTML, with
+/// `iterator::Hyperlink`, i.e. link references are resolved within the cell.
+///
+/// It returns either `Ok((i, links))` or some error, where every item of
+/// `links` is a tuple `(cell_index, line, (link_text, link_destination,
+/// link_title))`. `cell_index` counts from 0, as in nbformat's `cells`
+/// array, `line` is the line within the cell's source where the hyperlink
+/// starts, counting from 1. As the source is decoded from JSON, the link
+/// content is owned.
+/// ```
+/// use parse_hyperlinks::parser::jupyter::ipynb_links;
+/// use std::borrow::Cow;
+///
+/// let i = r##"{"cells": [
+///   {"cell_type": "code", "source": ["print('[a](b)')"]},
+///   {"cell_type": "markdown", "metadata": {}, "source": [
+///     "# Title\n",
+///     "See [the docs](https://example.org/ \"Docs\")."
+///   ]}
+/// ], "nbformat": 4, "nbformat_minor": 5}"##;
+///
+/// assert_eq!(
+///   ipynb_links(i),
+///   Ok(("", vec![
+///       (1, 2, (Cow::from("the docs"), Cow::from("https://example.org/"), Cow::from("Docs"))),
+///   ]))
+/// );
+/// ```
+pub fn ipynb_links(
+    i: &str,
+) -> nom::IResult<
+    &str,
+    Vec<(
+        usize,
+        usize,
+        (Cow<'static, str>, Cow<'static, str>, Cow<'static, str>),
+    )>,
+> {
+    let (i, strings) = json_strings(i)?;
+
+    // Every item is `(is_markdown, source)`.
+    let mut cells: BTreeMap<usize, (bool, String)> = BTreeMap::new();
+    for (pointer, value) in strings {
+        let mut segments = pointer.split('/').skip(1);
+        if segments.next() != Some("cells") {
+            continue;
+        }
+        let index = match segments.next().and_then(|s| s.parse::<usize>().ok()) {
+            Some(index) => index,
+            None => continue,
+        };
+        let cell = cells.entry(index).or_default();
+        match (segments.next(), segments.next()) {
+            (Some("cell_type"), None) => cell.0 = value == "markdown",
+            (Some("source"), _) => cell.1.push_str(&value),
+            _ => {}
+        }
+    }
+
+    let mut links = Vec::new();
+    for (index, (is_markdown, source)) in cells {
+        if !is_markdown {
+            continue;
+        }
+        for ((_, consumed, remaining), (te, de, ti)) in
+            Hyperlink::with_format(&source, false, Format::Markdown)
+        {
+            let offset = source.len() - remaining.len() - consumed.len();
+            let line = source[..offset].matches('\n').count() + 1;
+            links.push((
+                index,
+                line,
+                (
+                    Cow::Owned(te.into_owned()),
+                    Cow::Owned(de.into_owned()),
+                    Cow::Owned(ti.into_owned()),
+                ),
+            ));
+        }
+    }
+
+    Ok((i, links))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+
+    #[test]
+    fn test_ipynb_links() {
+        let i = r#"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "source": "[a][r]\n\n<https://example.org/a>\n\n[r]: https://example.org/r"
+  },
+  {
+   "source": ["[not](markdown)"],
+   "cell_type": "raw"
+  },
+  {
+   "cell_type": "markdown",
+   "source": ["Line 1\n", "Line 2 [b](https://example.org/b)\n", "`no <rst>`_ link:no[adoc]"]
+  }
+ ],
+ "metadata": {"links": "[m](https://example.org/m)"}
+}
+"#;
+        let expected = (
+            "",
+            vec![
+                (
+                    0,
+                    1,
+                    (
+                        Cow::from("a"),
+                        Cow::from("https://example.org/r"),
+                        Cow::from(""),
+                    ),
+                ),
+                (
+                    0,
+                    3,
+                    (
+                        Cow::from("https://example.org/a"),
+                        Cow::from("https://example.org/a"),
+                        Cow::from(""),
+                    ),
+                ),
+                (
+                    2,
+                    2,
+                    (
+                        Cow::from("b"),
+                        Cow::from("https://example.org/b"),
+                        Cow::from(""),
+                    ),
+                ),
+            ],
+        );
+        assert_eq!(ipynb_links(i).unwrap(), expected);
+
+        assert_eq!(
+            ipynb_links("{\"cells\": [}"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "}",
+                ErrorKind::Digit
+            )))
+        );
+    }
+}
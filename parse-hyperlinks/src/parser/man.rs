@@ -0,0 +1,191 @@
+//! This module implements parsers for hyperlinks in man pages written with
+//! the groff `man` macros.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::is_not;
+use nom::bytes::complete::tag;
+use nom::character::complete::line_ending;
+use nom::character::complete::space1;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `man_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn man_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = man_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a hyperlink in a man page formatted with the `.UR`/`.UE` or
+/// `.MT`/`.ME` macros.
+///
+/// The [macros](https://man7.org/linux/man-pages/man7/groff_man.7.html)
+/// enclose the _link text_, which spans zero or more lines:
+/// ```text
+/// .UR https://www.gnu.org/software/groff/
+/// the groff homepage
+/// .UE .
+/// ```
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The lines of the _link text_ are joined with a space.
+/// Macro calls inside the _link text_, e.g. `.I italic`, are replaced by
+/// their arguments; escape sequences are returned verbatim. When there is
+/// no _link text_, the _link text_ is the URL or the email address. For
+/// `.MT`, the _link destination_ is the email address prefixed with
+/// `mailto:`. The optional argument of `.UE` or `.ME`, usually trailing
+/// punctuation, is consumed, the line ending is not.
+///
+/// The parser expects to start at the beginning of the line (`.`) to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::man::man_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     man_text2dest(".UR https://www.gnu.org/software/groff/\nthe groff\nhomepage\n.UE .\nabc"),
+///     Ok(("\nabc", (Cow::from("the groff homepage"),
+///                   Cow::from("https://www.gnu.org/software/groff/"),
+///                   Cow::from(""))))
+/// );
+/// assert_eq!(
+///     man_text2dest(".MT groff@gnu.org\n.ME\nabc"),
+///     Ok(("\nabc", (Cow::from("groff@gnu.org"),
+///                   Cow::from("mailto:groff@gnu.org"),
+///                   Cow::from(""))))
+/// );
+/// ```
+pub fn man_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (opening, address)) = nom::sequence::terminated(
+        nom::sequence::separated_pair(
+            alt((tag(".UR"), tag(".MT"))),
+            space1,
+            nom::combinator::map(is_not("\r\n"), str::trim_end),
+        ),
+        line_ending,
+    )(i)?;
+    if address.contains(char::is_whitespace) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            address,
+            ErrorKind::Verify,
+        )));
+    }
+    let closing = if opening == ".UR" { ".UE" } else { ".ME" };
+
+    // Collect the link text lines until the closing macro.
+    let mut link_text = Vec::new();
+    let mut k = j;
+    let k = loop {
+        if let Ok((rest, _)) = nom::sequence::pair(
+            tag::<_, _, nom::error::Error<&str>>(closing),
+            alt((
+                nom::combinator::eof,
+                nom::combinator::peek(line_ending),
+                space1,
+            )),
+        )(k)
+        {
+            // Consume the optional trailing argument.
+            let (rest, _) = nom::combinator::opt(is_not("\r\n"))(rest)?;
+            break rest;
+        }
+        let (rest, line) = nom::sequence::terminated(
+            nom::combinator::verify(
+                nom::bytes::complete::take_till(|c| c == '\r' || c == '\n'),
+                |s: &str| !s.starts_with(".UR") && !s.starts_with(".MT"),
+            ),
+            line_ending,
+        )(k)?;
+        let line = match line.strip_prefix(['.', '\'']) {
+            // Macro call: keep the arguments only.
+            Some(l) => l.trim_start().split_once(' ').map_or("", |(_, a)| a),
+            None => line,
+        };
+        let line = line.trim();
+        if !line.is_empty() {
+            link_text.push(line);
+        }
+        k = rest;
+    };
+
+    let link_text = if link_text.is_empty() {
+        Cow::Borrowed(address)
+    } else {
+        Cow::Owned(link_text.join(" "))
+    };
+    let link_destination = if opening == ".MT" {
+        Cow::Owned(format!("mailto:{}", address))
+    } else {
+        Cow::Borrowed(address)
+    };
+
+    Ok((k, (link_text, link_destination, Cow::Borrowed(""))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_man_text2dest() {
+        let expected = (
+            "\r\nabc",
+            (
+                Cow::from("the groff homepage"),
+                Cow::from("https://www.gnu.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            man_text2dest(
+                ".UR https://www.gnu.org/ \r\nthe\r\n.I groff\r\n\r\nhomepage\r\n.UE ).\r\nabc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "",
+            (
+                Cow::from("https://www.gnu.org/"),
+                Cow::from("https://www.gnu.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            man_text2dest(".UR https://www.gnu.org/\n.UE").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            man_text2dest(".UR https://www.gnu.org/\nthe groff homepage\n.ME\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new("", ErrorKind::CrLf)))
+        );
+        assert_eq!(
+            man_text2dest(
+                ".UR https://www.gnu.org/\nthe groff homepage\n.UR https://example.org\n.UE\n"
+            ),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".UR https://example.org\n.UE\n",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            man_text2dest(".UR https://www.gnu.org/ homepage\n.UE\n"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "https://www.gnu.org/ homepage",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            man_text2dest(".URL https://www.gnu.org/\n.UE\n"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "L https://www.gnu.org/\n.UE\n",
+                ErrorKind::Space
+            )))
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::parser::Link;
 use crate::take_until_unbalanced;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
+use nom::character::complete::multispace0;
 use nom::character::complete::multispace1;
 use nom::combinator::*;
 use std::borrow::Cow;
@@ -89,11 +90,23 @@ pub fn md_label2dest_link(i: &str) -> nom::IResult<&str, Link> {
 /// document. [Link reference
 /// definitions](https://spec.commonmark.org/0.30/#link-reference-definition)
 /// can come either before or after the links that use them.
+///
+/// Footnote definitions `[^label]: text` are not _link reference
+/// definitions_. This parser rejects them, so that the hyperlinks in their
+/// text can be found by the inline link parsers.
 pub fn md_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
     // Consume up to three spaces.
     let (i, _) = nom::bytes::complete::take_while_m_n(0, 3, |c| c == ' ')(i)?;
     // Take label.
     let (i, link_text) = md_link_label(i)?;
+    // This is a footnote definition. The hyperlinks in its text are found by
+    // the inline link parsers.
+    if link_text.starts_with('^') {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
     let (i, _) = nom::character::complete::char(':')(i)?;
     // Take spaces.
     let (i, _) = verify(nom::character::complete::multispace1, |s: &str| {
@@ -101,13 +114,31 @@ pub fn md_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str
     })(i)?;
     // Take destination.
     let (i, link_destination) = md_link_destination(i)?;
-    // Try, but do not fail.
-    let (i, link_title) = alt((
-        // Take link title.
-        md_link_title,
-        nom::combinator::success(Cow::from("")),
-    ))(i)?;
+    // Try, but do not fail. When the title is followed by other characters
+    // than whitespace, the definition ends with the destination, provided
+    // that the title starts on a new line.
+    let (i, link_title) = match nom::sequence::terminated(
+        alt((
+            // Take link title.
+            md_link_title,
+            nom::combinator::success(Cow::from("")),
+        )),
+        md_line_end,
+    )(i)
+    {
+        Ok(res) => res,
+        Err(e) => match md_line_end(i) {
+            Ok((i, _)) if i.starts_with('\n') => (i, Cow::from("")),
+            _ => return Err(e),
+        },
+    };
 
+    Ok((i, (link_text, link_destination, link_title)))
+}
+
+/// Consumes whitespace until the end of the line or the end of the input.
+/// The newline character itself is not consumed.
+fn md_line_end(i: &str) -> nom::IResult<&str, ()> {
     // Now consume as much whitespace as possible.
     let (i, _) = nom::character::complete::space0(i)?;
 
@@ -116,7 +147,7 @@ pub fn md_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str
         let _ = nom::character::complete::newline(i)?;
     }
 
-    Ok((i, (link_text, link_destination, link_title)))
+    Ok((i, ()))
 }
 
 /// Wrapper around `md_text2label()` that packs the result in
@@ -143,6 +174,8 @@ pub fn md_text2label_link(i: &str) -> nom::IResult<&str, Link> {
 ///    are used as the link’s text. The link’s URI and title are provided by the
 ///    matching link reference definition. Thus, `[foo]` is equivalent to `[foo][]`.
 ///
+/// Footnote references `[^label]` are not recognized as _reference links_.
+///
 /// This parser expects to start at the beginning of the link `[` to succeed.
 /// It should always run at last position after all other parsers.
 /// ```rust
@@ -177,9 +210,121 @@ pub fn md_text2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
         let _ = nom::character::complete::none_of("[(")(i)?;
     }
 
+    // Footnote references `[^label]` are not hyperlinks.
+    if link_label.starts_with('^') {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+
     Ok((i, (link_text, link_label)))
 }
 
+/// Wrapper around `md_autolink()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn md_autolink_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = md_autolink(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a Markdown _autolink_.
+///
+/// This parser expects to start at the beginning of the link `<` to succeed.
+/// The _link text_ is the URI or email address as written. For email
+/// addresses `mailto:` is prepended to the _link destination_.
+/// `link_title` is always the empty `Cow::Borrowed("")`.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::markdown::md_autolink;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   md_autolink("<https://example.org>abc"),
+///   Ok(("abc", (Cow::from("https://example.org"), Cow::from("https://example.org"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   md_autolink("<me@example.org>abc"),
+///   Ok(("abc", (Cow::from("me@example.org"), Cow::from("mailto:me@example.org"), Cow::from(""))))
+/// );
+/// ```
+///
+/// [CommonMark Spec](https://spec.commonmark.org/0.30/#autolinks)\
+/// [Autolinks](https://spec.commonmark.org/0.30/#autolinks) are absolute URIs
+/// and email addresses inside `<` and `>`. They are parsed as links, with the
+/// URL or email address as the link label.
+pub fn md_autolink(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    nom::sequence::delimited(
+        tag("<"),
+        alt((
+            map(md_parse_absolute_uri, |s| {
+                (Cow::from(s), Cow::from(s), Cow::from(""))
+            }),
+            map(md_parse_email_address, |s| {
+                (
+                    Cow::from(s),
+                    Cow::from(format!("mailto:{}", s)),
+                    Cow::from(""),
+                )
+            }),
+        )),
+        tag(">"),
+    )(i)
+}
+
+/// An [absolute URI](https://spec.commonmark.org/0.30/#absolute-uri) consists
+/// of a [scheme](https://spec.commonmark.org/0.30/#scheme) followed by a colon
+/// (`:`) followed by zero or more characters other than [ASCII control
+/// characters](https://spec.commonmark.org/0.30/#ascii-control-character),
+/// [space](https://spec.commonmark.org/0.30/#space), `<`, and `>`.
+///
+/// A [scheme](https://spec.commonmark.org/0.30/#scheme) is any sequence of
+/// 2–32 characters beginning with an ASCII letter and followed by any
+/// combination of ASCII letters, digits, or the symbols plus (`+`), period
+/// (`.`), or hyphen (`-`).
+fn md_parse_absolute_uri(i: &str) -> nom::IResult<&str, &str> {
+    recognize(nom::sequence::tuple((
+        verify(
+            nom::bytes::complete::take_while1(|c: char| {
+                c.is_ascii_alphanumeric() || c == '+' || c == '.' || c == '-'
+            }),
+            |s: &str| {
+                (2..=32).contains(&s.len()) && s.starts_with(|c: char| c.is_ascii_alphabetic())
+            },
+        ),
+        tag(":"),
+        nom::bytes::complete::take_till(|c: char| {
+            c.is_ascii_control() || c == ' ' || c == '<' || c == '>'
+        }),
+    )))(i)
+}
+
+/// An [email address](https://spec.commonmark.org/0.30/#email-address), for
+/// these purposes, is anything that matches the non-normative regex from the
+/// [HTML5 spec](https://html.spec.whatwg.org/multipage/forms.html#e-mail-state-(type=email)):
+/// ```text
+/// /^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?
+/// (?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$/
+/// ```
+fn md_parse_email_address(i: &str) -> nom::IResult<&str, &str> {
+    // One label of the domain name.
+    fn domain_label(i: &str) -> nom::IResult<&str, &str> {
+        verify(
+            nom::bytes::complete::take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-'),
+            |s: &str| s.len() <= 63 && !s.starts_with('-') && !s.ends_with('-'),
+        )(i)
+    }
+
+    recognize(nom::sequence::tuple((
+        nom::bytes::complete::take_while1(|c: char| {
+            c.is_ascii_alphanumeric() || ".!#$%&'*+/=?^_`{|}~-".contains(c)
+        }),
+        tag("@"),
+        domain_label,
+        nom::multi::many0_count(nom::sequence::preceded(tag("."), domain_label)),
+    )))(i)
+}
+
 /// Parses _link text_.
 /// Brackets are allowed in the
 /// [link text](https://spec.commonmark.org/0.29/#link-text) only if (a) they are
@@ -200,7 +345,7 @@ fn md_link_text(i: &str) -> nom::IResult<&str, Cow<str>> {
 /// be at least one non-whitespace character. Unescaped square bracket characters
 /// are not allowed inside the opening and closing square brackets of link
 /// labels. A link label can have at most 999 characters inside the square
-/// brackets (TODO).
+/// brackets.
 /// [CommonMark Spec](https://spec.commonmark.org/0.29/#link-label)
 fn md_link_label(i: &str) -> nom::IResult<&str, Cow<str>> {
     nom::combinator::map_parser(
@@ -214,7 +359,7 @@ fn md_link_label(i: &str) -> nom::IResult<&str, Cow<str>> {
                 ),
                 tag("]"),
             ),
-            |l: &str| l.len() <= LABEL_LEN_MAX,
+            |l: &str| l.len() <= LABEL_LEN_MAX && !l.trim().is_empty(),
         ),
         md_escaped_str_transform,
     )(i)
@@ -259,30 +404,98 @@ fn md_parse_link_destination(i: &str) -> nom::IResult<&str, &str> {
     ))(i)
 }
 
-/// Matches `md_link_destination` in parenthesis.
+/// Matches `md_link_destination` and an optional `md_link_title` in
+/// parenthesis.
+///
+/// The opening `(` may be followed and the closing `)` preceded by optional
+/// spaces or tabs (including up to one line ending). A `)` inside the _link
+/// title_ does not close the parenthesis.
 fn md_link_destination_enclosed(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
-    let (rest, inner) =
-        nom::sequence::delimited(tag("("), take_until_unbalanced('(', ')'), tag(")"))(i)?;
-    let (i, link_destination) = md_link_destination(inner)?;
-    let (_i, link_title) = alt((
+    let (i, _) = tag("(")(i)?;
+    // Up to one line ending, a second one would end a blank line, even when
+    // it contains spaces.
+    let (i, _) = verify(multispace0, |s: &str| s.split('\n').count() <= 2)(i)?;
+    let (i, link_destination) = map_parser(
+        alt((
+            nom::sequence::delimited(
+                tag("<"),
+                nom::bytes::complete::escaped(
+                    nom::character::complete::none_of("\\<>\n\r"),
+                    '\\',
+                    nom::character::complete::one_of(ESCAPABLE),
+                ),
+                tag(">"),
+            ),
+            map(nom::bytes::complete::tag("<>"), |_| ""),
+            md_parse_link_destination_balanced,
+        )),
+        md_escaped_str_transform,
+    )(i)?;
+    let (i, link_title) = alt((
         // Take link title.
         md_link_title,
         nom::combinator::success(Cow::from("")),
     ))(i)?;
+    let (i, _) = verify(multispace0, |s: &str| s.split('\n').count() <= 2)(i)?;
+    let (i, _) = tag(")")(i)?;
 
-    Ok((rest, (link_destination, link_title)))
+    Ok((i, (link_destination, link_title)))
+}
+
+/// Parses a _link destination_ not enclosed in `<>` until the first space,
+/// line ending, control character or unbalanced closing parenthesis `)`.
+/// Escaped parenthesis `\(` and `\)` are not counted. The parser does not
+/// consume the closing parenthesis. It fails, when an opening parenthesis is
+/// not closed.
+fn md_parse_link_destination_balanced(i: &str) -> nom::IResult<&str, &str> {
+    let mut depth = 0;
+    let mut end = i.len();
+    let mut it = i.char_indices().peekable();
+    while let Some((idx, c)) = it.next() {
+        match c {
+            '\\' => {
+                // Skip the escaped character.
+                if let Some((_, c)) = it.peek() {
+                    if ESCAPABLE.contains(*c) {
+                        it.next();
+                    }
+                }
+            }
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = idx;
+                break;
+            }
+            ')' => depth -= 1,
+            c if c.is_whitespace() || c.is_ascii_control() => {
+                end = idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::TakeUntil,
+        )));
+    }
+
+    Ok((&i[end..], &i[..end]))
 }
 
 /// This is a wrapper around `md_parse_link_title()`. It takes its result
-/// and removes the `\` before the escaped characters `ESCAPABLE`.
+/// and removes the `\` before escaped ASCII punctuation characters.
 fn md_link_title(i: &str) -> nom::IResult<&str, Cow<str>> {
-    nom::combinator::map_parser(md_parse_link_title, md_escaped_str_transform)(i)
+    let (i, title) = md_parse_link_title(i)?;
+    Ok((i, md_unescape_ascii_punctuation(title)))
 }
 
 /// A link title is always preceded one or more whitespace inluding
 /// one newline.
-/// [CommonMark Spec](https://spec.commonmark.org/0.29/#link-title)
-/// A [link title](https://spec.commonmark.org/0.29/#link-title) consists of either
+/// [CommonMark Spec](https://spec.commonmark.org/0.30/#link-title)
+/// A [link title](https://spec.commonmark.org/0.30/#link-title) consists of either
 ///
 ///  - a sequence of zero or more characters between straight double-quote
 ///    characters (`"`), including a `"` character only if it is
@@ -294,39 +507,68 @@ fn md_link_title(i: &str) -> nom::IResult<&str, Cow<str>> {
 ///    (`(...)`), including a `(` or `)` character only if it is
 ///    backslash-escaped.
 ///
-///  Although [link titles](https://spec.commonmark.org/0.29/#link-title) may
+///  Although [link titles](https://spec.commonmark.org/0.30/#link-title) may
 ///  span multiple lines, they may not contain a [blank
-///  line](https://spec.commonmark.org/0.29/#blank-line).
+///  line](https://spec.commonmark.org/0.30/#blank-line).
 fn md_parse_link_title(i: &str) -> nom::IResult<&str, &str> {
     nom::sequence::preceded(
         verify(multispace1, |s: &str| !s.contains("\n\n")),
         verify(
             alt((
                 nom::sequence::delimited(tag("("), take_until_unbalanced('(', ')'), tag(")")),
-                nom::sequence::delimited(
-                    tag("'"),
-                    nom::bytes::complete::escaped(
-                        nom::character::complete::none_of(r#"\'"#),
-                        '\\',
-                        nom::character::complete::one_of(ESCAPABLE),
-                    ),
-                    tag("'"),
-                ),
-                nom::sequence::delimited(
-                    tag("\""),
-                    nom::bytes::complete::escaped(
-                        nom::character::complete::none_of(r#"\""#),
-                        '\\',
-                        nom::character::complete::one_of(ESCAPABLE),
-                    ),
-                    tag("\""),
-                ),
+                md_parse_quoted_title('\''),
+                md_parse_quoted_title('"'),
             )),
             |s: &str| !s.contains("\n\n"),
         ),
     )(i)
 }
 
+/// Parses a link title enclosed in `quote` characters and returns its
+/// content. Inside the title, `quote` must be backslash-escaped. The title
+/// can be empty.
+fn md_parse_quoted_title(quote: char) -> impl Fn(&str) -> nom::IResult<&str, &str> {
+    move |i: &str| {
+        let (j, _) = nom::character::complete::char(quote)(i)?;
+        let mut chars = j.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    // Skip the escaped character.
+                    chars.next();
+                }
+                c if c == quote => return Ok((&j[idx + c.len_utf8()..], &j[..idx])),
+                _ => {}
+            }
+        }
+        Err(nom::Err::Error(nom::error::Error::new(
+            j,
+            nom::error::ErrorKind::TakeUntil,
+        )))
+    }
+}
+
+/// Removes the `\` before [escaped ASCII punctuation
+/// characters](https://spec.commonmark.org/0.30/#backslash-escapes). A
+/// backslash before any other character is a literal backslash.
+fn md_unescape_ascii_punctuation(i: &str) -> Cow<str> {
+    if !i.contains('\\') {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut chars = i.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next_if(char::is_ascii_punctuation) {
+                Some(e) => s.push(e),
+                None => s.push(c),
+            },
+            _ => s.push(c),
+        }
+    }
+    Cow::Owned(s)
+}
+
 /// Remove the `\` before the escaped characters `ESCAPABLE`.
 fn md_escaped_str_transform(i: &str) -> nom::IResult<&str, Cow<str>> {
     nom::combinator::map(
@@ -386,6 +628,28 @@ mod tests {
                 (Cow::from("text"), Cow::from("url"), Cow::from("link title"))
             ))
         );
+        // No line ending between `<` and `>`.
+        assert!(md_text2dest("[a](<foo\nbar>)").is_err());
+        assert!(md_text2dest("[a](<foo\r\nbar>)").is_err());
+        // Blank lines with spaces or CRLF line endings.
+        assert_eq!(
+            md_text2dest("[text](\n  \nurl)abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\n  \nurl)abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            md_text2dest("[text](url\r\n\r\n)abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\r\n\r\n)abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            md_text2dest("[text](\r\nurl\r\n)abc"),
+            Ok(("abc", (Cow::from("text"), Cow::from("url"), Cow::from(""))))
+        );
         // [Example 483](https://spec.commonmark.org/0.30/#example-483)
         assert_eq!(
             md_text2dest("[](./target.md)abc"),
@@ -409,6 +673,112 @@ mod tests {
             md_text2dest("[]()abc"),
             Ok(("abc", (Cow::from(""), Cow::from(""), Cow::from(""))))
         );
+        // Escaped brackets in link text.
+        assert_eq!(
+            md_text2dest(r#"[te\]x\[t](url)abc"#),
+            Ok((
+                "abc",
+                (Cow::from("te]x[t"), Cow::from("url"), Cow::from(""))
+            ))
+        );
+        // Closing parenthesis in link title.
+        assert_eq!(
+            md_text2dest(r#"[text](url "ti)tle")abc"#),
+            Ok((
+                "abc",
+                (Cow::from("text"), Cow::from("url"), Cow::from("ti)tle"))
+            ))
+        );
+        // Closing parenthesis in link destination.
+        assert_eq!(
+            md_text2dest("[text](<u)rl>)abc"),
+            Ok(("abc", (Cow::from("text"), Cow::from("u)rl"), Cow::from(""))))
+        );
+        assert_eq!(
+            md_text2dest(r#"[text](u\)rl)abc"#),
+            Ok(("abc", (Cow::from("text"), Cow::from("u)rl"), Cow::from(""))))
+        );
+        // Whitespace around destination and title.
+        assert_eq!(
+            md_text2dest("[text](\n  url\n  'title'  )abc"),
+            Ok((
+                "abc",
+                (Cow::from("text"), Cow::from("url"), Cow::from("title"))
+            ))
+        );
+        // All three title delimiters, escapes and empty titles.
+        assert_eq!(
+            md_text2dest(r#"[text](url "a \"b\" \* \c")abc"#),
+            Ok((
+                "abc",
+                (
+                    Cow::from("text"),
+                    Cow::from("url"),
+                    Cow::from(r#"a "b" * \c"#)
+                )
+            ))
+        );
+        assert_eq!(
+            md_text2dest(r#"[text](url 'it\'s \\')abc"#),
+            Ok((
+                "abc",
+                (Cow::from("text"), Cow::from("url"), Cow::from(r#"it's \"#))
+            ))
+        );
+        assert_eq!(
+            md_text2dest("[text](url\n(ti\\(tle))abc"),
+            Ok((
+                "abc",
+                (Cow::from("text"), Cow::from("url"), Cow::from("ti(tle"))
+            ))
+        );
+        assert_eq!(
+            md_text2dest("[text](url '')abc"),
+            Ok(("abc", (Cow::from("text"), Cow::from("url"), Cow::from(""))))
+        );
+        // Nothing else is allowed after the title.
+        assert_eq!(
+            md_text2dest("[text](url title)abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "title)abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            md_text2dest("[text](ur(l abc)"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "ur(l abc)",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+
+    #[test]
+    fn test_md_parse_link_destination_balanced() {
+        assert_eq!(
+            md_parse_link_destination_balanced("url)abc"),
+            Ok((")abc", "url"))
+        );
+        assert_eq!(
+            md_parse_link_destination_balanced("u(r)l)abc"),
+            Ok((")abc", "u(r)l"))
+        );
+        assert_eq!(
+            md_parse_link_destination_balanced(r#"u\)rl)abc"#),
+            Ok((")abc", r#"u\)rl"#))
+        );
+        assert_eq!(
+            md_parse_link_destination_balanced("url \"title\")abc"),
+            Ok((" \"title\")abc", "url"))
+        );
+        assert_eq!(md_parse_link_destination_balanced(")abc"), Ok((")abc", "")));
+        assert_eq!(
+            md_parse_link_destination_balanced("u(rl"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "u(rl",
+                ErrorKind::TakeUntil
+            )))
+        );
     }
 
     #[test]
@@ -449,6 +819,22 @@ mod tests {
                 ErrorKind::NoneOf
             )))
         );
+        // Footnote references are not reference links.
+        assert_eq!(
+            md_text2label("[^note]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "abc",
+                ErrorKind::Verify
+            )))
+        );
+        // A whitespace only link label is not a full reference link.
+        assert_eq!(
+            md_text2label("[text][ ]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "[ ]abc",
+                ErrorKind::NoneOf
+            )))
+        );
         assert_eq!(
             md_text2label("[text](url)abc"),
             Err(nom::Err::Error(nom::error::Error::new(
@@ -488,6 +874,20 @@ mod tests {
                 (Cow::from("text"), Cow::from("url"), Cow::from("title"))
             ))
         );
+        assert_eq!(
+            md_label2dest("[text]: url\n  (ti\\)tle \\*)\nabc"),
+            Ok((
+                "\nabc",
+                (Cow::from("text"), Cow::from("url"), Cow::from("ti)tle *"))
+            ))
+        );
+        assert_eq!(
+            md_label2dest("[text]: url \"\"\nabc"),
+            Ok((
+                "\nabc",
+                (Cow::from("text"), Cow::from("url"), Cow::from(""))
+            ))
+        );
         assert_eq!(
             md_label2dest("[text]: url\n\"title\"\nabc"),
             Ok((
@@ -599,6 +999,32 @@ mod tests {
                 ErrorKind::Char
             )))
         );
+        // Footnote definitions are not link reference definitions.
+        assert_eq!(
+            md_label2dest("[^note]: https://example.org\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ": https://example.org\nabc",
+                ErrorKind::Verify
+            )))
+        );
+        // The title is not part of the definition, when followed by other
+        // characters.
+        // [Example 210](https://spec.commonmark.org/0.30/#example-210)
+        assert_eq!(
+            md_label2dest("[text]: url\n\"title\" ok\nabc"),
+            Ok((
+                "\n\"title\" ok\nabc",
+                (Cow::from("text"), Cow::from("url"), Cow::from(""))
+            ))
+        );
+        // [Example 209](https://spec.commonmark.org/0.30/#example-209)
+        assert_eq!(
+            md_label2dest("[text]: url \"title\" ok\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "ok\nabc",
+                ErrorKind::Char
+            )))
+        );
         assert_eq!(
             md_label2dest("[text]:\n\nurl \"link title\"\nabc"),
             Err(nom::Err::Error(nom::error::Error::new(
@@ -608,6 +1034,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_md_autolink() {
+        assert_eq!(
+            md_autolink("<http://foo.bar.baz>abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("http://foo.bar.baz"),
+                    Cow::from("http://foo.bar.baz"),
+                    Cow::from("")
+                )
+            ))
+        );
+        assert_eq!(
+            md_autolink("<mailto:me@ex.org>abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("mailto:me@ex.org"),
+                    Cow::from("mailto:me@ex.org"),
+                    Cow::from("")
+                )
+            ))
+        );
+        // [Example 597](https://spec.commonmark.org/0.30/#example-597)
+        assert_eq!(
+            md_autolink("<a+b+c:d>abc"),
+            Ok((
+                "abc",
+                (Cow::from("a+b+c:d"), Cow::from("a+b+c:d"), Cow::from(""))
+            ))
+        );
+        // [Example 606](https://spec.commonmark.org/0.30/#example-606)
+        assert_eq!(
+            md_autolink("<foo+special@Bar.baz-bar0.com>abc"),
+            Ok((
+                "abc",
+                (
+                    Cow::from("foo+special@Bar.baz-bar0.com"),
+                    Cow::from("mailto:foo+special@Bar.baz-bar0.com"),
+                    Cow::from("")
+                )
+            ))
+        );
+        // [Example 602](https://spec.commonmark.org/0.30/#example-602)
+        assert!(md_autolink("<https://foo.bar/baz bim>").is_err());
+        // [Example 609](https://spec.commonmark.org/0.30/#example-609)
+        assert!(md_autolink("<m:abc>").is_err());
+        // [Example 610](https://spec.commonmark.org/0.30/#example-610)
+        assert!(md_autolink("<foo.bar.baz>").is_err());
+        assert!(md_autolink("<a href=\"x\">").is_err());
+    }
+
     #[test]
     fn test_md_link_text() {
         assert_eq!(
@@ -642,6 +1121,23 @@ mod tests {
             md_link_label("[text: url"),
             Err(nom::Err::Error(nom::error::Error::new("", ErrorKind::Tag)))
         );
+        // At least one non-whitespace character is required.
+        // [Example 552](https://spec.commonmark.org/0.30/#example-552)
+        assert_eq!(
+            md_link_label("[]: url"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "]: url",
+                ErrorKind::Escaped
+            )))
+        );
+        // [Example 553](https://spec.commonmark.org/0.30/#example-553)
+        assert_eq!(
+            md_link_label("[\n ]: url"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "[\n ]: url",
+                ErrorKind::Verify
+            )))
+        );
         assert_eq!(
             md_link_label("[t[ext: url"),
             Err(nom::Err::Error(nom::error::Error::new(
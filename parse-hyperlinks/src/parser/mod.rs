@@ -2,18 +2,45 @@
 //! definitions from text input.
 
 pub mod asciidoc;
+pub mod bibtex;
+pub mod creole;
+pub mod css;
+pub mod docbook;
+pub mod dokuwiki;
+pub mod doxygen;
+pub mod email;
+pub mod feed;
+pub mod gemtext;
 pub mod html;
+pub mod icalendar;
+pub mod javadoc;
+pub mod json;
+pub mod jupyter;
+pub mod man;
 pub mod markdown;
+pub mod opml;
+pub mod org_mode;
 pub mod parse;
+pub mod plaintext;
+pub mod pod;
 pub mod restructured_text;
+pub mod rtf;
+pub mod rustdoc;
+pub mod sitemap;
+pub mod svg;
+pub mod texinfo;
+pub mod toml;
+pub mod vcard;
 pub mod wikitext;
+pub mod xml;
+pub mod yaml;
 
 use std::borrow::Cow;
 
 /// A link can be an _inline link_, a _reference link_, a _link reference
 /// definition_, a combined _inline link / link reference definition_, a
-/// _reference alias_ or an _inline image_. This is the main return type of this
-/// API.
+/// _reference alias_, a _cross reference_ or an _inline image_. This is the
+/// main return type of this API.
 ///
 /// The _link title_ in Markdown is optional, when not given the string is set
 /// to the empty string `""`.  The back ticks \` in reStructuredText can be
@@ -141,11 +168,178 @@ pub enum Link<'a> {
     /// ```
     Label2Label(Cow<'a, str>, Cow<'a, str>),
 
+    /// An internal **cross reference** points to an anchor, e.g. a section, a
+    /// block or an inline anchor, in the same or in another document. Unlike
+    /// the _link destination_ of an _inline link_, the _cross reference
+    /// target_ is not an URL.
+    /// * Asciidoc examples:
+    ///   ```adoc
+    ///   xref:xref_target[link_text]
+    ///
+    ///   <<xref_target,link_text>>
+    ///   ```
+    ///   When no _link text_ is given, it is set to the empty string.
+    ///
+    /// The tuple is defined as follows:
+    /// ```text
+    /// Text2Xref(link_text, xref_target)
+    /// ```
+    Text2Xref(Cow<'a, str>, Cow<'a, str>),
+
     /// Inline Image.
     /// The tuple is defined as follows:
     /// ```text
     /// Image(img_alt, img_src)
     /// ```
-    /// Note: this crate does not contain parsers for this variant.
+    /// Note: this crate contains only the Asciidoc parser
+    /// `asciidoc::adoc_img()` and the HTML parser `html::html_img()` for this
+    /// variant. They are not called by `parse::take_link()`.
     Image(Cow<'a, str>, Cow<'a, str>),
 }
+
+impl Link<'_> {
+    /// Returns the kind of the link, which tells usages, definitions and
+    /// images apart, even when they are packed in the same tuple type.
+    /// Autolinks can not be told apart by the variant alone, they are
+    /// `LinkKind::Inline` here, see `parse::take_link_kind()`.
+    /// ```
+    /// use parse_hyperlinks::parser::{Link, LinkKind};
+    /// use parse_hyperlinks::parser::parse::take_link;
+    ///
+    /// let i = "abc <https://example.org> abc `text`_\n.. _text: https://example.org";
+    ///
+    /// let (i, (_, link)) = take_link(i).unwrap();
+    /// assert_eq!(link.kind(), LinkKind::Inline);
+    /// let (i, (_, link)) = take_link(i).unwrap();
+    /// assert_eq!(link.kind(), LinkKind::Reference);
+    /// let (_, (_, link)) = take_link(i).unwrap();
+    /// assert_eq!(link.kind(), LinkKind::Definition);
+    /// ```
+    pub fn kind(&self) -> LinkKind {
+        match self {
+            Link::Text2Dest(..) | Link::TextLabel2Dest(..) => LinkKind::Inline,
+            Link::Text2Label(..) => LinkKind::Reference,
+            Link::Label2Dest(..) | Link::Label2Label(..) => LinkKind::Definition,
+            Link::Text2Xref(..) => LinkKind::Xref,
+            Link::Image(..) => LinkKind::Image,
+        }
+    }
+}
+
+/// Kinds of hyperlinks, see `Link::kind()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum LinkKind {
+    /// An _inline link_, `Link::Text2Dest` or `Link::TextLabel2Dest`, e.g.
+    /// `[text](destination)`.
+    #[default]
+    Inline,
+    /// An _inline link_ whose _link text_ is its _link destination_, e.g.
+    /// the Markdown autolink `<https://example.org>` or a bare URL. Returned
+    /// by `parse::take_link_kind()`, not by `Link::kind()`.
+    Autolink,
+    /// A _reference link_, `Link::Text2Label`, e.g. `` `text`_ ``.
+    Reference,
+    /// A _link reference definition_, `Link::Label2Dest`, or a _reference
+    /// alias_, `Link::Label2Label`, e.g. `.. _label: destination`.
+    Definition,
+    /// A _cross reference_, `Link::Text2Xref`, e.g. `<<target,text>>`.
+    Xref,
+    /// An inline image, `Link::Image`.
+    Image,
+    /// An anchor or internal target, e.g. `` _`target` ``, as returned by
+    /// `iterator::Definitions`. The parsers
+    /// `restructured_text::rst_inline_target()` and
+    /// `html::html_anchor_target()` return only the target names.
+    Target,
+}
+
+/// A hyperlink with named fields, an alternative to the tuple
+/// `(link_text, link_destination, link_title)` returned by the `*_text2dest()`
+/// parsers and by `iterator::Hyperlink`. Both convert into each other with
+/// `From`.
+///
+/// ```
+/// use parse_hyperlinks::{iterator::*, parser::*};
+/// use parse_hyperlinks::parser::markdown::md_text2dest;
+/// use std::borrow::Cow;
+///
+/// let (_, link) = md_text2dest(r#"[text](dest "title")abc"#).unwrap();
+/// let link = ParsedLink::from(link);
+/// assert_eq!(link.name, "text");
+/// assert_eq!(link.destination, "dest");
+/// assert_eq!(link.title, "title");
+///
+/// let owned: ParsedLink<'static> = link.clone().into_owned();
+/// assert_eq!(owned, link);
+///
+/// let mut link = ParsedLink::new("https://example.org", "https://example.org", "");
+/// link.kind = LinkKind::Autolink;
+///
+/// let link = ParsedLink::from(Hyperlink::new("[a](b)", false).next().unwrap().1);
+/// assert_eq!(link.destination, "b");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
+pub struct ParsedLink<'a> {
+    /// The _link text_, or the _link label_ of a _link reference
+    /// definition_.
+    pub name: Cow<'a, str>,
+    /// The _link destination_.
+    pub destination: Cow<'a, str>,
+    /// The _link title_, empty when not given.
+    pub title: Cow<'a, str>,
+    /// The kind of the hyperlink, `LinkKind::Inline` unless set otherwise.
+    pub kind: LinkKind,
+    /// The markup the hyperlink was parsed from, e.g. `[text](dest)`, for
+    /// tools replacing links in the input. Empty when not known. The iterator
+    /// `iterator::Hyperlink` returns it as `consumed_characters`.
+    pub source: Cow<'a, str>,
+}
+
+impl<'a> ParsedLink<'a> {
+    /// Constructor for a hyperlink.
+    pub fn new(
+        name: impl Into<Cow<'a, str>>,
+        destination: impl Into<Cow<'a, str>>,
+        title: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            destination: destination.into(),
+            title: title.into(),
+            kind: LinkKind::default(),
+            source: Cow::Borrowed(""),
+        }
+    }
+
+    /// Copies borrowed fields, so that the hyperlink does not depend on the
+    /// parsed input anymore.
+    pub fn into_owned(self) -> ParsedLink<'static> {
+        ParsedLink {
+            name: Cow::Owned(self.name.into_owned()),
+            destination: Cow::Owned(self.destination.into_owned()),
+            title: Cow::Owned(self.title.into_owned()),
+            kind: self.kind,
+            source: Cow::Owned(self.source.into_owned()),
+        }
+    }
+}
+
+impl<'a> From<(Cow<'a, str>, Cow<'a, str>, Cow<'a, str>)> for ParsedLink<'a> {
+    fn from((name, destination, title): (Cow<'a, str>, Cow<'a, str>, Cow<'a, str>)) -> Self {
+        Self {
+            name,
+            destination,
+            title,
+            kind: LinkKind::default(),
+            source: Cow::Borrowed(""),
+        }
+    }
+}
+
+impl<'a> From<ParsedLink<'a>> for (Cow<'a, str>, Cow<'a, str>, Cow<'a, str>) {
+    fn from(link: ParsedLink<'a>) -> Self {
+        (link.name, link.destination, link.title)
+    }
+}
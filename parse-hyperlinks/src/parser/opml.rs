@@ -0,0 +1,146 @@
+//! This module implements parsers to extract hyperlinks from OPML outlines.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::xml_start_tag;
+use crate::parser::Link;
+use nom::error::Error;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `opml_outline()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn opml_outline_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = opml_outline(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Parses the start tag of an OPML `<outline>` element and extracts the
+/// hyperlinks of its `xmlUrl` and `htmlUrl` attributes.
+///
+/// It returns either `Ok((i, links))` or some error, where every item of
+/// `links` is a tuple `(link_text, link_destination, link_title)`. The
+/// [OPML 2.0](https://opml.org/spec2.opml) subscription list stores the
+/// feed URL in `xmlUrl` and the URL of the website in `htmlUrl`. The
+/// _link text_ is the value of the `text` attribute, the _link title_ the
+/// value of the optional `title` attribute. When `text` is missing, the
+/// _link text_ is the URL. The links appear in the order `xmlUrl`,
+/// `htmlUrl`. When the outline has neither attribute, e.g. it is a
+/// category grouping other outlines, `links` is empty.
+///
+/// As many exporters do not respect the case of the attribute names, they
+/// are compared case insensitively. Only the start tag is consumed: nested
+/// outlines are parsed by subsequent calls.
+///
+/// The parser expects to start at the element start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::opml::opml_outline;
+/// use std::borrow::Cow;
+///
+/// let i = r#"<outline text="Example" type="rss" xmlUrl="https://example.org/feed.xml"
+///     htmlUrl="https://example.org/"/>abc"#;
+///
+/// assert_eq!(
+///   opml_outline(i),
+///   Ok(("abc", vec![
+///       (Cow::from("Example"), Cow::from("https://example.org/feed.xml"), Cow::from("")),
+///       (Cow::from("Example"), Cow::from("https://example.org/"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn opml_outline(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let (j, (name, attributes, _)) = xml_start_tag(i)?;
+    if name != "outline" {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    }
+
+    let mut link_text = None;
+    let mut link_title = Cow::Borrowed("");
+    let mut xml_url = None;
+    let mut html_url = None;
+    for (name, value) in attributes {
+        if name.eq_ignore_ascii_case("text") {
+            link_text = Some(value);
+        } else if name.eq_ignore_ascii_case("title") {
+            link_title = value;
+        } else if name.eq_ignore_ascii_case("xmlUrl") {
+            xml_url = Some(value);
+        } else if name.eq_ignore_ascii_case("htmlUrl") {
+            html_url = Some(value);
+        }
+    }
+
+    let links = [xml_url, html_url]
+        .into_iter()
+        .flatten()
+        .filter(|url| !url.is_empty())
+        .map(|url| {
+            (
+                link_text
+                    .clone()
+                    .filter(|te| !te.is_empty())
+                    .unwrap_or_else(|| url.clone()),
+                url,
+                link_title.clone(),
+            )
+        })
+        .collect();
+
+    Ok((j, links))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opml_outline() {
+        let expected = (
+            "abc",
+            vec![(
+                Cow::from("News & more"),
+                Cow::from("https://example.org/feed.xml"),
+                Cow::from("Example news"),
+            )],
+        );
+        assert_eq!(
+            opml_outline(
+                "<outline text='News &amp; more' title=\"Example news\" xmlurl='https://example.org/feed.xml' htmlUrl=''>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            vec![(
+                Cow::from("https://example.org/"),
+                Cow::from("https://example.org/"),
+                Cow::from(""),
+            )],
+        );
+        assert_eq!(
+            opml_outline("<outline htmlUrl=\"https://example.org/\" />abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            opml_outline("<outline text=\"Category\">abc").unwrap(),
+            ("abc", vec![])
+        );
+        assert_eq!(
+            opml_outline("<body>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<body>abc",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
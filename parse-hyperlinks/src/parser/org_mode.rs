@@ -0,0 +1,209 @@
+//! This module implements parsers for Org mode hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::is_not;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use std::borrow::Cow;
+
+/// Link types recognized in _angle links_ and _plain links_. This is a subset
+/// of Org mode's `org-link-types`.
+const ORG_LINK_TYPES: [&str; 8] = [
+    "doi", "file", "ftp", "http", "https", "mailto", "news", "shell",
+];
+
+/// Wrapper around `org_angle_link()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn org_angle_link_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = org_angle_link(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an Org mode _angle link_.
+///
+/// An [angle link](https://orgmode.org/manual/External-Links.html) is a
+/// link of a known type enclosed in angle brackets. Inside the brackets,
+/// whitespace sequences including newlines are replaced by one space.
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))`
+/// or some error. As Org mode does not know a _link text_ for this type of
+/// link, the _link text_ is a copy of the _link destination_. The _link
+/// title_ is always empty.
+///
+/// The parser expects to start at the link start (`<`) to succeed.
+/// ```rust
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::org_mode::org_angle_link;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   org_angle_link("<https://example.org>abc"),
+///   Ok(("abc", (Cow::from("https://example.org"),
+///               Cow::from("https://example.org"),
+///               Cow::from(""))))
+/// );
+/// assert_eq!(
+///   org_angle_link("<file:my\n  notes.org>abc"),
+///   Ok(("abc", (Cow::from("file:my notes.org"),
+///               Cow::from("file:my notes.org"),
+///               Cow::from(""))))
+/// );
+/// ```
+pub fn org_angle_link(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, link) = nom::sequence::delimited(
+        char('<'),
+        nom::combinator::verify(is_not("<>[]"), |s: &str| {
+            !s.contains("\n\n") && org_parse_link_type(s).is_ok()
+        }),
+        char('>'),
+    )(i)?;
+
+    let link = if link.contains(char::is_whitespace) {
+        Cow::Owned(link.split_whitespace().collect::<Vec<&str>>().join(" "))
+    } else {
+        Cow::Borrowed(link)
+    };
+
+    Ok((i, (link.clone(), link, Cow::Borrowed(""))))
+}
+
+/// Wrapper around `org_plain_link()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn org_plain_link_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = org_plain_link(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an Org mode _plain link_.
+///
+/// A [plain link](https://orgmode.org/manual/External-Links.html) is a
+/// link of a known type embedded in the text without any markup. The link
+/// ends before whitespace, brackets or parentheses. Trailing punctuation
+/// is not part of the link. It returns either
+/// `Ok((i, (link_text, link_destination, link_title)))` or some error.
+/// The _link text_ is a copy of the _link destination_. The _link title_ is
+/// always empty.
+///
+/// The parser expects to start at the link start (the first letter of the
+/// link type) to succeed.
+/// ```rust
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::org_mode::org_plain_link;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   org_plain_link("https://example.org/page. abc"),
+///   Ok((". abc", (Cow::from("https://example.org/page"),
+///                 Cow::from("https://example.org/page"),
+///                 Cow::from(""))))
+/// );
+/// ```
+pub fn org_plain_link(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (_, link) = is_not(" \t\r\n()<>[]")(i)?;
+    let link = link.trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '/');
+    // As trailing `:` is removed, the path can not be empty.
+    let _ = org_parse_link_type(link)?;
+
+    Ok((
+        &i[link.len()..],
+        (Cow::Borrowed(link), Cow::Borrowed(link), Cow::Borrowed("")),
+    ))
+}
+
+/// Parses a link type of `ORG_LINK_TYPES` followed by `:`. Returns the
+/// remaining path.
+fn org_parse_link_type(i: &str) -> nom::IResult<&str, &str> {
+    nom::sequence::terminated(
+        nom::combinator::verify(nom::character::complete::alphanumeric1, |s: &str| {
+            ORG_LINK_TYPES.contains(&s)
+        }),
+        tag(":"),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+
+    #[test]
+    fn test_org_angle_link() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("mailto:me@example.org"),
+                Cow::from("mailto:me@example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            org_angle_link("<mailto:me@example.org>abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            org_angle_link("<unknown:path>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "unknown:path>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            org_angle_link("<https://exa\n\nmple.org>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "https://exa\n\nmple.org>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            org_angle_link("<https://example.org abc"),
+            Err(nom::Err::Error(nom::error::Error::new("", ErrorKind::Char)))
+        );
+    }
+
+    #[test]
+    fn test_org_plain_link() {
+        let expected = (
+            "), abc",
+            (
+                Cow::from("file:/home/me/notes.org"),
+                Cow::from("file:/home/me/notes.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            org_plain_link("file:/home/me/notes.org), abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "",
+            (
+                Cow::from("https://example.org/"),
+                Cow::from("https://example.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(org_plain_link("https://example.org/").unwrap(), expected);
+
+        assert_eq!(
+            org_plain_link("https:. abc"),
+            Err(nom::Err::Error(nom::error::Error::new("", ErrorKind::Tag)))
+        );
+        assert_eq!(
+            org_plain_link("example.org abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "example.org",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            org_plain_link(" https://example.org"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " https://example.org",
+                ErrorKind::IsNot
+            )))
+        );
+    }
+}
@@ -7,26 +7,310 @@
 use crate::parser::asciidoc::adoc_label2dest_link;
 use crate::parser::asciidoc::adoc_text2dest_link;
 use crate::parser::asciidoc::adoc_text2label_link;
+use crate::parser::asciidoc::adoc_text2xref_link;
+use crate::parser::html::html_area2dest_link;
+use crate::parser::html::html_comment;
 use crate::parser::html::html_text2dest_link;
+use crate::parser::html::html_text2dest_mode;
+use crate::parser::html::HtmlMode;
+use crate::parser::markdown::md_autolink_link;
 use crate::parser::markdown::md_label2dest_link;
 use crate::parser::markdown::md_text2dest_link;
 use crate::parser::markdown::md_text2label_link;
+use crate::parser::restructured_text::rst_explicit_markup;
+use crate::parser::restructured_text::rst_image2dest_link;
 use crate::parser::restructured_text::rst_label2dest_link;
 use crate::parser::restructured_text::rst_label2label_link;
 use crate::parser::restructured_text::rst_text2dest_link;
 use crate::parser::restructured_text::rst_text2label_link;
 use crate::parser::restructured_text::rst_text_label2dest_link;
 use crate::parser::wikitext::wikitext_text2dest_link;
+use crate::parser::wikitext::wikitext_wikilink_link;
 use crate::parser::Link;
+use crate::parser::LinkKind;
 use nom::branch::alt;
 use nom::bytes::complete::take_till;
 use nom::character::complete::anychar;
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Link max label. This limits the damage of a forgotten closing brackets.
 /// [CommonMark Spec](https://spec.commonmark.org/0.30/#link-label)
 pub const LABEL_LEN_MAX: usize = 999;
 
+/// Normalizes a _link label_ or a reStructuredText _reference name_ before
+/// matching _reference links_ against _link reference definitions_.
+///
+/// As in [docutils](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#reference-names)
+/// and [CommonMark](https://spec.commonmark.org/0.30/#matches), reference
+/// names are whitespace-neutral and case-insensitive: leading and trailing
+/// whitespace is removed, inner whitespace sequences, including line
+/// breaks, are replaced by one space and the result is lowercased. When
+/// `label` is normalized already, it is returned unchanged.
+/// ```
+/// use parse_hyperlinks::parser::parse::normalize_label;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   normalize_label(Cow::from(" Python\n  Home Page")),
+///   Cow::from("python home page")
+/// );
+/// ```
+pub fn normalize_label(label: Cow<str>) -> Cow<str> {
+    let is_normalized = !label.starts_with(char::is_whitespace)
+        && !label.ends_with(char::is_whitespace)
+        && !label.contains("  ")
+        && !label
+            .chars()
+            .any(|c| (c.is_whitespace() && c != ' ') || c.to_lowercase().next() != Some(c));
+    if is_normalized {
+        return label;
+    }
+    Cow::Owned(
+        label
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase(),
+    )
+}
+
+/// Inserts the definition `value` of `label` into `definitions`, keyed by
+/// `normalize_label(label)`. When the label is defined already, the first
+/// definition is kept, as in CommonMark. `iterator::Hyperlink` and
+/// `resolver::Resolver` both resolve duplicate labels this way.
+/// ```
+/// use parse_hyperlinks::parser::parse::insert_definition;
+/// use std::borrow::Cow;
+/// use std::collections::HashMap;
+///
+/// let mut definitions = HashMap::new();
+/// insert_definition(&mut definitions, Cow::from("Label"), "first");
+/// insert_definition(&mut definitions, Cow::from("label"), "second");
+/// assert_eq!(definitions[&Cow::from("label")], "first");
+/// ```
+pub fn insert_definition<'a, V>(
+    definitions: &mut HashMap<Cow<'a, str>, V>,
+    label: Cow<'a, str>,
+    value: V,
+) {
+    definitions.entry(normalize_label(label)).or_insert(value);
+}
+
+/// Markup languages distinguished by `detect_format()` and
+/// `take_link_format()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Format {
+    /// Markdown, including inline HTML.
+    Markdown,
+    /// reStructuredText.
+    ReStructuredText,
+    /// Asciidoc.
+    Asciidoc,
+    /// Wikitext.
+    Wikitext,
+    /// HTML.
+    Html,
+    /// The format is not known, the input may contain links of all
+    /// supported markup languages.
+    #[default]
+    Unknown,
+}
+
+impl Format {
+    /// Bit of the markup language in `ParserConfig::formats`. All bits for
+    /// `Format::Unknown`.
+    fn bit(self) -> u8 {
+        match self {
+            Format::Markdown => 0b1,
+            Format::ReStructuredText => 0b10,
+            Format::Asciidoc => 0b100,
+            Format::Wikitext => 0b1000,
+            Format::Html => 0b10000,
+            Format::Unknown => u8::MAX,
+        }
+    }
+
+    /// Bits of all markup languages, whose links can appear in a document
+    /// of this format.
+    fn mask(self) -> u8 {
+        match self {
+            Format::Markdown => Format::Markdown.bit() | Format::Html.bit(),
+            f => f.bit(),
+        }
+    }
+}
+
+/// Configuration of `take_link_config()` and of the iterator
+/// `iterator::Hyperlink`, built by chaining its setters to
+/// `ParserConfig::new()`. By default, links of all markup languages are
+/// searched, HTML anchors are parsed with `HtmlMode::Strict` and the length
+/// of links is not limited.
+///
+/// The decoding of escape sequences and entities is defined by the markup
+/// language of each link and can not be configured.
+/// ```
+/// use parse_hyperlinks::parser::html::HtmlMode;
+/// use parse_hyperlinks::parser::parse::{take_link_config, Format, ParserConfig};
+/// use parse_hyperlinks::parser::Link;
+/// use std::borrow::Cow;
+///
+/// let config = ParserConfig::new()
+///     .format(Format::Markdown)
+///     .html_mode(HtmlMode::Lenient)
+///     .max_link_len(40);
+///
+/// let i = r#"`text1 <dest1>`__ <a href="dest2"title="title2">text2</a>"#;
+/// let (_, (_, link)) = take_link_config(i, &config).unwrap();
+/// assert_eq!(link, Link::Text2Dest(Cow::from("text2"), Cow::from("dest2"), Cow::from("title2")));
+///
+/// let config = config.max_link_len(20);
+/// assert!(take_link_config(i, &config).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// Bits of the enabled markup languages, see `Format::bit()`.
+    formats: u8,
+    /// Parser mode for HTML anchors.
+    html_mode: HtmlMode,
+    /// Links spanning more bytes of the input are ignored.
+    max_link_len: usize,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParserConfig {
+    /// Constructor for the default configuration.
+    pub fn new() -> Self {
+        Self {
+            formats: Format::Unknown.mask(),
+            html_mode: HtmlMode::Strict,
+            max_link_len: usize::MAX,
+        }
+    }
+
+    /// Searches only for links, that can appear in a document of markup
+    /// language `format`, as `take_link_format()` does. Links of all
+    /// markup languages are searched with `Format::Unknown`.
+    pub fn format(mut self, format: Format) -> Self {
+        self.formats = format.mask();
+        self
+    }
+
+    /// Enables or disables the search for links of the markup language
+    /// `format` only. Unlike `ParserConfig::format()`, enabling
+    /// `Format::Markdown` does not enable `Format::Html`.
+    /// `Format::Unknown` stands for all markup languages.
+    pub fn enable(mut self, format: Format, enabled: bool) -> Self {
+        if enabled {
+            self.formats |= format.bit();
+        } else {
+            self.formats &= !format.bit();
+        }
+        self
+    }
+
+    /// Parses HTML anchors in `mode`, see `html::html_text2dest_mode()`.
+    pub fn html_mode(mut self, mode: HtmlMode) -> Self {
+        self.html_mode = mode;
+        self
+    }
+
+    /// Ignores links spanning more than `len` bytes of the input, e.g. to
+    /// limit the damage of a forgotten closing tag.
+    pub fn max_link_len(mut self, len: usize) -> Self {
+        self.max_link_len = len;
+        self
+    }
+
+    /// True, when links of the markup language `format` are searched.
+    fn enables(&self, format: Format) -> bool {
+        self.formats & format.bit() != 0
+    }
+}
+
+/// Guesses the markup language of `input`.
+///
+/// A document starting with `<!DOCTYPE html` or `<html` is HTML. Otherwise,
+/// every line is checked for constructs typical for one of the markup
+/// languages, e.g. `.. _label:` or `` `text`_ `` for reStructuredText,
+/// `[text](destination)` or `[label]: destination` for Markdown. The format
+/// with the most matching lines wins. Without any match, or when two formats
+/// match equally often, `Format::Unknown` is returned.
+///
+/// This is a heuristic: the result can be wrong for short inputs.
+/// ```
+/// use parse_hyperlinks::parser::parse::{detect_format, Format};
+///
+/// assert_eq!(detect_format("abc [text](dest) abc"), Format::Markdown);
+/// assert_eq!(detect_format("abc `text`_ abc\n\n.. _text: dest"), Format::ReStructuredText);
+/// assert_eq!(detect_format("<!DOCTYPE html>\n<html></html>"), Format::Html);
+/// assert_eq!(detect_format("abc"), Format::Unknown);
+/// ```
+pub fn detect_format(input: &str) -> Format {
+    let start: String = input
+        .trim_start()
+        .chars()
+        .take(14)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Format::Html;
+    }
+
+    const FORMATS: [Format; 5] = [
+        Format::Markdown,
+        Format::ReStructuredText,
+        Format::Asciidoc,
+        Format::Wikitext,
+        Format::Html,
+    ];
+    let mut scores = [0usize; FORMATS.len()];
+    for line in input.lines() {
+        let l = line.trim_start();
+        let matches = [
+            // Markdown
+            l.contains("](")
+                || (l.starts_with('[') && l.contains("]: "))
+                || l.starts_with("```")
+                || l.starts_with("# "),
+            // reStructuredText
+            l.starts_with(".. ") || l.starts_with("__ ") || l.contains("`_") || l.contains(">`__"),
+            // Asciidoc
+            l.contains("link:")
+                || l.contains("xref:")
+                || l.contains("<<")
+                || l.starts_with("= ")
+                || (l.starts_with(':')
+                    && l[1..]
+                        .split_once(": ")
+                        .is_some_and(|(n, _)| !n.is_empty() && !n.contains(char::is_whitespace))),
+            // Wikitext
+            l.contains("[[") || l.contains("{{") || (l.starts_with("==") && l.ends_with("==")),
+            // HTML
+            l.contains("<a ") || l.contains("</p>") || l.contains("</div>"),
+        ];
+        for (score, m) in scores.iter_mut().zip(matches) {
+            *score += m as usize;
+        }
+    }
+
+    let max = scores.iter().copied().max().unwrap_or_default();
+    let mut winners = FORMATS
+        .iter()
+        .zip(scores)
+        .filter(|(_, score)| *score == max);
+    match (winners.next(), winners.next()) {
+        (Some((format, _)), None) if max > 0 => *format,
+        _ => Format::Unknown,
+    }
+}
+
 /// Consumes the input until it finds a Markdown, RestructuredText, Asciidoc or
 /// HTML formatted _inline link_ (`Text2Dest`) or _link reference definition_
 /// (`Label2Dest`).
@@ -118,10 +402,18 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 
 /// Consumes the input until it finds a Markdown, RestructuredText, Asciidoc or
 /// HTML formatted _inline link_ (`Text2Dest`), _reference link_ (`Text2Label`),
-/// _link reference definition_ (`Label2Dest`) or _reference alias_ (`Label2Label`).
+/// _link reference definition_ (`Label2Dest`), _reference alias_ (`Label2Label`)
+/// or _cross reference_ (`Text2Xref`).
 ///
 /// The parser consumes the finding and returns
 /// `Ok((remaining_input, (skipped_input, Link)))` or some error.
+/// `skipped_input` is the text between the start of the input and the link.
+/// As a regular `nom` parser, `take_link()` can be combined with other
+/// combinators, e.g. `nom::multi::many0(take_link)` collects all links of a
+/// text. It is the building block of the `iterator::Hyperlink` iterator.
+///
+/// Wikilinks `[[page]]` are Markdown _shortcut reference links_ here, see
+/// `take_link_format()` and `Format::Wikitext` to search them as wikilinks.
 ///
 /// # Markdown
 ///
@@ -147,6 +439,9 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// ```
 /// # reStructuredText
 ///
+/// Links in comments and in the content of literal directives, e.g.
+/// `.. code::`, are ignored, see `RST_LITERAL_DIRECTIVES`.
+///
 /// ```
 /// use parse_hyperlinks::parser::Link;
 /// use parse_hyperlinks::parser::parse::take_link;
@@ -160,6 +455,14 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// .. _label1: destination1
 /// .. __: destination3
 /// __ destination4
+/// .. image:: image5.png
+///    :target: destination5
+/// .. This comment is ignored: `text6 <destination6>`__
+/// .. code:: rst
+///
+///    As is this code: `text7 <destination7>`__
+///
+/// abc `text8 <destination8>`__abc
 /// "#;
 ///
 /// let (i, r) = take_link(i).unwrap();
@@ -177,6 +480,10 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// assert_eq!(r.1, Link::Label2Dest(Cow::from("_"), Cow::from("destination3"), Cow::from("")));
 /// let (i, r) = take_link(i).unwrap();
 /// assert_eq!(r.1, Link::Label2Dest(Cow::from("_"), Cow::from("destination4"), Cow::from("")));
+/// let (i, r) = take_link(i).unwrap();
+/// assert_eq!(r.1, Link::Text2Dest(Cow::from("image5.png"), Cow::from("destination5"), Cow::from("")));
+/// let (i, r) = take_link(i).unwrap();
+/// assert_eq!(r.1, Link::Text2Dest(Cow::from("text8"), Cow::from("destination8"), Cow::from("")));
 /// ```
 /// # Asciidoc
 ///
@@ -191,6 +498,8 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// abc{label2}[text2]abc
 /// abc{label3}abc
 /// :label4: https://destination4
+/// abc xref:target5[text5]abc
+/// abc<<target6,text6>>abc
 /// "#;
 ///
 /// let (i, r) = take_link(i).unwrap();
@@ -204,10 +513,19 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// assert_eq!(r.1, Link::Text2Label(Cow::from(""), Cow::from("label3")));
 /// let (i, r) = take_link(i).unwrap();
 /// assert_eq!(r.1, Link::Label2Dest(Cow::from("label4"), Cow::from("https://destination4"), Cow::from("")));
+/// let (i, r) = take_link(i).unwrap();
+/// assert_eq!(r.1, Link::Text2Xref(Cow::from("text5"), Cow::from("target5")));
+/// let (i, r) = take_link(i).unwrap();
+/// assert_eq!(r.1, Link::Text2Xref(Cow::from("text6"), Cow::from("target6")));
 /// ```
 ///
 /// # HTML
 ///
+/// HTML anchors are also recognized when they are embedded in other markup,
+/// e.g. in HTML blocks or inline HTML of a Markdown document. Image map
+/// areas `<area href="..." alt="...">` are recognized as well. Links of any
+/// markup language inside HTML comments `<!-- ... -->` are ignored.
+///
 /// ```
 /// use parse_hyperlinks::parser::Link;
 /// use parse_hyperlinks::parser::parse::take_link;
@@ -225,6 +543,117 @@ pub fn take_text2dest_label2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
 /// assert_eq!(r.1, Link::Text2Dest(Cow::from("text2"), Cow::from("destination2"), Cow::from("title2")));
 /// ```
 pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
+    take_link_format(i, Format::Unknown)
+}
+
+/// Like `take_link()`, but searches only for links of the markup language
+/// `format`. With `Format::Unknown`, all markup languages are searched, as
+/// in `take_link()`. Markdown documents may contain HTML links too.
+///
+/// Wikilinks `[[page]]` are Markdown _shortcut reference links_ as well.
+/// They are searched only when Markdown links are not, e.g. with
+/// `Format::Wikitext`.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::parse::{take_link_format, Format};
+/// use std::borrow::Cow;
+///
+/// let i = "abc [text1](dest1) abc `text2 <dest2>`__ abc";
+///
+/// let (_, r) = take_link_format(i, Format::ReStructuredText).unwrap();
+/// assert_eq!(r.0, "abc [text1](dest1) abc ");
+/// assert_eq!(r.1, Link::Text2Dest(Cow::from("text2"), Cow::from("dest2"), Cow::from("")));
+/// ```
+pub fn take_link_format(i: &str, format: Format) -> nom::IResult<&str, (&str, Link)> {
+    take_link_config(i, &ParserConfig::new().format(format))
+}
+
+/// Like `take_link()`, but configured by `config`, see `ParserConfig`.
+pub fn take_link_config<'a>(
+    i: &'a str,
+    config: &ParserConfig,
+) -> nom::IResult<&'a str, (&'a str, Link<'a>)> {
+    let (i, (skipped, link, _)) = take_link_kind(i, config)?;
+    Ok((i, (skipped, link)))
+}
+
+/// Like `take_link_config()`, but also returns the kind of the link. Unlike
+/// `Link::kind()`, which knows only the variant, it tells autolinks apart,
+/// as it knows which parser matched.
+/// ```
+/// use parse_hyperlinks::parser::LinkKind;
+/// use parse_hyperlinks::parser::parse::{take_link_kind, ParserConfig};
+///
+/// let i = "abc [https://example.org](https://example.org) <https://example.org>";
+/// let config = ParserConfig::new();
+///
+/// let (i, (_, _, kind)) = take_link_kind(i, &config).unwrap();
+/// assert_eq!(kind, LinkKind::Inline);
+/// let (_, (_, _, kind)) = take_link_kind(i, &config).unwrap();
+/// assert_eq!(kind, LinkKind::Autolink);
+/// ```
+pub fn take_link_kind<'a>(
+    i: &'a str,
+    config: &ParserConfig,
+) -> nom::IResult<&'a str, (&'a str, Link<'a>, LinkKind)> {
+    // Disables the parser `p` when `config` does not enable `f`, and rejects
+    // links longer than `config.max_link_len`.
+    let on = |f: Format, p: fn(&str) -> nom::IResult<&str, Link>| {
+        move |i: &'a str| {
+            if !config.enables(f) {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    i,
+                    nom::error::ErrorKind::Alt,
+                )));
+            }
+            let (j, link) = p(i)?;
+            if i.len() - j.len() > config.max_link_len {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    i,
+                    nom::error::ErrorKind::TooLarge,
+                )));
+            }
+            let kind = link.kind();
+            Ok((j, (link, kind)))
+        }
+    };
+    // Like `on`, but reports the links `p` returns as `LinkKind::Autolink`,
+    // when `is_autolink` says so.
+    let autolink =
+        |f: Format, p: fn(&str) -> nom::IResult<&str, Link>, is_autolink: fn(&Link) -> bool| {
+            move |i: &'a str| {
+                let (j, (link, kind)) = on(f, p)(i)?;
+                let kind = if is_autolink(&link) {
+                    LinkKind::Autolink
+                } else {
+                    kind
+                };
+                Ok((j, (link, kind)))
+            }
+        };
+    let html_anchor: fn(&str) -> nom::IResult<&str, Link> = match config.html_mode {
+        HtmlMode::Strict => html_text2dest_link,
+        HtmlMode::Lenient => |i| {
+            let (i, ((te, de, ti), _)) = html_text2dest_mode(i, HtmlMode::Lenient)?;
+            Ok((i, Link::Text2Dest(te, de, ti)))
+        },
+    };
+    let md = Format::Markdown;
+    let rst = Format::ReStructuredText;
+    let adoc = Format::Asciidoc;
+    let wiki = Format::Wikitext;
+    let html = Format::Html;
+    // Wikilinks are reported as Markdown links when both are enabled.
+    let wikilink = |i: &'a str| {
+        if config.enables(md) {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                i,
+                nom::error::ErrorKind::Alt,
+            )));
+        }
+        on(wiki, wikitext_wikilink_link)(i)
+    };
+
     let mut j = i;
     let mut skip_count = 0;
     let mut input_start = true;
@@ -247,12 +676,26 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
             if let Ok((k, r)) = alt((
                 // Now we search for `label2*`.
                 // For both parser is the indent meaningful. We mustn't consume them.
-                rst_label2label_link,
-                rst_label2dest_link,
+                on(rst, rst_label2label_link),
+                on(rst, rst_label2dest_link),
+                on(rst, rst_image2dest_link),
             ))(j)
             {
                 break (k, r);
             };
+
+            // Comments and literal directives, e.g. `.. code::`, are skipped
+            // as a whole.
+            if config.enables(rst) {
+                if let Ok((k, markup)) = rst_explicit_markup(j) {
+                    if markup.is_literal() {
+                        skip_count += j.len() - k.len();
+                        j = k;
+                        input_start = false;
+                        continue;
+                    }
+                };
+            };
         };
 
         // Are we on a whitespace? Now consume them.
@@ -269,9 +712,9 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
                 // Now we search for `label2*`.
                 // These parsers do not care about the indent, as long it is
                 // only whitespace.
-                wikitext_text2dest_link,
-                md_label2dest_link,
-                adoc_label2dest_link,
+                on(wiki, wikitext_text2dest_link),
+                on(md, md_label2dest_link),
+                on(adoc, adoc_label2dest_link),
             ))(j)
             {
                 break (k, r);
@@ -279,17 +722,32 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
         };
         // Start searching for links.
 
+        // Links in HTML comments are ignored.
+        if config.enables(html) {
+            if let Ok((k, _)) = html_comment(j) {
+                skip_count += j.len() - k.len();
+                j = k;
+                input_start = false;
+                continue;
+            };
+        };
+
         // Regular `text` links can start everywhere.
         if let Ok((k, r)) = alt((
             // This should be first, because it is very specific.
-            wikitext_text2dest_link,
+            on(wiki, wikitext_text2dest_link),
+            // Must be placed before all `md_*` parsers, which consume `[*]`.
+            wikilink,
             // Start with `text2dest`.
-            md_text2dest_link,
+            on(md, md_text2dest_link),
+            autolink(md, md_autolink_link, |_| true),
             // `rst_text2dest` must be always placed before `rst_text2label`.
-            rst_text2dest_link,
-            rst_text_label2dest_link,
-            adoc_text2label_link,
-            html_text2dest_link,
+            on(rst, rst_text2dest_link),
+            on(rst, rst_text_label2dest_link),
+            on(adoc, adoc_text2label_link),
+            on(adoc, adoc_text2xref_link),
+            on(html, html_anchor),
+            on(html, html_area2dest_link),
         ))(j)
         {
             break (k, r);
@@ -310,7 +768,16 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
             // `rst_text2label` must be always placed after `rst_text2dest`.
             // `md_text2label` must be always placed after `adoc_text2label` and `adoc_text2dest`,
             // because the former consumes `[*]`.
-            if let Ok((l, r)) = alt((rst_text2label_link, adoc_text2dest_link))(k) {
+            if let Ok((l, r)) = alt((
+                on(rst, rst_text2label_link),
+                // A URL without link text.
+                autolink(
+                    adoc,
+                    adoc_text2dest_link,
+                    |l| matches!(l, Link::Text2Dest(te, de, _) if te == de),
+                ),
+            ))(k)
+            {
                 // If ever we have skipped a char, remember it now.
                 skip_count += j.len() - k.len();
                 break (l, r);
@@ -318,7 +785,7 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
         };
 
         // This parser is so unspecific, that it must be the last.
-        if let Ok((k, r)) = md_text2label_link(j) {
+        if let Ok((k, r)) = on(md, md_text2label_link)(j) {
             break (k, r);
         };
 
@@ -357,19 +824,22 @@ pub fn take_link(i: &str) -> nom::IResult<&str, (&str, Link)> {
     // recognized in the middle of a line.
     // It is sufficient to do this check once, because both parser guarantee to
     // consume the whole line in case of success.
-    let (mut l, link) = res;
+    let (mut l, (link, kind)) = res;
     match link {
         Link::Label2Dest(_, _, _) | Link::Label2Label(_, _) => {}
         _ => {
             // Just consume, the result does not matter.
-            let (m, _) = nom::combinator::opt(alt((rst_label2dest_link, md_label2dest_link)))(l)?;
+            let (m, _) = nom::combinator::opt(alt((
+                on(rst, rst_label2dest_link),
+                on(md, md_label2dest_link),
+            )))(l)?;
             l = m;
         }
     };
 
     let skipped_input = &i[0..skip_count];
 
-    Ok((l, (skipped_input, link)))
+    Ok((l, (skipped_input, link, kind)))
 }
 
 #[cfg(test)]
@@ -553,6 +1023,36 @@ abc[https://wikitext.link Wikitext Testlink]abc
         let (i, (_, res)) = take_link(i).unwrap();
         assert_eq!(res, expected);
         assert_eq!(i, "abc");
+
+        let i = "abc [[Wiki Page|wiki text]] abc";
+        let expected = Link::Text2Dest(
+            Cow::from("wiki text"),
+            Cow::from("Wiki Page"),
+            Cow::from(""),
+        );
+        let (i, (skipped, res)) = take_link_format(i, Format::Wikitext).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(skipped, "abc ");
+        assert_eq!(i, " abc");
+
+        // Without format, a wikilink is a Markdown shortcut reference link.
+        let i = "abc [[x]] abc";
+        let expected = Link::Text2Label(Cow::from("[x]"), Cow::from("[x]"));
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(skipped, "abc ");
+        assert_eq!(i, " abc");
+
+        let i = "abc <https://md_autolink> abc";
+        let expected = Link::Text2Dest(
+            Cow::from("https://md_autolink"),
+            Cow::from("https://md_autolink"),
+            Cow::from(""),
+        );
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(skipped, "abc ");
+        assert_eq!(i, " abc");
     }
 
     #[test]
@@ -596,6 +1096,33 @@ abc[https://wikitext.link Wikitext Testlink]abc
         assert_eq!(res, expected);
     }
 
+    #[test]
+    fn test_take_link_footnotes() {
+        let i = r#"Text[^1] and more[^note].
+
+[^1]: See https://example.org/a for details.
+[^note]: <https://example.org/b>
+"#;
+
+        let expected = Link::Text2Dest(
+            Cow::from("https://example.org/a"),
+            Cow::from("https://example.org/a"),
+            Cow::from(""),
+        );
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(skipped, "Text[^1] and more[^note].\n\n[^1]: See ");
+
+        let expected = Link::Text2Dest(
+            Cow::from("https://example.org/b"),
+            Cow::from("https://example.org/b"),
+            Cow::from(""),
+        );
+        let (i, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+        assert!(take_link(i).is_err());
+    }
+
     #[test]
     fn test_take_link4() {
         let i = r#"
@@ -616,4 +1143,258 @@ __ label5
         let (_i, (_, res)) = take_link(i).unwrap();
         assert_eq!(res, expected);
     }
+
+    #[test]
+    fn test_take_link_html_comment() {
+        let i = "abc<!-- <a href=\"d1\">t1</a> [t2](d2) -->[t3](d3)<!-- [t4](d4)";
+
+        let expected = Link::Text2Dest(Cow::from("t3"), Cow::from("d3"), Cow::from(""));
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "abc<!-- <a href=\"d1\">t1</a> [t2](d2) -->");
+        assert_eq!(res, expected);
+
+        // An unterminated comment is plain text.
+        let expected = Link::Text2Dest(Cow::from("t4"), Cow::from("d4"), Cow::from(""));
+        let (_, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "<!-- ");
+        assert_eq!(res, expected);
+
+        let i = "Text <!-- unterminated\n[a](https://a.example) `b <https://b.example>`__\n";
+        let expected = Link::Text2Dest(
+            Cow::from("a"),
+            Cow::from("https://a.example"),
+            Cow::from(""),
+        );
+        let (i, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+        let expected = Link::Text2Dest(
+            Cow::from("b"),
+            Cow::from("https://b.example"),
+            Cow::from(""),
+        );
+        let (_, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_take_link_md_html() {
+        // Anchors in Markdown HTML blocks and inline HTML.
+        let i = "<div align=\"center\">\n  <a href=\"d1\"><img src=\"logo.png\"></a>\n</div>\n\n\
+*Text* with <a href=\"d2\">[inline]</a> and [md](d3).\n";
+
+        let expected = Link::Text2Dest(
+            Cow::from("<img src=\"logo.png\">"),
+            Cow::from("d1"),
+            Cow::from(""),
+        );
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "<div align=\"center\">\n  ");
+        assert_eq!(res, expected);
+
+        let expected = Link::Text2Dest(Cow::from("[inline]"), Cow::from("d2"), Cow::from(""));
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "\n</div>\n\n*Text* with ");
+        assert_eq!(res, expected);
+
+        let expected = Link::Text2Dest(Cow::from("md"), Cow::from("d3"), Cow::from(""));
+        let (_i, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_take_link_html_area() {
+        let i = "<map name=\"m\">\n  <area shape=rect coords=\"0,0,9,9\" href=\"d1\" alt=\"t1\">\n</map>";
+
+        let expected = Link::Text2Dest(Cow::from("t1"), Cow::from("d1"), Cow::from(""));
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "<map name=\"m\">\n  ");
+        assert_eq!(res, expected);
+        assert_eq!(i, "\n</map>");
+    }
+
+    #[test]
+    fn test_take_link_html_unclosed() {
+        let i = "<p><a href=\"d1\">t1\n<p><a href=\"d2\">t2<li><a href=\"d3\">t3</a>";
+
+        let expected = Link::Text2Dest(Cow::from("t1\n"), Cow::from("d1"), Cow::from(""));
+        let (i, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+
+        let expected = Link::Text2Dest(Cow::from("t2"), Cow::from("d2"), Cow::from(""));
+        let (i, (skipped, res)) = take_link(i).unwrap();
+        assert_eq!(skipped, "<p>");
+        assert_eq!(res, expected);
+
+        let expected = Link::Text2Dest(Cow::from("t3"), Cow::from("d3"), Cow::from(""));
+        let (_, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_take_link_rst_multiline() {
+        let i = "abc `a very long name <https://example.org/\n  very/long/path>`_ abc\n\
+                 abc `text <https://example.org/\nvery/long/path>`__ abc";
+
+        let expected = Link::TextLabel2Dest(
+            Cow::from("a very long name"),
+            Cow::from("https://example.org/very/long/path"),
+            Cow::from(""),
+        );
+        let (i, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+
+        let expected = Link::Text2Dest(
+            Cow::from("text"),
+            Cow::from("https://example.org/very/long/path"),
+            Cow::from(""),
+        );
+        let (_, (_, res)) = take_link(i).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(normalize_label(Cow::from("`Label`")), Cow::from("`label`"));
+        assert_eq!(normalize_label(Cow::from("a\t\r\n b ")), Cow::from("a b"));
+        assert_eq!(
+            normalize_label(Cow::from("STRASSE ǅ")),
+            Cow::from("strasse ǆ")
+        );
+        assert!(matches!(
+            normalize_label(Cow::Borrowed("rst label_1")),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            normalize_label(Cow::Borrowed("")),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let i = "= Title\n:url: https://example.org\n\nabc link:https://example.org[text]";
+        assert_eq!(detect_format(i), Format::Asciidoc);
+        let i = "== Title ==\nabc [[Page|text]] abc [https://example.org text]";
+        assert_eq!(detect_format(i), Format::Wikitext);
+        let i = "<div>\n<p>abc <a href=\"dest\">text</a></p>\n</div>";
+        assert_eq!(detect_format(i), Format::Html);
+        let i = "  <!doctype HTML>\n[text](dest)";
+        assert_eq!(detect_format(i), Format::Html);
+        // Markdown with inline HTML.
+        let i = "# Title\n[text1](dest1)\n<a href=\"dest2\">text2</a>\n[label]: dest3";
+        assert_eq!(detect_format(i), Format::Markdown);
+        // A tie.
+        assert_eq!(detect_format("[text1](dest1)\n`text2`_"), Format::Unknown);
+    }
+
+    #[test]
+    fn test_take_link_format() {
+        let i = "<a href=\"dest1\">text1</a> [[Page]] [text2](dest2)";
+        let (_, (_, res)) = take_link_format(i, Format::Markdown).unwrap();
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("text1"), Cow::from("dest1"), Cow::from(""))
+        );
+        let (_, (skipped, res)) = take_link_format(i, Format::Wikitext).unwrap();
+        assert_eq!(skipped, "<a href=\"dest1\">text1</a> ");
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("Page"), Cow::from("Page"), Cow::from(""))
+        );
+        assert!(take_link_format(i, Format::Asciidoc).is_err());
+    }
+
+    #[test]
+    fn test_take_link_combinator() {
+        let i = "abc [text1](dest1) abc `text2 <dest2>`__ abc";
+        let (rest, res) = nom::multi::many0(take_link)(i).unwrap();
+        assert_eq!(rest, " abc");
+        let expected = vec![
+            (
+                "abc ",
+                Link::Text2Dest(Cow::from("text1"), Cow::from("dest1"), Cow::from("")),
+            ),
+            (
+                " abc ",
+                Link::Text2Dest(Cow::from("text2"), Cow::from("dest2"), Cow::from("")),
+            ),
+        ];
+        assert_eq!(res, expected);
+
+        // The skipped input and the link's source text make up the input.
+        let (rest, (skipped, _)) = take_link(i).unwrap();
+        assert_eq!(skipped, "abc ");
+        assert_eq!(&i[skipped.len()..i.len() - rest.len()], "[text1](dest1)");
+    }
+
+    #[test]
+    fn test_take_link_config() {
+        let i = "abc <a href=\"d1\">t1</a> [t2](d2) [t3](a-very-long-destination3) `t4 <d4>`__";
+
+        let config = ParserConfig::new().enable(Format::Html, false);
+        let (_, (_, res)) = take_link_config(i, &config).unwrap();
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("t2"), Cow::from("d2"), Cow::from(""))
+        );
+
+        let config = ParserConfig::new()
+            .enable(Format::Unknown, false)
+            .enable(Format::Html, true);
+        let (j, (_, res)) = take_link_config(i, &config).unwrap();
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("t1"), Cow::from("d1"), Cow::from(""))
+        );
+        assert!(take_link_config(j, &config).is_err());
+
+        // The long link is skipped, but the search continues behind it.
+        let config = ParserConfig::new()
+            .format(Format::Markdown)
+            .max_link_len(20);
+        let (j, (_, res)) = take_link_config(i, &config).unwrap();
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("t1"), Cow::from("d1"), Cow::from(""))
+        );
+        let (j, (skipped, res)) = take_link_config(j, &config).unwrap();
+        assert_eq!(skipped, " ");
+        assert_eq!(
+            res,
+            Link::Text2Dest(Cow::from("t2"), Cow::from("d2"), Cow::from(""))
+        );
+        assert!(take_link_config(j, &config).is_err());
+
+        assert_eq!(
+            ParserConfig::default(),
+            ParserConfig::new().format(Format::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_take_link_kind() {
+        fn kinds(mut i: &str, config: &ParserConfig) -> Vec<LinkKind> {
+            let mut res = Vec::new();
+            while let Ok((j, (_, _, kind))) = take_link_kind(i, config) {
+                res.push(kind);
+                i = j;
+            }
+            res
+        }
+        let i = "[https://x](https://x) <https://x> <me@example.org>";
+        assert_eq!(
+            kinds(i, &ParserConfig::new().format(Format::Markdown)),
+            vec![LinkKind::Inline, LinkKind::Autolink, LinkKind::Autolink]
+        );
+        let i = "abc https://x[] abc https://x[text] abc https://x abc";
+        assert_eq!(
+            kinds(i, &ParserConfig::new().format(Format::Asciidoc)),
+            vec![LinkKind::Autolink, LinkKind::Inline, LinkKind::Autolink]
+        );
+        let i = "abc [[Page]] abc";
+        assert_eq!(
+            kinds(i, &ParserConfig::new().format(Format::Wikitext)),
+            vec![LinkKind::Inline]
+        );
+    }
 }
@@ -0,0 +1,225 @@
+//! This module implements parsers to detect bare URLs in plain text.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Prefixes, which start a bare URL. They are matched case insensitively.
+const PLAINTEXT_URL_PREFIXES: [&str; 4] = ["http://", "https://", "ftp://", "www."];
+
+/// Punctuation, which is not part of a bare URL when it ends the URL.
+const PLAINTEXT_TRAILING_PUNCTUATION: &str = "?!.,:;*_~'\"";
+
+/// Wrapper around `plaintext_url()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn plaintext_url_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = plaintext_url(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Consumes the input until the parser finds a bare URL, which is preceded
+/// by the beginning of the input, whitespace or one of `*_~("'<`.
+///
+/// It returns either `Ok((i, (skipped, (link_text, link_destination,
+/// Cow::from("")))))` or some error. See `plaintext_url()` for the
+/// recognized URLs.
+/// ```
+/// use parse_hyperlinks::parser::plaintext::take_plaintext_url;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   take_plaintext_url("See the docs (www.example.org/a_(b)), please."),
+///   Ok(("), please.", ("See the docs (",
+///       (Cow::from("www.example.org/a_(b)"), Cow::from("http://www.example.org/a_(b)"), Cow::from("")))))
+/// );
+/// ```
+pub fn take_plaintext_url(i: &str) -> nom::IResult<&str, (&str, (Cow<str>, Cow<str>, Cow<str>))> {
+    let mut preceding: Option<char> = None;
+    for (idx, c) in i.char_indices() {
+        let is_boundary = match preceding {
+            None => true,
+            Some(p) => p.is_whitespace() || "*_~(\"'<".contains(p),
+        };
+        if is_boundary && "hHfFwW".contains(c) {
+            if let Ok((rest, link)) = plaintext_url(&i[idx..]) {
+                return Ok((rest, (&i[..idx], link)));
+            }
+        }
+        preceding = Some(c);
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        i,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Parses a bare URL starting with `http://`, `https://`, `ftp://` or `www.`.
+///
+/// The boundary rules follow the
+/// [autolinks extension](https://github.github.com/gfm/#autolinks-extension-)
+/// of GitHub Flavored Markdown: the URL ends before whitespace or `<`. Then,
+/// trailing punctuation `?!.,:;*_~'"` is removed. A trailing `)` is removed
+/// as long as the URL contains more closing than opening parentheses, so
+/// that `(see https://example.org/a_(b))` yields `https://example.org/a_(b)`.
+/// A trailing entity reference like `&amp;` is removed too. The host must
+/// not be empty and consist of letters, digits, `-`, `_` and `.` only.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The _link text_ is the URL as written. The _link
+/// destination_ is the same, except for URLs starting with `www.`, which
+/// are prefixed with `http://`. Prefixes are matched case insensitively.
+///
+/// The parser expects to start at the first letter of the URL to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::plaintext::plaintext_url;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   plaintext_url("https://example.org/path?q=1. abc"),
+///   Ok((". abc", (Cow::from("https://example.org/path?q=1"), Cow::from("https://example.org/path?q=1"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   plaintext_url("www.example.org, abc"),
+///   Ok((", abc", (Cow::from("www.example.org"), Cow::from("http://www.example.org"), Cow::from(""))))
+/// );
+/// ```
+pub fn plaintext_url(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let error = || nom::Err::Error(nom::error::Error::new(i, ErrorKind::Verify));
+    let prefix = PLAINTEXT_URL_PREFIXES
+        .iter()
+        .find(|p| i.get(..p.len()).is_some_and(|s| s.eq_ignore_ascii_case(p)))
+        .ok_or_else(error)?;
+
+    let end = i
+        .find(|c: char| c.is_whitespace() || c == '<')
+        .unwrap_or(i.len());
+    let mut url = &i[..end];
+    loop {
+        if let Some(u) = url.strip_suffix(';') {
+            let entity = u.trim_end_matches(|c: char| c.is_ascii_alphanumeric());
+            if entity.len() < u.len() && entity.ends_with('&') {
+                url = &entity[..entity.len() - 1];
+                continue;
+            }
+        }
+        if let Some(u) = url.strip_suffix(|c| PLAINTEXT_TRAILING_PUNCTUATION.contains(c)) {
+            url = u;
+            continue;
+        }
+        if url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+            url = &url[..url.len() - 1];
+            continue;
+        }
+        break;
+    }
+
+    let host = url
+        .get(prefix.len()..)
+        .unwrap_or_default()
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or_default();
+    if host.is_empty()
+        || !host
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.".contains(c))
+    {
+        return Err(error());
+    }
+
+    let link_destination = if *prefix == "www." {
+        Cow::Owned(format!("http://{}", url))
+    } else {
+        Cow::Borrowed(url)
+    };
+
+    Ok((
+        &i[url.len()..],
+        (Cow::Borrowed(url), link_destination, Cow::Borrowed("")),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plaintext_url() {
+        let expected = (
+            "));!",
+            (
+                Cow::from("HTTPS://en.wikipedia.org/wiki/Rust_(language)"),
+                Cow::from("HTTPS://en.wikipedia.org/wiki/Rust_(language)"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            plaintext_url("HTTPS://en.wikipedia.org/wiki/Rust_(language)));!").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "&amp;<br>",
+            (
+                Cow::from("ftp://example.org/?a=1"),
+                Cow::from("ftp://example.org/?a=1"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            plaintext_url("ftp://example.org/?a=1&amp;<br>").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            plaintext_url("http://[::1]/ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "http://[::1]/ abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            plaintext_url("www. abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "www. abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            plaintext_url("mailto:info@example.org"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "mailto:info@example.org",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_take_plaintext_url() {
+        let expected = (
+            "",
+            (
+                "xhttp://example.org/ _",
+                (
+                    Cow::from("www.example.org"),
+                    Cow::from("http://www.example.org"),
+                    Cow::from(""),
+                ),
+            ),
+        );
+        assert_eq!(
+            take_plaintext_url("xhttp://example.org/ _www.example.org").unwrap(),
+            expected
+        );
+        assert_eq!(
+            take_plaintext_url("no link: www.\n"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "no link: www.\n",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! This module implements parsers for Perl POD hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::tag;
+use nom::character::complete::multispace1;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `pod_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn pod_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = pod_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a POD _link formatting code_ `L<...>`.
+///
+/// The [formatting code](https://perldoc.perl.org/perlpod#L%3Cname%3E----A-hyperlink)
+/// has the forms `L<name>`, `L<name/section>`, `L</section>` or
+/// `L<scheme:...>`. Every form can be preceded by a _link text_ and a `|`,
+/// e.g. `L<text|name>`. The section may be enclosed in double quotes. Like
+/// all formatting codes, it can also be written with more than one angle
+/// bracket and surrounding whitespace, e.g. `L<< text|name >>`. Formatting
+/// codes inside the _link text_ are returned verbatim.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The _link destination_ is the URL, the manual page name
+/// `name` or `name#section`. For `L</section>` it is `#section`. When no
+/// _link text_ is given, it is derived from the destination as `perldoc`
+/// does: `name`, `"section" in name` or `"section"`.
+///
+/// The parser expects to start at the link start (`L`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::pod::pod_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     pod_text2dest("L<Perl homepage|https://www.perl.org/>abc"),
+///     Ok(("abc", (Cow::from("Perl homepage"), Cow::from("https://www.perl.org/"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     pod_text2dest("L<perlsyn/\"For Loops\">abc"),
+///     Ok(("abc", (Cow::from("\"For Loops\" in perlsyn"), Cow::from("perlsyn#For Loops"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     pod_text2dest("L<< C<< $a <=> $b >>|perlop >>abc"),
+///     Ok(("abc", (Cow::from("C<< $a <=> $b >>"), Cow::from("perlop"), Cow::from(""))))
+/// );
+/// ```
+pub fn pod_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, inner) = nom::sequence::preceded(tag("L"), pod_formatting_code_content)(i)?;
+
+    // The `|` separating the link text must not be part of a nested
+    // formatting code.
+    let (link_text, target) = match pod_find_outside_codes(inner, '|') {
+        Some(idx) => (Some(inner[..idx].trim()), inner[idx + 1..].trim()),
+        None => (None, inner.trim()),
+    };
+    if target.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            target,
+            ErrorKind::Verify,
+        )));
+    }
+
+    // URL
+    let is_url = target.split_once(':').is_some_and(|(scheme, rest)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            && !rest.starts_with(':')
+            && !target.contains(char::is_whitespace)
+    });
+    if is_url {
+        return Ok((
+            i,
+            (
+                Cow::Borrowed(link_text.unwrap_or(target)),
+                Cow::Borrowed(target),
+                Cow::Borrowed(""),
+            ),
+        ));
+    }
+
+    // Manual page and section.
+    let (name, section) = match target.split_once('/') {
+        Some((n, s)) => {
+            let s = s.trim();
+            let s = s
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .unwrap_or(s);
+            (n.trim(), Some(s))
+        }
+        None => match target.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            // `L<"section">` is a deprecated form of `L</"section">`.
+            Some(s) => ("", Some(s)),
+            None => (target, None),
+        },
+    };
+    let (link_text, link_destination) = match section {
+        Some(s) => (
+            match link_text {
+                Some(te) => Cow::Borrowed(te),
+                None if name.is_empty() => Cow::Owned(format!("\"{}\"", s)),
+                None => Cow::Owned(format!("\"{}\" in {}", s, name)),
+            },
+            Cow::Owned(format!("{}#{}", name, s)),
+        ),
+        None => (
+            Cow::Borrowed(link_text.unwrap_or(name)),
+            Cow::Borrowed(name),
+        ),
+    };
+
+    Ok((i, (link_text, link_destination, Cow::Borrowed(""))))
+}
+
+/// Parses the content of a formatting code, including its angle brackets.
+/// With single angle brackets, nested angle brackets must be balanced. With
+/// `n > 1` angle brackets, whitespace must follow the opening and precede
+/// the closing brackets.
+fn pod_formatting_code_content(i: &str) -> nom::IResult<&str, &str> {
+    let (j, brackets) = nom::bytes::complete::take_while1(|c| c == '<')(i)?;
+    if brackets.len() == 1 {
+        let mut depth = 0;
+        for (idx, c) in j.char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' if depth == 0 => return Ok((&j[idx + 1..], &j[..idx])),
+                '>' => depth -= 1,
+                _ => {}
+            }
+        }
+        return Err(nom::Err::Error(nom::error::Error::new(
+            j,
+            ErrorKind::TakeUntil,
+        )));
+    }
+
+    let (j, _) = multispace1(j)?;
+    let closing = ">".repeat(brackets.len());
+    let mut k = j;
+    while let Some(c) = k.chars().next() {
+        // Skip nested formatting codes.
+        if c.is_ascii_uppercase() && k[1..].starts_with('<') {
+            if let Ok((rest, _)) = pod_formatting_code_content(&k[1..]) {
+                k = rest;
+                continue;
+            }
+        }
+        if c.is_whitespace() {
+            let rest = k.trim_start();
+            if let Some(rest) = rest.strip_prefix(&closing) {
+                return Ok((rest, &j[..j.len() - k.len()]));
+            }
+        }
+        k = &k[c.len_utf8()..];
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        j,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Returns the byte index of the first `pat` in `i` that is not part of a
+/// nested formatting code `X<...>`.
+fn pod_find_outside_codes(i: &str, pat: char) -> Option<usize> {
+    let mut k = i;
+    while !k.is_empty() {
+        let mut chars = k.chars();
+        let c = chars.next()?;
+        if c.is_ascii_uppercase() && chars.next() == Some('<') {
+            if let Ok((rest, _)) = pod_formatting_code_content(&k[1..]) {
+                k = rest;
+                continue;
+            }
+        }
+        if c == pat {
+            return Some(i.len() - k.len());
+        }
+        k = &k[c.len_utf8()..];
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("Net::Ping"),
+                Cow::from("Net::Ping"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(pod_text2dest("L<Net::Ping>abc").unwrap(), expected);
+        assert_eq!(pod_text2dest("L<<  Net::Ping >>abc").unwrap(), expected);
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("\"Object Oriented\""),
+                Cow::from("#Object Oriented"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(pod_text2dest("L</Object Oriented>abc").unwrap(), expected);
+        assert_eq!(
+            pod_text2dest("L<\"Object Oriented\">abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/"),
+                Cow::from("https://example.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            pod_text2dest("L<https://example.org/>abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("I<the> docs"),
+                Cow::from("perlpod#Formatting Codes"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            pod_text2dest("L<I<the> docs|perlpod/\"Formatting Codes\">abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            pod_text2dest("L<text|>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            pod_text2dest("L<Net::Ping abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "Net::Ping abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+        assert_eq!(
+            pod_text2dest("L<<Net::Ping>>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "Net::Ping>>abc",
+                ErrorKind::MultiSpace
+            )))
+        );
+    }
+}
@@ -16,6 +16,52 @@ use std::borrow::Cow;
 /// `rst_escaped_link_text_transform()`.
 const ESCAPABLE: &str = r#" `:<>_\"#;
 
+/// Directives whose content is no reStructuredText, but literal text, code
+/// or raw output. Links in their content are ignored.
+pub const RST_LITERAL_DIRECTIVES: &[&str] = &[
+    "code",
+    "code-block",
+    "sourcecode",
+    "literalinclude",
+    "parsed-literal",
+    "raw",
+    "math",
+    "csv-table",
+];
+
+/// Kinds of explicit markup blocks, as classified by
+/// `rst_explicit_markup()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RstMarkup<'a> {
+    /// A hyperlink target, e.g. `.. _label: destination` or `.. __: label_`.
+    Target,
+    /// A directive with this name, e.g. `note` in `.. note::`.
+    Directive(&'a str),
+    /// A substitution definition with this substitution name, e.g. `logo`
+    /// in `.. |logo| image:: logo.png`.
+    SubstitutionDefinition(&'a str),
+    /// A footnote, e.g. `.. [1] text`, `.. [#note] text` or `.. [*] text`.
+    Footnote,
+    /// A citation, e.g. `.. [CIT2002] text`.
+    Citation,
+    /// Any other explicit markup block, e.g. `.. This is a comment`.
+    Comment,
+}
+
+impl RstMarkup<'_> {
+    /// True for blocks, whose content is never searched for links:
+    /// comments and the directives listed in `RST_LITERAL_DIRECTIVES`.
+    pub fn is_literal(&self) -> bool {
+        match self {
+            RstMarkup::Comment => true,
+            RstMarkup::Directive(name) => RST_LITERAL_DIRECTIVES
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(name)),
+            _ => false,
+        }
+    }
+}
+
 /// Wrapper around `rst_text2dest()` that packs the result in
 /// `Link::Text2Dest`.
 pub fn rst_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
@@ -45,7 +91,9 @@ pub fn rst_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 /// abc `Python home page <http://www.python.org>`__ abc
 /// ```
 /// The bracketed URI must be preceded by whitespace and be the last text
-/// before the end string.
+/// before the end string. The hyperlink reference may span several lines:
+/// whitespace in the URI, including line breaks and indentation, is
+/// removed, line breaks in the link text are kept.
 pub fn rst_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
     let (i, (ln, ld)) = rst_parse_text2target(true, false)(i)?;
     let ln = rst_escaped_link_text_transform(ln)?.1;
@@ -218,11 +266,47 @@ pub fn rst_text2label_link(i: &str) -> nom::IResult<&str, Link> {
 /// ```
 ///
 pub fn rst_text2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
-    let (i, (te, la)) = rst_parse_text2label(i)?;
+    let (i, ((te, la), _)) = rst_text2label_anonymous(i)?;
+    Ok((i, (te, la)))
+}
+
+/// Parse a RestructuredText _reference link_ like `rst_text2label()` and
+/// also return whether the reference is anonymous.
+///
+/// It returns either `Ok((i, ((link_text, link_label), anonymous)))` or some
+/// error. Anonymous references end with two underscores `__` and are
+/// resolved by position: the n-th anonymous reference of a document points
+/// to the n-th anonymous target `.. __: destination`. As in
+/// `rst_text2label()`, their `link_label` is `_`. Anonymous references with
+/// embedded alias, e.g. `` `link text <link label_>`__ ``, are the
+/// exception: they do not consume an anonymous target, but refer to
+/// `link label`. Their `link_label` is the alias, `anonymous` is `true`
+/// nevertheless.
+///
+/// The caller must guarantee the same conditions as for `rst_text2label()`.
+/// ```rust
+/// use parse_hyperlinks::parser::restructured_text::rst_text2label_anonymous;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rst_text2label_anonymous("`link text`_ abc"),
+///   Ok((" abc", ((Cow::from("link text"), Cow::from("link text")), false)))
+/// );
+/// assert_eq!(
+///   rst_text2label_anonymous("`link text`__ abc"),
+///   Ok((" abc", ((Cow::from("link text"), Cow::from("_")), true)))
+/// );
+/// assert_eq!(
+///   rst_text2label_anonymous("`link text <link label_>`__ abc"),
+///   Ok((" abc", ((Cow::from("link text"), Cow::from("link label")), true)))
+/// );
+/// ```
+pub fn rst_text2label_anonymous(i: &str) -> nom::IResult<&str, ((Cow<str>, Cow<str>), bool)> {
+    let (i, (te, la, anonymous)) = rst_parse_text2label(i)?;
     let te = rst_escaped_link_text_transform(te)?.1;
     let la = rst_escaped_link_text_transform(la)?.1;
 
-    Ok((i, (te, la)))
+    Ok((i, ((te, la), anonymous)))
 }
 
 /// Parses a _reference link_. (Doctree element `reference`).
@@ -246,24 +330,33 @@ pub fn rst_text2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
 ///
 /// For named references in reStructuredText `link_text` and `link_label`
 /// are the same. By convention we return for anonymous references:
-/// `link_label='_'`.
+/// `link_label='_'`, unless they have an embedded alias, e.g.
+/// `` `link text <link label_>`__ ``. The returned `bool` is `true` for
+/// anonymous references.
 ///
 /// The parser checks that this _reference link_ is followed by a whitespace
 /// without consuming it.
 ///
-fn rst_parse_text2label(i: &str) -> nom::IResult<&str, (&str, &str)> {
-    let (mut i, (link_text, mut link_label)) = alt((
-        rst_parse_text2target(false, true),
-        nom::combinator::map(rst_parse_simple_label, |s| (s, s)),
-    ))(i)?;
-
-    // Is this an anonymous reference? Consume the second `_` also.
-    if let (j, Some(_)) = nom::combinator::opt(nom::character::complete::char('_'))(i)? {
-        link_label = "_";
-        i = j;
-    };
-
-    Ok((i, (link_text, link_label)))
+fn rst_parse_text2label(i: &str) -> nom::IResult<&str, (&str, &str, bool)> {
+    alt((
+        // With embedded alias the label is known, also for anonymous
+        // references.
+        nom::combinator::map(rst_parse_text2target(false, true), |(te, la)| {
+            (te, la, false)
+        }),
+        nom::combinator::map(rst_parse_text2target(true, true), |(te, la)| (te, la, true)),
+        nom::combinator::map(
+            nom::sequence::pair(
+                rst_parse_simple_label,
+                // Is this an anonymous reference? Consume the second `_` also.
+                nom::combinator::opt(nom::character::complete::char('_')),
+            ),
+            |(s, anonymous)| match anonymous {
+                Some(_) => (s, "_", true),
+                None => (s, s, false),
+            },
+        ),
+    ))(i)
 }
 
 /// Wrapper around `rst_label2dest()` that packs the result in
@@ -277,6 +370,12 @@ pub fn rst_label2dest_link(i: &str) -> nom::IResult<&str, Link> {
 ///
 /// This parser consumes until the end of the line. As rst does not know about link titles,
 /// the parser always returns an empty `link_title` as `Cow::Borrowed("")`.
+/// Anonymous targets, `.. __: destination` or `__ destination`, have no
+/// name: they are returned with the marker `_` as `link_label`.
+/// Whitespace in the destination is removed, unless the destination is
+/// enclosed in backquotes: `` .. _label: `destination with spaces` ``.
+/// `iterator::Hyperlink` matches them in order of appearance with the
+/// anonymous references `` `text`__ ``.
 /// ```
 /// use parse_hyperlinks::parser::Link;
 /// use parse_hyperlinks::parser::restructured_text::rst_label2dest;
@@ -294,6 +393,10 @@ pub fn rst_label2dest_link(i: &str) -> nom::IResult<&str, Link> {
 ///   rst_label2dest("   __ destination\nabc"),
 ///   Ok(("\nabc", (Cow::from("_"), Cow::from("destination"), Cow::from(""))))
 /// );
+/// assert_eq!(
+///   rst_label2dest(".. _label: `my file.pdf`\nabc"),
+///   Ok(("\nabc", (Cow::from("label"), Cow::from("my file.pdf"), Cow::from(""))))
+/// );
 /// ```
 /// Here some examples for link references:
 /// ```rst
@@ -337,6 +440,751 @@ pub fn rst_label2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
     rst_label2target(true, i)
 }
 
+/// URI schemes, which Docutils recognizes in standalone URIs. This is the
+/// subset of Docutils' `urischemes` list in common use.
+const RST_URI_SCHEMES: [&str; 28] = [
+    "data", "dav", "dns", "file", "ftp", "git", "gopher", "http", "https", "imap", "irc", "ldap",
+    "mailto", "mid", "news", "nfs", "nntp", "pop", "rsync", "sftp", "sip", "sips", "smb", "snmp",
+    "ssh", "svn", "telnet", "urn",
+];
+
+/// Wrapper around `rst_standalone_uri()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn rst_standalone_uri_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = rst_standalone_uri(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a RestructuredText _standalone hyperlink_, i.e. an absolute URI or
+/// an email address without markup.
+///
+/// Docutils recognizes [standalone
+/// hyperlinks](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#standalone-hyperlinks)
+/// with a known scheme, see `RST_URI_SCHEMES`, e.g.
+/// `https://example.org/path?query#fragment` or `mailto:info@example.org`,
+/// and bare email addresses. Docutils' boundary rules apply: the URI
+/// consists of the characters ``-_.!~*'()[];/:@&=+$,%?#`` and
+/// alphanumerics, but it must end with one of `_~*/=+` or an alphanumeric
+/// character, unless it is followed by `>`. Therefore, trailing
+/// punctuation like `.` or `)` is not part of the URI. The character after
+/// the URI must be whitespace, the end of the input or one of
+/// ``'")]}>-/:.,;!?\``.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from(""))))`
+/// or some error. The _link text_ is the URI or email address as written,
+/// the _link destination_ is the same, but for bare email addresses, which
+/// are prefixed with `mailto:`.
+///
+/// The character preceding the link must be whitespace or one of
+/// ``'"([{<-/:`` or the link must be at the beginning of the input. As this
+/// parser only sees the link, this is up to the caller. The parser expects
+/// to start at the first letter of the link to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::restructured_text::rst_standalone_uri;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rst_standalone_uri("https://docutils.sourceforge.io/rst.html). abc"),
+///   Ok(("). abc", (Cow::from("https://docutils.sourceforge.io/rst.html"),
+///                  Cow::from("https://docutils.sourceforge.io/rst.html"),
+///                  Cow::from(""))))
+/// );
+/// assert_eq!(
+///   rst_standalone_uri("docutils-users@lists.sourceforge.net, abc"),
+///   Ok((", abc", (Cow::from("docutils-users@lists.sourceforge.net"),
+///                 Cow::from("mailto:docutils-users@lists.sourceforge.net"),
+///                 Cow::from(""))))
+/// );
+/// ```
+pub fn rst_standalone_uri(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let error = || nom::Err::Error(nom::error::Error::new(i, nom::error::ErrorKind::Verify));
+    let is_uric = |c: char| c.is_ascii_alphanumeric() || "-_.!~*'()[];/:@&=+$,%?#".contains(c);
+    let is_urilast = |c: char| c.is_ascii_alphanumeric() || "_~*/=+".contains(c);
+
+    let run_len = i.find(|c| !is_uric(c)).unwrap_or(i.len());
+    // Backtrack to the last character allowed at the end.
+    let end = if i[run_len..].starts_with('>') && run_len > 0 {
+        run_len
+    } else {
+        i[..run_len].rfind(is_urilast).map_or(0, |idx| idx + 1)
+    };
+    let link = &i[..end];
+    let rest = &i[end..];
+    if !rest.is_empty()
+        && !rest.starts_with(|c: char| c.is_whitespace() || "'\")]}>-/:.,;!?\\".contains(c))
+    {
+        return Err(error());
+    }
+
+    // Absolute URI.
+    if let Some((scheme, hier_part)) = link.split_once(':') {
+        if RST_URI_SCHEMES
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(scheme))
+        {
+            if hier_part.trim_start_matches('/').is_empty() {
+                return Err(error());
+            }
+            return Ok((
+                rest,
+                (Cow::Borrowed(link), Cow::Borrowed(link), Cow::Borrowed("")),
+            ));
+        }
+    }
+
+    // Email address.
+    let (local, domain) = link.split_once('@').ok_or_else(error)?;
+    let is_emailc = |c: char| c.is_ascii_alphanumeric() || "-_!~*'{|}/#?^`&=+$%.".contains(c);
+    if local.is_empty()
+        || !local.chars().all(is_emailc)
+        || local.starts_with('.')
+        || domain.split('.').count() < 2
+        || domain
+            .split('.')
+            .any(|l| l.is_empty() || !l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+    {
+        return Err(error());
+    }
+
+    Ok((
+        rest,
+        (
+            Cow::Borrowed(link),
+            Cow::Owned(format!("mailto:{}", link)),
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+/// Wrapper around `rst_role_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn rst_role_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = rst_role_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an _interpreted text_ with an explicit role, as Sphinx uses it for
+/// cross-references, e.g. `` :doc:`Title <path/to/doc>` ``,
+/// `` :ref:`label` `` or `` :download:`script <script.py>` ``.
+///
+/// The [roles](https://www.sphinx-doc.org/en/master/usage/referencing.html)
+/// are not restricted to a fixed list. Domain roles like `` :py:func:`f` ``
+/// are recognized too. The interpreted text is either `target` or
+/// `title <target>`.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link title_ is the role name, e.g. `doc` or `py:func`,
+/// so that the caller can tell apart the kinds of references. Without an
+/// explicit title, the _link text_ is the target. Sphinx' target prefixes
+/// are handled: `~` shortens the _link text_ to the last component of the
+/// target, `!` is removed. Escape sequences are resolved as in
+/// `rst_text2dest()`.
+///
+/// The parser expects to start at the role start (`:`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::restructured_text::rst_role_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rst_role_text2dest(":doc:`Installation <usage/install>` abc"),
+///   Ok((" abc", (Cow::from("Installation"), Cow::from("usage/install"), Cow::from("doc"))))
+/// );
+/// assert_eq!(
+///   rst_role_text2dest(":py:meth:`~queue.Queue.get` abc"),
+///   Ok((" abc", (Cow::from("get"), Cow::from("queue.Queue.get"), Cow::from("py:meth"))))
+/// );
+/// ```
+pub fn rst_role_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (role, inner)) = nom::sequence::pair(
+        nom::sequence::preceded(
+            nom::character::complete::char(':'),
+            verify(
+                nom::bytes::complete::take_while1(|c: char| {
+                    c.is_ascii_alphanumeric() || "-_+.:".contains(c)
+                }),
+                |s: &str| {
+                    s.len() > 1 && s.ends_with(':') && !s.starts_with(':') && !s.ends_with("::")
+                },
+            ),
+        ),
+        nom::sequence::delimited(
+            tag("`"),
+            nom::bytes::complete::escaped(
+                nom::character::complete::none_of(r#"\`"#),
+                '\\',
+                nom::character::complete::one_of(ESCAPABLE),
+            ),
+            tag("`"),
+        ),
+    )(i)?;
+    let role = &role[..role.len() - 1];
+    let (_, _) = nom::combinator::not(nom::character::complete::char('_'))(j)?;
+
+    // Split `title <target>`.
+    let title_target: IResult<&str, (&str, &str)> = nom::sequence::pair(
+        nom::bytes::complete::escaped(
+            nom::character::complete::none_of(r#"\<"#),
+            '\\',
+            nom::character::complete::one_of(ESCAPABLE),
+        ),
+        nom::sequence::delimited(
+            tag("<"),
+            nom::bytes::complete::escaped(
+                nom::character::complete::none_of(r#"\<>"#),
+                '\\',
+                nom::character::complete::one_of(ESCAPABLE),
+            ),
+            nom::sequence::terminated(tag(">"), nom::combinator::eof),
+        ),
+    )(inner);
+    let (link_text, target) = match title_target {
+        Ok((_, (text, target))) if !text.trim().is_empty() => (Some(text.trim_end()), target),
+        _ => (None, inner),
+    };
+    let target = target.trim();
+    let (shorten, target) = match target.strip_prefix('~') {
+        Some(t) => (true, t),
+        None => (false, target.strip_prefix('!').unwrap_or(target)),
+    };
+    if target.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            inner,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let link_destination = rst_escaped_link_destination_transform(target)?.1;
+    let link_text = match link_text {
+        Some(text) => rst_escaped_link_text_transform(text)?.1,
+        None if shorten => {
+            let last = target.rsplit(['.', ':', '/']).next().unwrap_or(target);
+            rst_escaped_link_text_transform(last)?.1
+        }
+        None => rst_escaped_link_text_transform(target)?.1,
+    };
+
+    Ok((j, (link_text, link_destination, Cow::Borrowed(role))))
+}
+
+/// Parses an _inline internal target_ `` _`target` ``.
+///
+/// An [inline internal target](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#inline-internal-targets)
+/// defines a target in the middle of a paragraph. References
+/// `` `target`_ `` elsewhere in the document point to it. It returns either
+/// `Ok((i, target))` or some error. Escape sequences are resolved as in
+/// `rst_text2label()`, so that `target` can be compared with the
+/// `link_label` of a reference.
+///
+/// The caller must guarantee the same conditions as for `rst_text2label()`:
+/// the parser is at the input start or the preceding byte is a whitespace,
+/// possibly followed by one of `([<'"`. The target must not be followed by
+/// an alphanumeric character or `_`.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::rst_inline_target;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rst_inline_target("_`Python home page`, abc"),
+///   Ok((", abc", Cow::from("Python home page")))
+/// );
+/// ```
+pub fn rst_inline_target(i: &str) -> nom::IResult<&str, Cow<str>> {
+    let (j, target) = verify(
+        nom::sequence::delimited(
+            tag("_`"),
+            nom::bytes::complete::escaped(
+                nom::character::complete::none_of(r#"\`"#),
+                '\\',
+                nom::character::complete::one_of(ESCAPABLE),
+            ),
+            tag("`"),
+        ),
+        |s: &str| s.len() <= LABEL_LEN_MAX && !s.trim().is_empty(),
+    )(i)?;
+    // The end-string must not be followed by a word character.
+    let (_, _) = nom::combinator::not(nom::character::complete::satisfy(|c| {
+        c.is_alphanumeric() || c == '_'
+    }))(j)?;
+    let target = rst_escaped_link_text_transform(target)?.1;
+    Ok((j, target))
+}
+
+/// Wrapper around `rst_substitution2label()` that packs the result in
+/// `Link::Text2Label`.
+pub fn rst_substitution2label_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, la)) = rst_substitution2label(i)?;
+    Ok((i, Link::Text2Label(te, la)))
+}
+
+/// Parses a _substitution reference_ that is also a hyperlink reference,
+/// e.g. `|logo|_` or, anonymous, `|logo|__`.
+///
+/// It returns either `Ok((i, (substitution_name, link_label)))` or some
+/// error. The text of the link is the substitution `substitution_name`,
+/// defined elsewhere, e.g. by `.. |logo| image:: logo.png`, see
+/// `rst_substitution_image()`. As for
+/// [named references](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#substitution-references),
+/// the `link_label` is the substitution name. For anonymous references, the
+/// `link_label` is `_`, as in `rst_text2label()`. Substitution references
+/// without trailing `_` are no hyperlinks and are rejected.
+///
+/// The caller must guarantee the same conditions as for `rst_text2label()`.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::rst_substitution2label;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rst_substitution2label("|Python logo|_ abc"),
+///   Ok((" abc", (Cow::from("Python logo"), Cow::from("Python logo"))))
+/// );
+/// assert_eq!(
+///   rst_substitution2label("|logo|__ abc"),
+///   Ok((" abc", (Cow::from("logo"), Cow::from("_"))))
+/// );
+/// ```
+pub fn rst_substitution2label(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
+    let (j, (name, underscores)) = nom::sequence::pair(
+        nom::sequence::delimited(
+            tag("|"),
+            verify(nom::bytes::complete::is_not("|"), |s: &str| {
+                s.len() <= LABEL_LEN_MAX
+                    && !s.starts_with(char::is_whitespace)
+                    && !s.ends_with(char::is_whitespace)
+            }),
+            tag("|"),
+        ),
+        alt((tag("__"), tag("_"))),
+    )(i)?;
+    // The end-string must not be followed by a word character.
+    let (_, _) = nom::combinator::not(nom::character::complete::satisfy(|c| {
+        c.is_alphanumeric() || c == '_'
+    }))(j)?;
+
+    let link_label = if underscores == "__" { "_" } else { name };
+    Ok((j, (Cow::Borrowed(name), Cow::Borrowed(link_label))))
+}
+
+/// Parses a _substitution definition_ with an `image` directive, e.g.
+/// `.. |logo| image:: logo.png`, and its options `:alt:` and `:target:`.
+///
+/// With the [substitution](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#substitution-definitions)
+/// `|logo|`, the image can be placed anywhere in the text. When the
+/// `:target:` option is given, the image is a hyperlink too.
+///
+/// It returns either
+/// `Ok((i, (substitution_name, img_alt, img_src, link_destination)))` or
+/// some error. `img_alt` and `link_destination` are empty when the
+/// corresponding option is missing. The image URI can be split over
+/// several lines, which are joined without whitespace. A `link_destination`
+/// ending with `_`, e.g. `` `Python`_ ``, is a reference name and is
+/// returned as is. The directive block ends before the first line that is
+/// not indented; its line ending is not consumed.
+///
+/// The parser expects to start at the beginning of the line to succeed.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::rst_substitution_image;
+/// use std::borrow::Cow;
+///
+/// let i = ".. |logo| image:: images/logo.png
+///    :alt: Our logo
+///    :target: https://example.org/
+/// abc";
+///
+/// assert_eq!(
+///   rst_substitution_image(i),
+///   Ok(("\nabc", (Cow::from("logo"),
+///                 Cow::from("Our logo"),
+///                 Cow::from("images/logo.png"),
+///                 Cow::from("https://example.org/"))))
+/// );
+/// ```
+pub fn rst_substitution_image(
+    i: &str,
+) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (indent, name, first_line)) = nom::sequence::tuple((
+        nom::character::complete::space0,
+        nom::sequence::delimited(
+            tag(".. |"),
+            verify(nom::bytes::complete::is_not("|\r\n"), |s: &str| {
+                !s.starts_with(char::is_whitespace) && !s.ends_with(char::is_whitespace)
+            }),
+            nom::sequence::tuple((
+                tag("|"),
+                nom::character::complete::space1,
+                nom::bytes::complete::tag_no_case("image::"),
+            )),
+        ),
+        nom::character::complete::not_line_ending,
+    ))(i)?;
+
+    let (j, (img_alt, img_src, link_destination)) = rst_image_block(indent, first_line, j)
+        .ok_or_else(|| nom::Err::Error(nom::error::Error::new(i, nom::error::ErrorKind::Verify)))?;
+
+    Ok((j, (Cow::Borrowed(name), img_alt, img_src, link_destination)))
+}
+
+/// Wrapper around `rst_image2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn rst_image2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = rst_image2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an `image` or `figure` directive with a `:target:` option, e.g.
+/// `.. image:: logo.png` followed by the indented line
+/// `:target: https://example.org/`.
+///
+/// With the `:target:` option, the
+/// [image](https://docutils.sourceforge.io/docs/ref/rst/directives.html#image)
+/// is a clickable hyperlink.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ is the value of the `:alt:` option or, if
+/// missing, the image URI. The _link destination_ is the value of the
+/// `:target:` option. As in `rst_substitution_image()`, URIs can be split
+/// over several lines. As rst does not know about link titles, the parser
+/// always returns an empty `link_title`. Directives without `:target:`
+/// option, or with a reference name as target, e.g. `` `Python`_ ``, are
+/// rejected. The directive block ends before the first line that is not
+/// indented; its line ending is not consumed. The content of a figure, its
+/// caption and legend, follows after a blank line and is not consumed.
+///
+/// The parser expects to start at the beginning of the line to succeed.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::rst_image2dest;
+/// use std::borrow::Cow;
+///
+/// let i = ".. figure:: images/logo.png
+///    :alt: Our logo
+///    :target: https://example.org/
+///
+///    The caption.";
+///
+/// assert_eq!(
+///   rst_image2dest(i),
+///   Ok(("\n\n   The caption.", (Cow::from("Our logo"),
+///                                Cow::from("https://example.org/"),
+///                                Cow::from(""))))
+/// );
+/// ```
+pub fn rst_image2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (indent, _, first_line)) = nom::sequence::tuple((
+        nom::character::complete::space0,
+        nom::sequence::preceded(
+            tag(".. "),
+            alt((
+                nom::bytes::complete::tag_no_case("image::"),
+                nom::bytes::complete::tag_no_case("figure::"),
+            )),
+        ),
+        nom::character::complete::not_line_ending,
+    ))(i)?;
+
+    let error = || nom::Err::Error(nom::error::Error::new(i, nom::error::ErrorKind::Verify));
+    let (j, (img_alt, img_src, link_destination)) =
+        rst_image_block(indent, first_line, j).ok_or_else(error)?;
+    if link_destination.is_empty() || link_destination.ends_with('_') {
+        return Err(error());
+    }
+    let link_text = if img_alt.is_empty() { img_src } else { img_alt };
+
+    Ok((j, (link_text, link_destination, Cow::Borrowed(""))))
+}
+
+/// Parses the body of an `image` directive, see `rst_directive_body()`.
+///
+/// It returns `Some((i, (img_alt, img_src, link_destination)))` or `None`
+/// when the image URI is missing.
+fn rst_image_block<'a>(
+    indent: &'a str,
+    first_line: &'a str,
+    i: &'a str,
+) -> Option<(&'a str, (Cow<'a, str>, Cow<'a, str>, Cow<'a, str>))> {
+    let (j, (argument, options)) = rst_directive_body(indent, first_line, i);
+    let img_src = rst_join_uri(&argument);
+    if img_src.is_empty() {
+        return None;
+    }
+
+    let option = |wanted: &str| -> &[&str] {
+        options
+            .iter()
+            .find(|(name, _)| *name == wanted)
+            .map_or(&[], |(_, value)| value.as_slice())
+    };
+    let img_alt = match option("alt") {
+        [] => Cow::Borrowed(""),
+        [line] => Cow::Borrowed(*line),
+        lines => Cow::Owned(lines.join(" ")),
+    };
+    let link_destination = match option("target") {
+        // A reference name, e.g. `` `Python\n home`_ ``.
+        lines if lines.len() > 1 && lines.last().is_some_and(|l| l.ends_with('_')) => {
+            Cow::Owned(lines.join(" "))
+        }
+        lines => rst_join_uri(lines),
+    };
+
+    Some((j, (img_alt, img_src, link_destination)))
+}
+
+/// Parses the body of a directive: the directive argument, starting with
+/// `first_line`, and the options in the following lines, indented deeper
+/// than `indent`, see `rst_indented_lines()`. `i` is the input after
+/// `first_line`.
+///
+/// It returns `(i, (argument, options))`: the lines of the argument and
+/// for every option `:name: value` its name and the lines of its value.
+/// The argument ends at the first option.
+fn rst_directive_body<'a>(
+    indent: &'a str,
+    first_line: &'a str,
+    i: &'a str,
+) -> (&'a str, (Vec<&'a str>, Vec<(&'a str, Vec<&'a str>)>)) {
+    let (j, v) = rst_indented_lines(rst_indent_width(indent))(i).unwrap_or((i, Vec::new()));
+    let mut argument = vec![first_line.trim()];
+    let mut options: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in v.into_iter().map(|(_, l)| l.trim_end()) {
+        // Options are `:name: value`, the value can continue on the next lines.
+        let option = line.strip_prefix(':').and_then(|l| l.split_once(':'));
+        match (option, options.last_mut()) {
+            (Some((name, value)), _) => options.push((name, vec![value.trim()])),
+            (None, Some((_, value))) => value.push(line),
+            (None, None) => argument.push(line),
+        }
+    }
+    (j, (argument, options))
+}
+
+/// Joins the lines of a URI, that is split over several lines, without
+/// whitespace.
+fn rst_join_uri<'a>(lines: &[&'a str]) -> Cow<'a, str> {
+    match lines {
+        [] => Cow::Borrowed(""),
+        [line] => Cow::Borrowed(*line),
+        lines => Cow::Owned(lines.concat()),
+    }
+}
+
+/// Options of directives, whose value can be a URL.
+const RST_URL_OPTIONS: [&str; 2] = ["url", "target"];
+
+/// Wrapper around `rst_directive_urls()` that packs every result in
+/// `Link::Text2Dest`.
+pub fn rst_directive_urls_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, v) = rst_directive_urls(i)?;
+    Ok((
+        i,
+        v.into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Extracts the URLs of resources from any directive, e.g.
+/// `.. include:: https://example.org/chapter.rst` or
+/// `.. image:: https://cdn.example.org/img.png`.
+///
+/// [Directives](https://docutils.sourceforge.io/docs/ref/rst/directives.html)
+/// carry URLs in their argument and in the options `:url:`, e.g. of
+/// `.. raw:: html` or `.. csv-table::`, and `:target:`. Substitution
+/// definitions, e.g. `.. |logo| image:: https://example.org/logo.png`, are
+/// directives too. As in `rst_substitution_image()`, URLs can be split over
+/// several lines. Only absolute URLs with a known scheme, see
+/// `RST_URI_SCHEMES`, are extracted, relative paths are ignored.
+///
+/// It returns either `Ok((i, links))` or some error. Every item of `links`
+/// is a tuple `(link_text, link_destination, link_title)`: the _link text_
+/// and the _link destination_ are the URL, the _link title_ is the
+/// directive name. The argument comes first, then the options in order of
+/// appearance. A directive without URL is rejected. The directive block
+/// ends before the first line that is not indented; its line ending is not
+/// consumed.
+///
+/// The parser expects to start at the beginning of the line to succeed.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::rst_directive_urls;
+/// use std::borrow::Cow;
+///
+/// let i = ".. raw:: html
+///    :url: https://example.org/snippet.html
+/// abc";
+///
+/// assert_eq!(
+///   rst_directive_urls(i),
+///   Ok(("\nabc", vec![(Cow::from("https://example.org/snippet.html"),
+///                      Cow::from("https://example.org/snippet.html"),
+///                      Cow::from("raw"))]))
+/// );
+/// ```
+pub fn rst_directive_urls(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let (j, (indent, name, first_line)) = nom::sequence::tuple((
+        nom::character::complete::space0,
+        nom::sequence::preceded(
+            nom::sequence::pair(
+                tag(".. "),
+                opt(nom::sequence::terminated(
+                    nom::sequence::delimited(
+                        tag("|"),
+                        nom::bytes::complete::is_not("|\r\n"),
+                        tag("|"),
+                    ),
+                    nom::character::complete::space1,
+                )),
+            ),
+            map_opt(
+                nom::bytes::complete::take_till1(char::is_whitespace),
+                |w: &str| w.strip_suffix("::").filter(|name| !name.is_empty()),
+            ),
+        ),
+        nom::character::complete::not_line_ending,
+    ))(i)?;
+
+    let (j, (argument, options)) = rst_directive_body(indent, first_line, j);
+    let is_url = |uri: &str| {
+        !uri.contains(char::is_whitespace)
+            && uri.split_once(':').is_some_and(|(scheme, hier_part)| {
+                RST_URI_SCHEMES
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(scheme))
+                    && !hier_part.trim_start_matches('/').is_empty()
+            })
+    };
+    let links: Vec<_> = std::iter::once(rst_join_uri(&argument))
+        .chain(
+            options
+                .iter()
+                .filter(|(name, _)| RST_URL_OPTIONS.contains(name))
+                .map(|(_, value)| rst_join_uri(value)),
+        )
+        .filter(|uri| is_url(uri))
+        .map(|uri| (uri.clone(), uri, Cow::Borrowed(name)))
+        .collect();
+    if links.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+
+    Ok((j, links))
+}
+
+/// Consumes a whole explicit markup block and classifies it.
+///
+/// An [explicit markup block](https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html#explicit-markup-blocks)
+/// starts with `..` followed by whitespace or the end of the line. It
+/// continues with all following lines indented deeper than the `..`,
+/// including blank lines between them. As in docutils, a line with `..`
+/// alone, followed by a blank line, is an empty comment without content.
+///
+/// It returns either `Ok((i, markup))` or some error. The line ending of
+/// the last line of the block is not consumed. Hyperlink targets are only
+/// classified here, use `rst_label2dest()` or `rst_label2label()` to parse
+/// them.
+///
+/// The parser expects to start at the beginning of the line to succeed.
+/// ```
+/// use parse_hyperlinks::parser::restructured_text::{rst_explicit_markup, RstMarkup};
+///
+/// let i = ".. code-block:: rust
+///
+///    let url = \"`text <https://example.org>`_\";
+///
+/// abc";
+///
+/// let (j, markup) = rst_explicit_markup(i).unwrap();
+/// assert_eq!(j, "\n\nabc");
+/// assert_eq!(markup, RstMarkup::Directive("code-block"));
+/// assert!(markup.is_literal());
+///
+/// assert_eq!(
+///   rst_explicit_markup(".. |logo| image:: logo.png\nabc"),
+///   Ok(("\nabc", RstMarkup::SubstitutionDefinition("logo")))
+/// );
+/// assert_eq!(
+///   rst_explicit_markup(".. This is a comment.\nabc"),
+///   Ok(("\nabc", RstMarkup::Comment))
+/// );
+/// ```
+pub fn rst_explicit_markup(i: &str) -> nom::IResult<&str, RstMarkup> {
+    let (mut j, (indent, head)) = nom::sequence::pair(
+        nom::character::complete::space0,
+        nom::sequence::preceded(
+            tag(".."),
+            verify(nom::character::complete::not_line_ending, |l: &str| {
+                l.is_empty() || l.starts_with([' ', '\t'])
+            }),
+        ),
+    )(i)?;
+    let head = head.trim();
+
+    let markup = if head.starts_with('_') {
+        RstMarkup::Target
+    } else if let Some(label) = head
+        .strip_prefix('[')
+        .and_then(|h| h.split_once(']'))
+        .map(|(label, _)| label)
+        .filter(|label| !label.is_empty())
+    {
+        if label.starts_with('#') || label == "*" || label.chars().all(|c| c.is_ascii_digit()) {
+            RstMarkup::Footnote
+        } else {
+            RstMarkup::Citation
+        }
+    } else if let Some((name, _)) = head
+        .strip_prefix('|')
+        .and_then(|h| h.split_once('|'))
+        .filter(|(name, rest)| !name.is_empty() && rest.starts_with([' ', '\t']))
+    {
+        RstMarkup::SubstitutionDefinition(name)
+    } else if let Some(name) = head
+        .split([' ', '\t'])
+        .next()
+        .and_then(|w| w.strip_suffix("::"))
+        .filter(|name| {
+            name.starts_with(char::is_alphanumeric)
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "-_.:+".contains(c))
+        })
+    {
+        RstMarkup::Directive(name)
+    } else {
+        RstMarkup::Comment
+    };
+
+    // Returns the line following the line ending at the start of `i`, and
+    // the remaining input after this line.
+    fn next_line(i: &str) -> Option<(&str, &str)> {
+        let (i, _) = nom::character::complete::line_ending::<_, ()>(i).ok()?;
+        let end = i.find(['\r', '\n']).unwrap_or(i.len());
+        Some(i.split_at(end))
+    }
+
+    // An empty comment ends at the next blank line.
+    if head.is_empty() && next_line(j).is_some_and(|(line, _)| line.trim().is_empty()) {
+        return Ok((j, markup));
+    }
+
+    // Consume indented lines and blank lines between them.
+    let marker_width = rst_indent_width(indent);
+    let mut k = j;
+    while let Some((line, rest)) = next_line(k) {
+        let text = line.trim_start_matches([' ', '\t']);
+        if !text.trim().is_empty() {
+            if rst_indent_width(&line[..line.len() - text.len()]) <= marker_width {
+                break;
+            }
+            j = rest;
+        }
+        k = rest;
+    }
+
+    Ok((j, markup))
+}
+
 /// Parser for _link_reference_definitions_:
 /// * `label==false`:  the link is of type `Label2Dest`
 /// * `label==true`: the link is of type `Label2Label`
@@ -372,10 +1220,11 @@ fn rst_label2target(label: bool, i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
             if !label {
                 let _ = nom::combinator::not(rst_parse_simple_label)(lt).map_err(my_err)?;
             };
-            (
-                rst_escaped_link_text_transform(ls)?.1,
-                rst_escaped_link_destination_transform(lt)?.1,
-            )
+            let lt = match rst_quoted_destination(label, lt) {
+                Some(q) => rst_escaped_link_text_transform(q)?.1,
+                None => rst_escaped_link_destination_transform(lt)?.1,
+            };
+            (rst_escaped_link_text_transform(ls)?.1, lt)
         }
 
         Cow::Owned(strg) => {
@@ -399,12 +1248,11 @@ fn rst_label2target(label: bool, i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
                     .1
                     .to_string(),
             );
-            let lt = Cow::Owned(
-                rst_escaped_link_destination_transform(lt)
-                    .map_err(my_err)?
-                    .1
-                    .to_string(),
-            );
+            let lt = match rst_quoted_destination(label, lt) {
+                Some(q) => rst_escaped_link_text_transform(q),
+                None => rst_escaped_link_destination_transform(lt),
+            };
+            let lt = Cow::Owned(lt.map_err(my_err)?.1.to_string());
             (ls, lt)
         }
     };
@@ -416,6 +1264,20 @@ fn rst_label2target(label: bool, i: &str) -> nom::IResult<&str, (Cow<str>, Cow<s
     Ok((i, (source, target)))
 }
 
+/// Returns the content of a backquoted destination, e.g.
+/// `` `destination with spaces` ``, of a `Label2Dest` link
+/// (`label==false`). Unlike unquoted destinations, whitespace in the
+/// content is significant.
+fn rst_quoted_destination(label: bool, i: &str) -> Option<&str> {
+    if label {
+        return None;
+    }
+    i.trim_end()
+        .strip_prefix('`')
+        .and_then(|q| q.strip_suffix('`'))
+        .filter(|q| !q.trim().is_empty() && !q.contains('`'))
+}
+
 /// The parser recognizes `Label2Dest` links (`label==false`):
 ///     _label: dest
 /// or `Label2Label` links (`label==true):
@@ -528,7 +1390,9 @@ fn rst_parse_simple_label(i: &str) -> nom::IResult<&str, &str> {
 /// * whose first line begins with ".." followed by whitespace (the "explicit
 ///   markup start"),
 /// * whose second and subsequent lines (if any) are indented relative to the
-///   first, and
+///   first, by any amount; tabs and spaces can be mixed, tab stops are every
+///   8th column; indentation beyond the indentation common to all these
+///   lines is preserved, and
 /// * which ends before an unindented line
 /// As with external hyperlink targets, the link block of an indirect
 /// hyperlink target may begin on the same line as the explicit markup start
@@ -538,45 +1402,75 @@ fn rst_explicit_markup_block<'a>(
     block_header: &'a str,
 ) -> impl Fn(&'a str) -> IResult<&'a str, Cow<'a, str>> {
     move |i: &'a str| {
-        fn indent<'a>(wsp1: &'a str, wsp2: &'a str) -> impl Fn(&'a str) -> IResult<&'a str, ()> {
-            move |i: &str| {
-                let (i, _) = nom::character::complete::line_ending(i)?;
-                let (i, _) = nom::bytes::complete::tag(wsp1)(i)?;
-                let (i, _) = nom::bytes::complete::tag(wsp2)(i)?;
-                Ok((i, ()))
-            }
-        }
-
-        let (i, (wsp1, wsp2)) = nom::sequence::pair(
+        let (i, wsp1) = nom::sequence::terminated(
             nom::character::complete::space0,
-            nom::combinator::map(nom::bytes::complete::tag(block_header), |_| "   "),
+            nom::bytes::complete::tag(block_header),
         )(i)?;
-
-        let (j, v) = nom::multi::separated_list1(
-            indent(wsp1, wsp2),
+        let (j, (first, v)) = nom::sequence::pair(
             nom::character::complete::not_line_ending,
+            rst_indented_lines(rst_indent_width(wsp1)),
         )(i)?;
 
         // If the block consists of only one line return now.
-        if v.len() == 1 {
-            return Ok((j, Cow::Borrowed(v[0])));
+        if v.is_empty() {
+            return Ok((j, Cow::Borrowed(first)));
         };
 
-        let mut s = String::new();
-        let mut is_first = true;
-
-        for subs in &v {
-            if !is_first {
-                s.push(' ');
-            }
+        // Only the indentation common to all continuation lines is removed.
+        let min_width = v.iter().map(|(w, _)| *w).min().unwrap_or_default();
+        let mut s = String::from(first);
+        for (width, subs) in &v {
+            s.push(' ');
+            // Keep the additional indentation, tabs are expanded.
+            s.push_str(&" ".repeat(width - min_width));
             s.push_str(subs);
-            is_first = false;
         }
 
         Ok((j, Cow::from(s)))
     }
 }
 
+/// Parses the continuation lines of an explicit markup block: all
+/// following lines, that are indented deeper than the explicit markup start
+/// at column `marker_width`. The block ends before the first line that is
+/// less indented, contains only whitespace or starts another explicit
+/// markup block (`.. ` or `__ `). The line ending of the last line is not
+/// consumed.
+///
+/// It returns the lines without indentation, each paired with the width
+/// of its indentation.
+fn rst_indented_lines<'a>(
+    marker_width: usize,
+) -> impl Fn(&'a str) -> IResult<&'a str, Vec<(usize, &'a str)>> {
+    move |i: &'a str| {
+        nom::multi::many0(nom::sequence::preceded(
+            nom::character::complete::line_ending,
+            map(
+                verify(
+                    nom::sequence::pair(
+                        nom::character::complete::space0,
+                        nom::character::complete::not_line_ending,
+                    ),
+                    |(wsp, l): &(&str, &str)| {
+                        rst_indent_width(wsp) > marker_width
+                            && !l.trim().is_empty()
+                            && !l.starts_with(".. ")
+                            && !l.starts_with("__ ")
+                    },
+                ),
+                |(wsp, l)| (rst_indent_width(wsp), l),
+            ),
+        ))(i)
+    }
+}
+
+/// Returns the width of the indentation `wsp` in columns. As in docutils,
+/// tab stops are every 8th column.
+fn rst_indent_width(wsp: &str) -> usize {
+    wsp.chars()
+        .fold(0, |w, c| if c == '\t' { w + 8 - w % 8 } else { w + 1 })
+}
+
 /// Replace the following escaped characters:
 ///     \\\`\ \:\<\>
 /// with:
@@ -711,16 +1605,40 @@ mod tests {
         let expected = (
             "abc",
             (
-                Cow::from(r#"my news at <http://python.org>"#),
-                Cow::from(r#"http://news. <python>.org"#),
+                Cow::from(r#"my news at <http://python.org>"#),
+                Cow::from(r#"http://news. <python>.org"#),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_text2dest(
+                r#"`my news at \<http\://python.org\> <http:// news.\ \<python\>.org>`__abc"#
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("a very long\nname"),
+                Cow::from("https://example.org/very/long/path"),
                 Cow::from(""),
             ),
         );
         assert_eq!(
-            rst_text2dest(
-                r#"`my news at \<http\://python.org\> <http:// news.\ \<python\>.org>`__abc"#
-            )
-            .unwrap(),
+            rst_text2dest("`a very long\nname <https://example.org/\nvery/long/path>`__abc")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            rst_text2dest("`a very long\nname <https://example.org/\r\n   very/long/path>`__abc")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            rst_text2dest("`a very long\nname\n<https://example.org/very/long/path>`__abc")
+                .unwrap(),
             expected
         );
     }
@@ -840,42 +1758,47 @@ mod tests {
     fn test_rst_parse_text2label() {
         assert_eq!(
             rst_parse_text2label("linktext_ abc"),
-            Ok((" abc", ("linktext", "linktext")))
+            Ok((" abc", ("linktext", "linktext", false)))
         );
 
         assert_eq!(
             rst_parse_text2label("linktext__ abc"),
-            Ok((" abc", ("linktext", "_")))
+            Ok((" abc", ("linktext", "_", true)))
         );
 
         assert_eq!(
             rst_parse_text2label("link_text_ abc"),
-            Ok((" abc", ("link_text", "link_text")))
+            Ok((" abc", ("link_text", "link_text", false)))
         );
 
         assert_eq!(
             rst_parse_text2label("`link text`_ abc"),
-            Ok((" abc", ("link text", "link text")))
+            Ok((" abc", ("link text", "link text", false)))
         );
 
         assert_eq!(
             rst_parse_text2label("`link text`_abc"),
-            Ok(("abc", ("link text", "link text")))
+            Ok(("abc", ("link text", "link text", false)))
         );
 
         assert_eq!(
             rst_parse_text2label("`link_text`_ abc"),
-            Ok((" abc", ("link_text", "link_text")))
+            Ok((" abc", ("link_text", "link_text", false)))
         );
 
         assert_eq!(
             rst_parse_text2label("`link text`__ abc"),
-            Ok((" abc", ("link text", "_")))
+            Ok((" abc", ("link text", "_", true)))
         );
 
         assert_eq!(
             rst_parse_text2label("`link text<link label_>`_ abc"),
-            Ok((" abc", ("link text", "link label")))
+            Ok((" abc", ("link text", "link label", false)))
+        );
+
+        assert_eq!(
+            rst_parse_text2label("`link text<link label_>`__ abc"),
+            Ok((" abc", ("link text", "link label", true)))
         );
     }
 
@@ -898,6 +1821,16 @@ mod tests {
                 .unwrap(),
             expected
         );
+        assert_eq!(
+            rst_label2dest("  .. _`Python: home page`:\n   http://www.py\n         thon.org\nabc")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            rst_label2dest("\t.. _`Python: home page`:\n\t\thttp://www.py\n\t   thon.org\nabc")
+                .unwrap(),
+            expected
+        );
 
         let expected = nom::Err::Error(nom::error::Error::new(
             "x .. _`Python: home page`: http://www.python.org\nabc",
@@ -982,6 +1915,10 @@ mod tests {
             rst_label2dest(r#"__ http://news.python.org"#).unwrap(),
             expected
         );
+        assert_eq!(
+            rst_label2dest(".. __:\n   http://news.\n   python.org").unwrap(),
+            expected
+        );
         assert_eq!(
             rst_label2dest(".. _label: `link destination`_").unwrap_err(),
             nom::Err::Error(nom::error::Error::new(
@@ -996,6 +1933,31 @@ mod tests {
                 ErrorKind::EscapedTransform
             )),
         );
+
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("my file"),
+                Cow::from("C:\\my documents\\my file.pdf"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_label2dest(".. _my file:\n   `C:\\\\my documents\\\\my file.pdf`  \nabc").unwrap(),
+            expected
+        );
+        let expected = (
+            "",
+            (
+                Cow::from("_"),
+                Cow::from("my long file name.pdf"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_label2dest("__ `my long\n   file name.pdf`").unwrap(),
+            expected
+        );
     }
 
     #[test]
@@ -1175,6 +2137,22 @@ mod tests {
             rst_explicit_markup_block(".. ")("   .. first\n      second\n       1indent\nout"),
             Ok(("\nout", Cow::from("first second  1indent")))
         );
+        assert_eq!(
+            rst_explicit_markup_block(".. ")("\t.. first\n\t   second\n  \t     third\n\tout"),
+            Ok(("\n\tout", Cow::from("first second   third")))
+        );
+        assert_eq!(
+            rst_explicit_markup_block(".. ")(".. first\n\tsecond\nout"),
+            Ok(("\nout", Cow::from("first second")))
+        );
+        assert_eq!(
+            rst_explicit_markup_block(".. ")("  .. first\n     second\n   third\n  out"),
+            Ok(("\n  out", Cow::from("first   second third")))
+        );
+        assert_eq!(
+            rst_explicit_markup_block(".. ")(".. first\n second\n  \nout"),
+            Ok(("\n  \nout", Cow::from("first second")))
+        );
         assert_eq!(
             rst_explicit_markup_block(".. ")("   ..first"),
             Err(nom::Err::Error(nom::error::Error::new(
@@ -1269,4 +2247,343 @@ mod tests {
             Ok(("", Cow::Owned("http://www.python.org".to_string())))
         );
     }
+
+    #[test]
+    fn test_rst_standalone_uri() {
+        let expected = (
+            ">abc",
+            (
+                Cow::from("ftp://example.org/a_(b)?x=1#y."),
+                Cow::from("ftp://example.org/a_(b)?x=1#y."),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_standalone_uri("ftp://example.org/a_(b)?x=1#y.>abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "'. abc",
+            (
+                Cow::from("MAILTO:jane@example.org"),
+                Cow::from("MAILTO:jane@example.org"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_standalone_uri("MAILTO:jane@example.org'. abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            rst_standalone_uri("https://example.org/×"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "https://example.org/×",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_standalone_uri("foo:bar abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "foo:bar abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_standalone_uri("https://. abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "https://. abc",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_role_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("the <script>"),
+                Cow::from("../example.py"),
+                Cow::from("download"),
+            ),
+        );
+        assert_eq!(
+            rst_role_text2dest(r#":download:`the \<script\>  <../example.py>`abc"#).unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("my-label"),
+                Cow::from("my-label"),
+                Cow::from("ref"),
+            ),
+        );
+        assert_eq!(rst_role_text2dest(":ref:`my-label`abc").unwrap(), expected);
+        assert_eq!(rst_role_text2dest(":ref:`!my-label`abc").unwrap(), expected);
+
+        assert_eq!(
+            rst_role_text2dest("::`target`abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ":`target`abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_role_text2dest(":doc:`Title <~>`abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "Title <~>",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_role_text2dest(":doc:`target`_abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "_abc",
+                ErrorKind::Not
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_inline_target() {
+        assert_eq!(
+            rst_inline_target("_`a\\`b\\_c`"),
+            Ok(("", Cow::from("a`b_c")))
+        );
+        assert_eq!(
+            rst_inline_target("_`Python\nhome page`) abc"),
+            Ok((") abc", Cow::from("Python\nhome page")))
+        );
+        assert_eq!(
+            rst_inline_target("_`target`_ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "_ abc",
+                nom::error::ErrorKind::Not
+            )))
+        );
+        assert_eq!(
+            rst_inline_target("_` ` abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "_` ` abc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_inline_target("`target`_ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "`target`_ abc",
+                nom::error::ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_substitution_image() {
+        let i = "  .. |Python logo| IMAGE::\n       https://example.org/\n       python.png\n      :alt: The\n        Python logo\n      :width: 50\n      :target: `Python\n        home`_\n\n  abc";
+        let expected = (
+            "\n\n  abc",
+            (
+                Cow::from("Python logo"),
+                Cow::from("The Python logo"),
+                Cow::from("https://example.org/python.png"),
+                Cow::from("`Python home`_"),
+            ),
+        );
+        assert_eq!(rst_substitution_image(i).unwrap(), expected);
+
+        let expected = (
+            "",
+            (
+                Cow::from("x"),
+                Cow::from(""),
+                Cow::from("x.png"),
+                Cow::from("https://example.org/a"),
+            ),
+        );
+        assert_eq!(
+            rst_substitution_image(
+                ".. |x| image:: x.png\n   :target: https://example.org/\n     a"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            rst_substitution_image(".. |x| image::\n   :alt: x\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".. |x| image::\n   :alt: x\nabc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_substitution_image(".. |x| replace:: text\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "replace:: text\nabc",
+                nom::error::ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_image2dest() {
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("images/logo.png"),
+                Cow::from("https://example.org/home"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rst_image2dest("  .. IMAGE:: images/logo.png\n     :width: 50\n     :target: https://example.org/\n       home\nabc")
+                .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            rst_image2dest(".. image:: logo.png\n   :alt: logo\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".. image:: logo.png\n   :alt: logo\nabc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_image2dest(".. image:: logo.png\n   :target: `Python`_\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".. image:: logo.png\n   :target: `Python`_\nabc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_image2dest(".. |x| image:: logo.png\n   :target: https://example.org/\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "|x| image:: logo.png\n   :target: https://example.org/\nabc",
+                nom::error::ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_substitution2label() {
+        assert_eq!(
+            rst_substitution2label("|a\nb|_, abc"),
+            Ok((", abc", (Cow::from("a\nb"), Cow::from("a\nb"))))
+        );
+        assert_eq!(
+            rst_substitution2label("|logo| abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " abc",
+                nom::error::ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            rst_substitution2label("|logo|___ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "_ abc",
+                nom::error::ErrorKind::Not
+            )))
+        );
+        assert_eq!(
+            rst_substitution2label("| logo|_ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " logo|_ abc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn test_rst_explicit_markup() {
+        assert_eq!(
+            rst_explicit_markup("  .. note:: Read\r\n\r\n\t`this <https://example.org>`_\r\n  abc"),
+            Ok(("\r\n  abc", RstMarkup::Directive("note")))
+        );
+        assert_eq!(
+            rst_explicit_markup(".. _label: destination\nabc"),
+            Ok(("\nabc", RstMarkup::Target))
+        );
+        assert_eq!(
+            rst_explicit_markup(".. [#note] A footnote\n   continues.\n\nabc"),
+            Ok(("\n\nabc", RstMarkup::Footnote))
+        );
+        assert_eq!(
+            rst_explicit_markup(".. [CIT2002] A citation.\nabc"),
+            Ok(("\nabc", RstMarkup::Citation))
+        );
+        assert_eq!(
+            rst_explicit_markup("..\n   A comment\n\n   continues.\nabc"),
+            Ok(("\nabc", RstMarkup::Comment))
+        );
+        // An empty comment ends at the blank line.
+        assert_eq!(
+            rst_explicit_markup("..\n\n   A block quote.\nabc"),
+            Ok(("\n\n   A block quote.\nabc", RstMarkup::Comment))
+        );
+        assert_eq!(
+            rst_explicit_markup(".. |x|replace:: x\nabc"),
+            Ok(("\nabc", RstMarkup::Comment))
+        );
+        assert_eq!(
+            rst_explicit_markup("..abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "abc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert!(RstMarkup::Directive("Code-Block").is_literal());
+        assert!(!RstMarkup::Directive("image").is_literal());
+    }
+
+    #[test]
+    fn test_rst_directive_urls() {
+        let i = "  .. |logo| Image:: https://cdn.example.org/\n       logo.png\n     :alt: https://example.org/\n     :target: https://example.org/\n       home\n  abc";
+        let expected = (
+            "\n  abc",
+            vec![
+                (
+                    Cow::from("https://cdn.example.org/logo.png"),
+                    Cow::from("https://cdn.example.org/logo.png"),
+                    Cow::from("Image"),
+                ),
+                (
+                    Cow::from("https://example.org/home"),
+                    Cow::from("https://example.org/home"),
+                    Cow::from("Image"),
+                ),
+            ],
+        );
+        assert_eq!(rst_directive_urls(i).unwrap(), expected);
+
+        let expected = (
+            "\nabc",
+            vec![(
+                Cow::from("https://example.org/chapter.rst"),
+                Cow::from("https://example.org/chapter.rst"),
+                Cow::from("include"),
+            )],
+        );
+        assert_eq!(
+            rst_directive_urls(".. include:: https://example.org/chapter.rst\nabc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            rst_directive_urls(".. image:: images/logo.png\n   :target: `Python`_\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                ".. image:: images/logo.png\n   :target: `Python`_\nabc",
+                nom::error::ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rst_directive_urls(".. _label: https://example.org\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "_label: https://example.org\nabc",
+                nom::error::ErrorKind::MapOpt
+            )))
+        );
+    }
 }
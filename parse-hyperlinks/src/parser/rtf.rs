@@ -0,0 +1,319 @@
+//! This module implements parsers for RTF hyperlink fields.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `rtf_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn rtf_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = rtf_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an RTF `HYPERLINK` field.
+///
+/// The field `{\field{\*\fldinst HYPERLINK "url"}{\fldrslt text}}`
+/// consists of the field instruction and the field result, which is the
+/// _link text_. It returns either
+/// `Ok((i, (link_text, link_destination, link_title)))` or some error.
+///
+/// In the field instruction, the switch `\l "bookmark"` references a
+/// location in the same document, which results in the _link destination_
+/// `#bookmark`, or, together with a URL, `url#bookmark`. The switch
+/// `\o "tooltip"` sets the _link title_. As backslashes in RTF text are
+/// escaped, Word writes the switches as `\\l` and `\\o`. Other switches
+/// are ignored.
+///
+/// The field result is converted to plain text: control words and
+/// ignorable destinations `{\*...}` are removed, the control symbols
+/// `\\`, `\{`, `\}`, `\~` and the character escapes `\'hh` (interpreted
+/// as ISO 8859-1) and `\uN` are resolved and whitespace is normalized. An
+/// empty result yields the URL as _link text_. Fields with other
+/// instructions, e.g. `PAGE`, are not recognized.
+///
+/// The parser expects to start at the field start (`{`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::rtf::rtf_text2dest;
+/// use std::borrow::Cow;
+///
+/// let i = r#"{\field{\*\fldinst{HYPERLINK "https://example.org/" \\o "Tip"}}{\fldrslt{\ul\cf1 Caf\'e9}}}abc"#;
+/// assert_eq!(
+///   rtf_text2dest(i),
+///   Ok(("abc", (Cow::from("Café"), Cow::from("https://example.org/"), Cow::from("Tip"))))
+/// );
+/// ```
+pub fn rtf_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, content) = rtf_group(i)?;
+    let (mut k, _) = tag("\\field")(content)?;
+
+    // Find the field instruction and the field result groups.
+    let mut instruction = None;
+    let mut result = "";
+    while let Some(idx) = k.find('{') {
+        let (rest, group) = rtf_group(&k[idx..])?;
+        if let Some(g) = group.strip_prefix("\\*\\fldinst") {
+            instruction = Some(g);
+        } else if let Some(g) = group.strip_prefix("\\fldrslt") {
+            result = g;
+        }
+        k = rest;
+    }
+    let instruction = rtf_plain_text(
+        instruction
+            .ok_or_else(|| nom::Err::Error(nom::error::Error::new(content, ErrorKind::Tag)))?,
+    );
+
+    // Parse the instruction: `HYPERLINK "url" \l "bookmark" \o "tooltip"`.
+    let args = nom::sequence::preceded(
+        nom::character::complete::multispace0,
+        nom::bytes::complete::tag_no_case::<_, _, nom::error::Error<&str>>("HYPERLINK"),
+    )(instruction.as_str())
+    .map(|(args, _)| rtf_field_args(args))
+    .map_err(|_| nom::Err::Error(nom::error::Error::new(i, ErrorKind::Tag)))?;
+    let mut url = String::new();
+    let mut bookmark = "";
+    let mut link_title = String::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "\\l" => bookmark = args.next().map_or("", |a| a.as_str()),
+            "\\o" => link_title = args.next().cloned().unwrap_or_default(),
+            "\\t" => {
+                args.next();
+            }
+            a if a.starts_with('\\') => {}
+            a if url.is_empty() => url = a.to_string(),
+            _ => {}
+        }
+    }
+    if !bookmark.is_empty() {
+        url.push('#');
+        url.push_str(bookmark);
+    }
+    if url.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+
+    let link_text = rtf_plain_text(result);
+    let link_text = if link_text.is_empty() {
+        url.clone()
+    } else {
+        link_text
+    };
+
+    Ok((
+        j,
+        (
+            Cow::Owned(link_text),
+            Cow::Owned(url),
+            Cow::Owned(link_title),
+        ),
+    ))
+}
+
+/// Parses a group `{...}` and returns its content. Nested groups must be
+/// balanced, escaped braces `\{` and `\}` are ignored.
+fn rtf_group(i: &str) -> nom::IResult<&str, &str> {
+    let (j, _) = char('{')(i)?;
+    let mut depth = 0;
+    let mut chars = j.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '{' => depth += 1,
+            '}' if depth == 0 => return Ok((&j[idx + 1..], &j[..idx])),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        j,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Splits field instruction arguments at whitespace. Arguments can be
+/// enclosed in double quotes.
+fn rtf_field_args(i: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut rest = i.trim_start();
+    while !rest.is_empty() {
+        let (arg, r) = match rest.strip_prefix('"') {
+            Some(r) => r.split_once('"').unwrap_or((r, "")),
+            None => rest.split_once(char::is_whitespace).unwrap_or((rest, "")),
+        };
+        args.push(arg.to_string());
+        rest = r.trim_start();
+    }
+    args
+}
+
+/// Converts RTF text into plain text. See `rtf_text2dest()` for details.
+fn rtf_plain_text(i: &str) -> String {
+    let mut s = String::with_capacity(i.len());
+    let mut depth = 0;
+    // Depth of an ignorable destination `{\*...}` being skipped.
+    let mut skip_depth = None;
+    let mut k = i;
+    while let Some(c) = k.chars().next() {
+        k = &k[c.len_utf8()..];
+        match c {
+            '{' => {
+                depth += 1;
+                if skip_depth.is_none() && k.starts_with("\\*") {
+                    skip_depth = Some(depth);
+                }
+                continue;
+            }
+            '}' => {
+                if skip_depth == Some(depth) {
+                    skip_depth = None;
+                }
+                depth -= 1;
+                continue;
+            }
+            _ if skip_depth.is_some() => continue,
+            '\r' | '\n' => continue,
+            '\\' => {}
+            c => {
+                s.push(c);
+                continue;
+            }
+        }
+
+        // Control symbol or control word.
+        let mut chars = k.chars();
+        match chars.next() {
+            Some(e @ ('\\' | '{' | '}')) => {
+                s.push(e);
+                k = &k[1..];
+            }
+            Some('~') => {
+                s.push(' ');
+                k = &k[1..];
+            }
+            Some('_') => {
+                s.push('-');
+                k = &k[1..];
+            }
+            Some('\'') => {
+                let hex = k.get(1..3).and_then(|h| u8::from_str_radix(h, 16).ok());
+                if let Some(b) = hex {
+                    s.push(char::from(b));
+                    k = &k[3..];
+                } else {
+                    k = &k[1..];
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let word_len = k
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(k.len());
+                let word = &k[..word_len];
+                k = &k[word_len..];
+                let param_len = k
+                    .char_indices()
+                    .find(|&(idx, c)| !(c.is_ascii_digit() || (idx == 0 && c == '-')))
+                    .map_or(k.len(), |(idx, _)| idx);
+                let param = &k[..param_len];
+                k = &k[param_len..];
+                k = k.strip_prefix(' ').unwrap_or(k);
+                match word {
+                    "par" | "line" | "tab" | "emspace" | "enspace" => s.push(' '),
+                    "u" => {
+                        if let Ok(n) = param.parse::<i32>() {
+                            let n = if n < 0 { n + 65536 } else { n };
+                            s.push(char::from_u32(n as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+                            // Skip the fallback character.
+                            if k.starts_with("\\'") {
+                                k = k.get(4..).unwrap_or_default();
+                            } else if let Some(c) = k.chars().next() {
+                                if c != '\\' && c != '{' && c != '}' {
+                                    k = &k[c.len_utf8()..];
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Other control symbols, e.g. `\-`.
+            Some(c) => k = &k[c.len_utf8()..],
+            None => {}
+        }
+    }
+    s.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtf_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("Section {2} \u{2013} intro"),
+                Cow::from("doc.rtf#sec2"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rtf_text2dest(
+                "{\\field\\flddirty{\\*\\fldinst HYPERLINK \"doc.rtf\" \\\\l \"sec2\" }\r\n{\\fldrslt {\\*\\bkmkstart x}Section \\{2\\}\\~\\u8211?\\par intro}}abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/"),
+                Cow::from("https://example.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rtf_text2dest(
+                "{\\field{\\*\\fldinst {hyperlink https://example.org/}}{\\fldrslt }}abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            rtf_text2dest("{\\field{\\*\\fldinst PAGE}{\\fldrslt 1}}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "{\\field{\\*\\fldinst PAGE}{\\fldrslt 1}}abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            rtf_text2dest("{\\field{\\fldrslt text}}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\\field{\\fldrslt text}",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            rtf_text2dest("{\\field{\\*\\fldinst HYPERLINK}{\\fldrslt text}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "\\field{\\*\\fldinst HYPERLINK}{\\fldrslt text}abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! This module implements parsers for hyperlinks in Rust documentation
+//! comments.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::markdown::md_text2dest;
+use crate::parser::Link;
+use crate::take_until_unbalanced;
+use nom::bytes::complete::tag;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Disambiguators, which may prefix the item path of an intra-doc link, e.g.
+/// `struct@Vec`.
+const RUSTDOC_DISAMBIGUATORS: [&str; 20] = [
+    "const",
+    "constant",
+    "derive",
+    "enum",
+    "field",
+    "fn",
+    "function",
+    "macro",
+    "method",
+    "mod",
+    "module",
+    "prim",
+    "primitive",
+    "static",
+    "struct",
+    "tyalias",
+    "trait",
+    "type",
+    "union",
+    "value",
+];
+
+/// Wrapper around `rustdoc_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn rustdoc_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = rustdoc_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a Markdown inline link or an
+/// [intra-doc link](https://doc.rust-lang.org/rustdoc/write-documentation/linking-to-items-by-name.html)
+/// in a Rust documentation comment.
+///
+/// Recognized are:
+/// * Markdown inline links `[text](destination "title")`, see
+///   `markdown::md_text2dest()`. When the destination is an item path, e.g.
+///   `[text](crate::module::Item)`, it is normalized as described below.
+/// * Intra-doc shortcut links like ``[`Vec::push`]``. To distinguish them
+///   from Markdown shortcut reference links, the path must be enclosed in
+///   backticks, contain `::` or `@`, or end with `()` or `!`.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. For shortcut links, the _link text_ is the text between the
+/// brackets as written and the _link title_ is empty. Item paths are
+/// normalized: enclosing backticks, disambiguators like `struct@`, generic
+/// arguments like `<T>` and the suffixes `()` and `!` are removed. For
+/// example, ``[`Vec::<T>::push()`]`` has the _link destination_
+/// `Vec::push`.
+///
+/// The parser expects to start at the link start (`[`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::rustdoc::rustdoc_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   rustdoc_text2dest("[`Vec::push`] abc"),
+///   Ok((" abc", (Cow::from("`Vec::push`"), Cow::from("Vec::push"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   rustdoc_text2dest("[the parser](crate::parser::Link) abc"),
+///   Ok((" abc", (Cow::from("the parser"), Cow::from("crate::parser::Link"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   rustdoc_text2dest("[Rust](https://www.rust-lang.org/) abc"),
+///   Ok((" abc", (Cow::from("Rust"), Cow::from("https://www.rust-lang.org/"), Cow::from(""))))
+/// );
+/// ```
+pub fn rustdoc_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    // Inline link.
+    if let Ok((j, (link_text, link_destination, link_title))) = md_text2dest(i) {
+        let link_destination = match rustdoc_item_path(&link_destination) {
+            Some(path) if path != link_destination => Cow::Owned(path),
+            _ => link_destination,
+        };
+        return Ok((j, (link_text, link_destination, link_title)));
+    }
+
+    // Shortcut link.
+    let (j, link_text) =
+        nom::sequence::delimited(tag("["), take_until_unbalanced('[', ']'), tag("]"))(i)?;
+    if j.starts_with(['(', '[', ':']) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            j,
+            ErrorKind::Verify,
+        )));
+    }
+    let is_intra_doc =
+        (link_text.len() > 2 && link_text.starts_with('`') && link_text.ends_with('`'))
+            || link_text.contains("::")
+            || link_text.contains('@')
+            || link_text.ends_with("()")
+            || link_text.ends_with('!');
+    let link_destination = match rustdoc_item_path(link_text) {
+        Some(path) if is_intra_doc => path,
+        _ => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                link_text,
+                ErrorKind::Verify,
+            )))
+        }
+    };
+
+    Ok((
+        j,
+        (
+            Cow::Borrowed(link_text),
+            Cow::Owned(link_destination),
+            Cow::Borrowed(""),
+        ),
+    ))
+}
+
+/// Normalizes an item path, see `rustdoc_text2dest()`. Returns `None` if
+/// `i` is not a valid item path.
+fn rustdoc_item_path(i: &str) -> Option<String> {
+    let mut path = i.trim();
+    if let Some(p) = path.strip_prefix('`').and_then(|p| p.strip_suffix('`')) {
+        path = p;
+    }
+    if let Some((disambiguator, p)) = path.split_once('@') {
+        if !RUSTDOC_DISAMBIGUATORS.contains(&disambiguator) {
+            return None;
+        }
+        path = p;
+    }
+    for suffix in ["()", "!()", "![]", "!{}", "!"] {
+        if let Some(p) = path.strip_suffix(suffix) {
+            path = p;
+            break;
+        }
+    }
+
+    // Remove generic arguments.
+    let mut normalized = String::with_capacity(path.len());
+    let mut depth = 0;
+    for c in path.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' if depth > 0 => depth -= 1,
+            '>' => return None,
+            _ if depth == 0 => normalized.push(c),
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    let normalized = normalized.replace("::::", "::");
+    let normalized = normalized
+        .strip_suffix("::")
+        .map(str::to_string)
+        .unwrap_or(normalized);
+
+    let is_identifier = |s: &str| {
+        s.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    if normalized.split("::").all(is_identifier) {
+        Some(normalized)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rustdoc_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("`Vec::<T>::push()`"),
+                Cow::from("Vec::push"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            rustdoc_text2dest("[`Vec::<T>::push()`]abc").unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("the macro"),
+                Cow::from("std::println"),
+                Cow::from("Print"),
+            ),
+        );
+        assert_eq!(
+            rustdoc_text2dest("[the macro](macro@std::println! \"Print\")abc").unwrap(),
+            expected
+        );
+
+        let expected = ("abc", (Cow::from("`u8`"), Cow::from("u8"), Cow::from("")));
+        assert_eq!(rustdoc_text2dest("[`u8`]abc").unwrap(), expected);
+
+        assert_eq!(
+            rustdoc_text2dest("[label]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "label",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rustdoc_text2dest("[`Vec`][label]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "[label]abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            rustdoc_text2dest("[`a b`]abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "`a b`",
+                ErrorKind::Verify
+            )))
+        );
+    }
+}
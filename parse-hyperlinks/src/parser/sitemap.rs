@@ -0,0 +1,144 @@
+//! This module implements parsers for hyperlinks in XML sitemaps.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::xml_element_content;
+use crate::parser::xml::xml_start_tag;
+use crate::parser::xml::xml_text;
+use crate::parser::Link;
+use nom::error::Error;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `sitemap_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn sitemap_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = sitemap_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a `<url>` element of a sitemap or a `<sitemap>` element of a
+/// sitemap index file.
+///
+/// Both [elements](https://www.sitemaps.org/protocol.html) contain the URL
+/// in the child element `<loc>` and optionally the date of the last
+/// modification in `<lastmod>`. Other child elements, e.g. `<changefreq>`
+/// or `<priority>`, are ignored.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))`
+/// or some error. The _link text_ and the _link destination_ are the URL,
+/// the _link title_ is the content of `<lastmod>`, or empty if there is
+/// none. Entities and CDATA sections are resolved.
+///
+/// The parser expects to start at the element start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::sitemap::sitemap_text2dest;
+/// use std::borrow::Cow;
+///
+/// let i = "<url>
+///    <loc>https://example.org/</loc>
+///    <lastmod>2005-01-01</lastmod>
+/// </url>abc";
+///
+/// assert_eq!(
+///   sitemap_text2dest(i),
+///   Ok(("abc", (Cow::from("https://example.org/"), Cow::from("https://example.org/"), Cow::from("2005-01-01"))))
+/// );
+/// ```
+pub fn sitemap_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, _, is_empty)) = xml_start_tag(i)?;
+    if name != "url" && name != "sitemap" {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    }
+    if is_empty {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+    let (j, content) = xml_element_content(j, name)?;
+
+    // Collect the child elements.
+    let mut loc = None;
+    let mut lastmod = Cow::Borrowed("");
+    let mut k = content;
+    while let Some(idx) = k.find('<') {
+        let m = &k[idx..];
+        if let Some(comment) = m.strip_prefix("<!--") {
+            k = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if let Ok((rest, (child, _, false))) = xml_start_tag(m) {
+            if let Ok((rest, child_content)) = xml_element_content(rest, child) {
+                match child {
+                    "loc" => loc = Some(xml_text(child_content)),
+                    "lastmod" => lastmod = xml_text(child_content),
+                    _ => {}
+                }
+                k = rest;
+                continue;
+            }
+        }
+        k = &m[1..];
+    }
+
+    match loc {
+        Some(link_destination) if !link_destination.is_empty() => {
+            Ok((j, (link_destination.clone(), link_destination, lastmod)))
+        }
+        _ => Err(nom::Err::Error(Error::new(i, ErrorKind::Verify))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sitemap_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/sitemap1.xml.gz?a=1&b=2"),
+                Cow::from("https://example.org/sitemap1.xml.gz?a=1&b=2"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            sitemap_text2dest(
+                "<sitemap>\n<!-- <lastmod>x</lastmod> -->\n  <loc> https://example.org/sitemap1.xml.gz?a=1&amp;b=2 </loc>\n</sitemap>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("https://example.org/"),
+                Cow::from("https://example.org/"),
+                Cow::from("2004-12-23T18:00:15+00:00"),
+            ),
+        );
+        assert_eq!(
+            sitemap_text2dest(
+                "<url><lastmod>2004-12-23T18:00:15+00:00</lastmod><priority>0.8</priority><loc><![CDATA[https://example.org/]]></loc></url>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            sitemap_text2dest("<url><lastmod>2004-12-23</lastmod></url>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<url><lastmod>2004-12-23</lastmod></url>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            sitemap_text2dest("<urlset><url><loc>https://example.org/</loc></url></urlset>"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<urlset><url><loc>https://example.org/</loc></url></urlset>",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! This module implements parsers for SVG hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::xml::xml_element_content;
+use crate::parser::xml::xml_start_tag;
+use crate::parser::xml::xml_text;
+use crate::parser::Link;
+use nom::error::Error;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `svg_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn svg_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = svg_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses an SVG `<a>`, `<image>` or `<use>` element.
+///
+/// These [elements](https://www.w3.org/TR/SVG2/linking.html) reference
+/// their target with the SVG 2 attribute `href` or with the SVG 1.1
+/// attribute `xlink:href`. When both are present, `href` takes precedence,
+/// as required by the specification.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ is the element content converted to plain
+/// text: nested markup is removed, entities are decoded and whitespace is
+/// normalized. For empty elements, the _link text_ is the URL. The _link
+/// title_ is the value of the `xlink:title` attribute, if any. The whole
+/// element is consumed.
+///
+/// The parser expects to start at the element start (`<`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::svg::svg_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   svg_text2dest(r#"<a href="https://example.org/"><text x="5" y="15">Example</text></a>abc"#),
+///   Ok(("abc", (Cow::from("Example"), Cow::from("https://example.org/"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///   svg_text2dest(r#"<image xlink:href="logo.png" width="10" height="10"/>abc"#),
+///   Ok(("abc", (Cow::from("logo.png"), Cow::from("logo.png"), Cow::from(""))))
+/// );
+/// ```
+pub fn svg_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, attributes, is_empty)) = xml_start_tag(i)?;
+    if !["a", "image", "use"].contains(&name) {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Tag)));
+    }
+
+    let mut href = None;
+    let mut xlink_href = None;
+    let mut link_title = Cow::Borrowed("");
+    for (name, value) in attributes {
+        match name {
+            "href" => href = Some(value),
+            "xlink:href" => xlink_href = Some(value),
+            "xlink:title" => link_title = value,
+            _ => {}
+        }
+    }
+    let link_destination = href.or(xlink_href).unwrap_or_default();
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(Error::new(i, ErrorKind::Verify)));
+    }
+
+    let (j, content) = if is_empty {
+        (j, "")
+    } else {
+        xml_element_content(j, name)?
+    };
+    let link_text = match xml_text(content) {
+        t if t.is_empty() => link_destination.clone(),
+        t => t,
+    };
+
+    Ok((j, (link_text, link_destination, link_title)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("Circle & square"),
+                Cow::from("#shape"),
+                Cow::from("Shapes"),
+            ),
+        );
+        assert_eq!(
+            svg_text2dest(
+                "<use xlink:href=\"#old\" href=\"#shape\" xlink:title=\"Shapes\">\n  <desc>Circle &amp; square</desc>\n</use>abc"
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            svg_text2dest(
+                "<a xlink:href='#shape' xlink:title='Shapes'><tspan>Circle</tspan> &amp; <tspan>square</tspan></a>abc"
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            svg_text2dest("<a href=\"\"><text>Example</text></a>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<a href=\"\"><text>Example</text></a>abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            svg_text2dest("<rect href=\"#shape\"/>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<rect href=\"#shape\"/>abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            svg_text2dest("<a href=\"#shape\"><text>Example</text>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<text>Example</text>abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! This module implements parsers for Texinfo hyperlinks.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::Link;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `texinfo_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn texinfo_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = texinfo_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a Texinfo `@uref` or `@url` command.
+///
+/// The [command](https://www.gnu.org/software/texinfo/manual/texinfo/html_node/_0040url.html)
+/// `@uref{url[, text][, replacement]}` takes up to three comma separated
+/// arguments. `@url` is a synonym for `@uref`. It returns either
+/// `Ok((i, (link_text, link_destination, Cow::from(""))))` or some error.
+/// The _link text_ is the _replacement_, if given, otherwise the _text_,
+/// or, if neither is given, the URL. Leading and trailing whitespace of the
+/// arguments is removed, newlines are replaced by spaces. The escape
+/// sequences `@@`, `@{`, `@}` and `@comma{}` are resolved, all other
+/// commands in the arguments are returned verbatim.
+///
+/// The parser expects to start at the link start (`@`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::texinfo::texinfo_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     texinfo_text2dest("@uref{https://www.gnu.org/, GNU Homepage}abc"),
+///     Ok(("abc", (Cow::from("GNU Homepage"), Cow::from("https://www.gnu.org/"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     texinfo_text2dest("@uref{mailto:bug-texinfo@@gnu.org, , bug reports}abc"),
+///     Ok(("abc", (Cow::from("bug reports"), Cow::from("mailto:bug-texinfo@gnu.org"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     texinfo_text2dest("@url{https://www.gnu.org/}abc"),
+///     Ok(("abc", (Cow::from("https://www.gnu.org/"), Cow::from("https://www.gnu.org/"), Cow::from(""))))
+/// );
+/// ```
+pub fn texinfo_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, args) =
+        nom::sequence::preceded(alt((tag("@uref"), tag("@url"))), texinfo_braced_args)(i)?;
+    if args.len() > 3 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            args[3],
+            ErrorKind::ManyMN,
+        )));
+    }
+
+    let mut args = args.into_iter().map(texinfo_clean_arg);
+    let link_destination = args.next().unwrap_or_default();
+    if link_destination.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+    let text = args.next().unwrap_or_default();
+    let replacement = args.next().unwrap_or_default();
+    let link_text = if !replacement.is_empty() {
+        replacement
+    } else if !text.is_empty() {
+        text
+    } else {
+        link_destination.clone()
+    };
+
+    Ok((i, (link_text, link_destination, Cow::Borrowed(""))))
+}
+
+/// Parses the braced argument list of a command and splits it at the
+/// commas, which are not nested in braces. Escaped braces `@{` and `@}` are
+/// ignored.
+fn texinfo_braced_args(i: &str) -> nom::IResult<&str, Vec<&str>> {
+    let (j, _) = nom::character::complete::char('{')(i)?;
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut chars = j.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '@' => {
+                // Skip the escaped character.
+                chars.next();
+            }
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                args.push(&j[start..idx]);
+                return Ok((&j[idx + 1..], args));
+            }
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(&j[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        j,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Trims an argument, replaces newlines by spaces and resolves the escape
+/// sequences `@@`, `@{`, `@}` and `@comma{}`.
+fn texinfo_clean_arg(i: &str) -> Cow<str> {
+    let i = i.trim();
+    if !i.contains(['@', '\n', '\r']) {
+        return Cow::Borrowed(i);
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut rest = i;
+    while let Some(c) = rest.chars().next() {
+        if let Some(r) = rest.strip_prefix("@comma{}") {
+            s.push(',');
+            rest = r;
+            continue;
+        }
+        match (c, rest[c.len_utf8()..].chars().next()) {
+            ('@', Some(e)) if "@{}".contains(e) => {
+                s.push(e);
+                rest = &rest[2..];
+                continue;
+            }
+            ('\r', _) => {}
+            ('\n', _) => s.push(' '),
+            (c, _) => s.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texinfo_text2dest() {
+        let expected = (
+            "abc",
+            (
+                Cow::from("The GNU, Homepage"),
+                Cow::from("https://www.gnu.org/"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            texinfo_text2dest("@uref{ https://www.gnu.org/ ,\nThe GNU@comma{}\nHomepage }abc")
+                .unwrap(),
+            expected
+        );
+
+        let expected = (
+            "abc",
+            (
+                Cow::from("@code{x, y}"),
+                Cow::from("https://example.org/{x}"),
+                Cow::from(""),
+            ),
+        );
+        assert_eq!(
+            texinfo_text2dest("@url{https://example.org/@{x@},@code{x, y}}abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            texinfo_text2dest("@uref{a, b, c, d}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " d",
+                ErrorKind::ManyMN
+            )))
+        );
+        assert_eq!(
+            texinfo_text2dest("@uref{ , text}abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "abc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            texinfo_text2dest("@uref{https://www.gnu.org/ abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "https://www.gnu.org/ abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+}
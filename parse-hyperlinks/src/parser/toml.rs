@@ -0,0 +1,460 @@
+//! This module implements a scanner to extract URLs from TOML documents.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::json::json_looks_like_url;
+use crate::parser::Link;
+use std::borrow::Cow;
+
+/// Maximum nesting depth of arrays and inline tables. Deeper values are
+/// skipped.
+const TOML_MAX_DEPTH: usize = 128;
+
+/// Wrapper around `toml_urls()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn toml_urls_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = toml_urls(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Scans a TOML document and extracts all string values that look like a
+/// URL.
+///
+/// A string looks like a URL when it starts with a scheme followed by
+/// `://`, e.g. `https://`, or with `mailto:`, the same heuristic as
+/// `json::json_urls()` applies. Keys are never extracted.
+///
+/// It returns `Ok(("", links))`, where every item of `links` is a tuple
+/// `(link_text, link_destination, Cow::from(""))`. The _link text_ is the
+/// dotted key path of the value, e.g. `package.repository`, as it would be
+/// written in [TOML](https://toml.io/en/v1.0.0). Keys which are not bare
+/// keys are quoted, e.g. `"a.b"`, array elements and array of tables are
+/// indexed with brackets, e.g. `bin[0].path` or `urls[1]`. The links appear
+/// in document order. The whole input is consumed.
+///
+/// All four kinds of strings, arrays and inline tables spanning several
+/// lines and comments are understood. Lines which are not valid TOML are
+/// skipped.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::toml::toml_urls;
+/// use std::borrow::Cow;
+///
+/// let i = r#"[package]
+/// name = "parse-hyperlinks"
+/// repository = "https://gitlab.com/getreu/parse-hyperlinks" # source
+///
+/// [[bin]]
+/// name = "a"
+///
+/// [[bin]]
+/// docs = { home = 'https://example.org/' }
+/// "#;
+///
+/// assert_eq!(
+///   toml_urls(i),
+///   Ok(("", vec![
+///       (Cow::from("package.repository"), Cow::from("https://gitlab.com/getreu/parse-hyperlinks"), Cow::from("")),
+///       (Cow::from("bin[1].docs.home"), Cow::from("https://example.org/"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn toml_urls(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let mut links = Vec::new();
+    // The rendered key path of the current table.
+    let mut table = String::new();
+    // The current index of every array of tables, keyed by its key path.
+    let mut array_tables: Vec<(Vec<Cow<str>>, usize)> = Vec::new();
+
+    let mut k = i;
+    loop {
+        k = toml_skip(k, true);
+        if k.is_empty() {
+            break;
+        }
+
+        // Table header `[key]` or array of tables header `[[key]]`.
+        if let Some(header) = k.strip_prefix('[') {
+            let is_array = header.starts_with('[');
+            let header = if is_array { &header[1..] } else { header };
+            let parsed = toml_key(header).and_then(|(rest, key)| {
+                let rest = toml_skip(rest, false).strip_prefix(']')?;
+                let rest = if is_array {
+                    rest.strip_prefix(']')?
+                } else {
+                    rest
+                };
+                Some((rest, key))
+            });
+            match parsed {
+                Some((rest, key)) => {
+                    if is_array {
+                        // A new element resets the arrays of tables nested in it.
+                        array_tables
+                            .retain(|(k, _)| k.len() <= key.len() || k[..key.len()] != key[..]);
+                        match array_tables.iter_mut().find(|(k, _)| *k == key) {
+                            Some((_, index)) => *index += 1,
+                            None => array_tables.push((key.clone(), 0)),
+                        }
+                    }
+                    table.clear();
+                    for n in 1..=key.len() {
+                        toml_push_key(&mut table, &key[n - 1]);
+                        if let Some((_, index)) =
+                            array_tables.iter().find(|(k, _)| k[..] == key[..n])
+                        {
+                            table.push_str(&format!("[{}]", index));
+                        }
+                    }
+                    k = rest;
+                }
+                None => k = toml_next_line(k),
+            }
+            continue;
+        }
+
+        // Key/value pair.
+        let parsed = toml_key(k).and_then(|(rest, key)| {
+            let rest = toml_skip(rest, false).strip_prefix('=')?;
+            let mut path = table.clone();
+            for segment in &key {
+                toml_push_key(&mut path, segment);
+            }
+            toml_value(toml_skip(rest, false), &path, 0, &mut links)
+        });
+        k = match parsed {
+            Some(rest) => rest,
+            None => toml_next_line(k),
+        };
+    }
+
+    Ok(("", links))
+}
+
+/// Parses a value, pushes the string values looking like a URL to `links`
+/// and returns the input after the value. Returns `None` if the value is not
+/// valid.
+fn toml_value<'a>(
+    i: &'a str,
+    path: &str,
+    depth: usize,
+    links: &mut Vec<(Cow<'a, str>, Cow<'a, str>, Cow<'a, str>)>,
+) -> Option<&'a str> {
+    if depth > TOML_MAX_DEPTH {
+        return None;
+    }
+    match i.chars().next()? {
+        '"' | '\'' => {
+            let (rest, value) = toml_string(i)?;
+            if json_looks_like_url(&value) {
+                links.push((Cow::Owned(path.to_string()), value, Cow::Borrowed("")));
+            }
+            Some(rest)
+        }
+        '[' => {
+            let mut j = &i[1..];
+            let mut index = 0;
+            loop {
+                j = toml_skip(j, true);
+                if let Some(rest) = j.strip_prefix(']') {
+                    return Some(rest);
+                }
+                j = toml_value(j, &format!("{}[{}]", path, index), depth + 1, links)?;
+                index += 1;
+                j = toml_skip(j, true);
+                match j.strip_prefix(',') {
+                    Some(rest) => j = rest,
+                    None => return j.strip_prefix(']'),
+                }
+            }
+        }
+        '{' => {
+            let mut j = &i[1..];
+            loop {
+                j = toml_skip(j, true);
+                if let Some(rest) = j.strip_prefix('}') {
+                    return Some(rest);
+                }
+                let (rest, key) = toml_key(j)?;
+                let rest = toml_skip(rest, false).strip_prefix('=')?;
+                let mut p = path.to_string();
+                for segment in &key {
+                    toml_push_key(&mut p, segment);
+                }
+                j = toml_value(toml_skip(rest, false), &p, depth + 1, links)?;
+                j = toml_skip(j, true);
+                match j.strip_prefix(',') {
+                    Some(rest) => j = rest,
+                    None => return j.strip_prefix('}'),
+                }
+            }
+        }
+        _ => {
+            // Number, boolean or date.
+            let end = i.find([',', ']', '}', '#', '\r', '\n']).unwrap_or(i.len());
+            if i[..end].trim_end().is_empty() {
+                return None;
+            }
+            Some(&i[end..])
+        }
+    }
+}
+
+/// Parses a dotted key, e.g. `a."b.c".d`, and returns its segments.
+fn toml_key(i: &str) -> Option<(&str, Vec<Cow<str>>)> {
+    let mut key = Vec::new();
+    let mut j = toml_skip(i, false);
+    loop {
+        let (rest, segment) = if j.starts_with(['"', '\'']) {
+            if j.starts_with("\"\"\"") || j.starts_with("'''") {
+                return None;
+            }
+            toml_string(j)?
+        } else {
+            let end = j
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(j.len());
+            if end == 0 {
+                return None;
+            }
+            (&j[end..], Cow::Borrowed(&j[..end]))
+        };
+        key.push(segment);
+        let rest = toml_skip(rest, false);
+        match rest.strip_prefix('.') {
+            Some(rest) => j = toml_skip(rest, false),
+            None => return Some((rest, key)),
+        }
+    }
+}
+
+/// Parses a basic, literal, multi-line basic or multi-line literal string
+/// and returns its value with the escape sequences resolved.
+fn toml_string(i: &str) -> Option<(&str, Cow<str>)> {
+    for (delimiter, is_basic) in [("\"\"\"", true), ("'''", false)] {
+        if let Some(j) = i.strip_prefix(delimiter) {
+            // A newline immediately following the opening delimiter is trimmed.
+            let j = j
+                .strip_prefix("\r\n")
+                .or_else(|| j.strip_prefix('\n'))
+                .unwrap_or(j);
+            let quote = delimiter.chars().next()?;
+            let mut chars = j.char_indices();
+            while let Some((idx, c)) = chars.next() {
+                if c == '\\' && is_basic {
+                    chars.next();
+                } else if j[idx..].starts_with(delimiter) {
+                    // Up to two quotes are allowed before the delimiter.
+                    let quotes = j[idx..].chars().take_while(|&c| c == quote).count();
+                    if quotes > 5 {
+                        return None;
+                    }
+                    let end = idx + quotes - 3;
+                    let value = if is_basic {
+                        toml_unescape(&j[..end])?
+                    } else {
+                        Cow::Borrowed(&j[..end])
+                    };
+                    return Some((&j[idx + quotes..], value));
+                }
+            }
+            return None;
+        }
+    }
+
+    let quote = i.chars().next()?;
+    let j = i.strip_prefix(['"', '\''])?;
+    let mut chars = j.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            '\r' | '\n' => return None,
+            c if c == quote => {
+                let value = if quote == '"' {
+                    toml_unescape(&j[..idx])?
+                } else {
+                    Cow::Borrowed(&j[..idx])
+                };
+                return Some((&j[idx + 1..], value));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Resolves the escape sequences of a basic string. A backslash at the end
+/// of a line removes all whitespace up to the next non-whitespace
+/// character. Returns `None` if an escape sequence is invalid.
+fn toml_unescape(i: &str) -> Option<Cow<str>> {
+    if !i.contains('\\') {
+        return Some(Cow::Borrowed(i));
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut chars = i.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            s.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'b' => s.push('\x08'),
+            't' => s.push('\t'),
+            'n' => s.push('\n'),
+            'f' => s.push('\x0C'),
+            'r' => s.push('\r'),
+            'e' => s.push('\x1B'),
+            c @ ('"' | '\\') => s.push(c),
+            e @ ('u' | 'U') => {
+                let len = if e == 'u' { 4 } else { 8 };
+                let hex: String = chars.by_ref().take(len).collect();
+                s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            c if c.is_whitespace() => {
+                // Line ending backslash.
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            _ => return None,
+        }
+    }
+    Some(Cow::Owned(s))
+}
+
+/// Appends a key segment to the rendered key path. Keys which are not bare
+/// keys are quoted.
+fn toml_push_key(path: &mut String, key: &str) {
+    if !path.is_empty() {
+        path.push('.');
+    }
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        path.push_str(key);
+    } else {
+        path.push('"');
+        path.push_str(&key.replace('\\', "\\\\").replace('"', "\\\""));
+        path.push('"');
+    }
+}
+
+/// Skips spaces, tabs and comments. With `newlines`, newlines are skipped
+/// too, otherwise a comment is skipped up to the line ending.
+fn toml_skip(i: &str, newlines: bool) -> &str {
+    let mut k = i;
+    loop {
+        k = k.trim_start_matches([' ', '\t']);
+        if let Some(comment) = k.strip_prefix('#') {
+            k = &comment[comment.find(['\r', '\n']).unwrap_or(comment.len())..];
+        } else if newlines && k.starts_with(['\r', '\n']) {
+            k = &k[1..];
+        } else {
+            return k;
+        }
+    }
+}
+
+/// Returns the input after the next line ending.
+fn toml_next_line(i: &str) -> &str {
+    i.find('\n').map_or("", |idx| &i[idx + 1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_urls() {
+        let i = r#"
+homepage = "https://example.org/\u00e9"
+"site.url" = 'http://example.org/'
+not valid = https://example.org/ignored
+[dependencies]
+foo.git = "https://example.org/foo.git"
+[[products]]
+name = "a"
+[[products.urls]]
+url = """
+https://example.org/a1"""
+[[products]]
+[[products.urls]]
+url = '''ftp://example.org/b1'''
+[target."cfg(unix)"]
+mirrors = [
+  "https://example.org/1", # comment
+  ["mailto:info@example.org"],
+]
+"#;
+        let expected = (
+            "",
+            vec![
+                (
+                    Cow::from("homepage"),
+                    Cow::from("https://example.org/\u{e9}"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("\"site.url\""),
+                    Cow::from("http://example.org/"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("dependencies.foo.git"),
+                    Cow::from("https://example.org/foo.git"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("products[0].urls[0].url"),
+                    Cow::from("https://example.org/a1"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("products[1].urls[0].url"),
+                    Cow::from("ftp://example.org/b1"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("target.\"cfg(unix)\".mirrors[0]"),
+                    Cow::from("https://example.org/1"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("target.\"cfg(unix)\".mirrors[1][0]"),
+                    Cow::from("mailto:info@example.org"),
+                    Cow::from(""),
+                ),
+            ],
+        );
+        assert_eq!(toml_urls(i).unwrap(), expected);
+
+        let i = "title = \"日本語テキスト\"\nnote = '日本語テキスト'\n";
+        assert_eq!(toml_urls(i).unwrap(), ("", vec![]));
+    }
+
+    #[test]
+    fn test_toml_string() {
+        assert_eq!(
+            toml_string(r#""a\"b\tc" x"#),
+            Some((" x", Cow::from("a\"b\tc")))
+        );
+        assert_eq!(
+            toml_string("\"\"\"a \\\n   b\"\"\"\"\" x"),
+            Some((" x", Cow::from("a b\"\"")))
+        );
+        assert_eq!(
+            toml_string(r#"'C:\path' x"#),
+            Some((" x", Cow::from("C:\\path")))
+        );
+        assert_eq!(toml_string("\"a\nb\""), None);
+        assert_eq!(toml_string(r#""\x41""#), None);
+    }
+}
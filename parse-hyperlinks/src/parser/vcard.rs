@@ -0,0 +1,150 @@
+//! This module implements parsers for hyperlinks in vCard files.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::icalendar::icalendar_content_line;
+use crate::parser::Link;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Wrapper around `vcard_text2dest()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn vcard_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = vcard_text2dest(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parses a vCard `URL` or `PHOTO` property.
+///
+/// The [properties](https://www.rfc-editor.org/rfc/rfc6350#section-6.7.8)
+/// are content lines like `URL;TYPE=work:https://example.org/` and may be
+/// prefixed by a group, e.g. `item1.URL:...`. Long content lines are
+/// folded as in iCalendar. `PHOTO` holds a URI in vCard 4.0, in vCard 3.0
+/// only with the parameter `VALUE=uri` (`VALUE=url` in vCard 2.1). A
+/// `PHOTO` property with an `ENCODING` parameter holds inline data and is no
+/// link.
+///
+/// It returns either `Ok((i, (link_text, link_destination, link_title)))` or
+/// some error. The _link text_ and the _link destination_ are the unfolded
+/// value. Some applications escape `:`, `,` and `;` in URLs with a
+/// backslash; these escape sequences are resolved. The _link title_ is the
+/// property name, `URL` or `PHOTO`, without group. Names and parameters are
+/// case insensitive. The line ending of the content line is not consumed.
+///
+/// The parser expects to start at the beginning of the content line to
+/// succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::vcard::vcard_text2dest;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   vcard_text2dest("item1.URL;type=pref:http\\://example.org/\r\nEND:VCARD"),
+///   Ok(("\r\nEND:VCARD", (Cow::from("http://example.org/"),
+///                         Cow::from("http://example.org/"),
+///                         Cow::from("URL"))))
+/// );
+/// assert_eq!(
+///   vcard_text2dest("PHOTO;VALUE=uri:https://example.org/\r\n jdoe.jpg\r\nabc"),
+///   Ok(("\r\nabc", (Cow::from("https://example.org/jdoe.jpg"),
+///                   Cow::from("https://example.org/jdoe.jpg"),
+///                   Cow::from("PHOTO"))))
+/// );
+/// ```
+pub fn vcard_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (j, (name, params, value)) = icalendar_content_line(i)?;
+    let name = name.rsplit('.').next().unwrap_or_default();
+    let link_title = if name.eq_ignore_ascii_case("URL") {
+        "URL"
+    } else if name.eq_ignore_ascii_case("PHOTO") {
+        "PHOTO"
+    } else {
+        return Err(nom::Err::Error(nom::error::Error::new(i, ErrorKind::Tag)));
+    };
+
+    let param = |wanted: &str| {
+        params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(wanted))
+            .map(|(_, v)| v.clone())
+    };
+    let is_uri = match param("VALUE") {
+        Some(v) => v.eq_ignore_ascii_case("uri") || v.eq_ignore_ascii_case("url"),
+        None => param("ENCODING").is_none(),
+    };
+    if !is_uri || value.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            ErrorKind::Verify,
+        )));
+    }
+
+    let link_destination = vcard_unescape(value);
+    Ok((
+        j,
+        (
+            link_destination.clone(),
+            link_destination,
+            Cow::Borrowed(link_title),
+        ),
+    ))
+}
+
+/// Resolves the escape sequences `\:`, `\,`, `\;` and `\\`.
+fn vcard_unescape(i: Cow<str>) -> Cow<str> {
+    if !i.contains('\\') {
+        return i;
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut chars = i.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&e)) if ":,;\\".contains(e) => {
+                s.push(e);
+                chars.next();
+            }
+            (c, _) => s.push(c),
+        }
+    }
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vcard_text2dest() {
+        let expected = (
+            "\nabc",
+            (
+                Cow::from("https://example.org/a,b"),
+                Cow::from("https://example.org/a,b"),
+                Cow::from("PHOTO"),
+            ),
+        );
+        assert_eq!(
+            vcard_text2dest("photo;MEDIATYPE=image/jpeg:https://example.org/a\\,b\nabc").unwrap(),
+            expected
+        );
+        assert_eq!(
+            vcard_text2dest("PHOTO;JPEG;VALUE=URL:https://example.org/a\\,b\nabc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            vcard_text2dest("PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN\nabc",
+                ErrorKind::Verify
+            )))
+        );
+        assert_eq!(
+            vcard_text2dest("URL.TEL:+1-555-0100\nabc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "URL.TEL:+1-555-0100\nabc",
+                ErrorKind::Tag
+            )))
+        );
+    }
+}
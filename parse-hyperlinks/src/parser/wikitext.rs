@@ -3,7 +3,6 @@
 #![allow(clippy::type_complexity)]
 
 use crate::parser::Link;
-use nom::branch::alt;
 use nom::bytes::complete::is_not;
 use nom::bytes::complete::tag;
 use percent_encoding::percent_decode_str;
@@ -21,6 +20,14 @@ pub fn wikitext_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 /// It returns either `Ok((i, (link_text, link_destination, Cow::from("")))`
 /// or some error.
 ///
+/// The link destination must start with one of the protocols MediaWiki
+/// recognizes by default (see `$wgUrlProtocols`), e.g. `https://`, `ftp://`
+/// or `mailto:`. The protocol is matched case insensitively. The _link text_
+/// is separated from the _link destination_ by whitespace. When it is
+/// missing, the returned _link text_ is empty: MediaWiki renders such
+/// bare-bracket links as numbered references `[1]`, `[2]`, ... whose number
+/// depends on their position in the page, which is the renderer's business.
+///
 /// The parser expects to start at the link start (`[`) to succeed.
 /// ```
 /// use parse_hyperlinks::parser::Link;
@@ -39,6 +46,19 @@ pub fn wikitext_text2dest_link(i: &str) -> nom::IResult<&str, Link> {
 ///     wikitext_text2dest("[https://www.w3schools.com/ W3Schools]abc").unwrap(),
 ///     expected
 /// );
+///
+/// let expected = (
+///     "abc",
+///     (
+///         Cow::from(""),
+///         Cow::from("ftp://example.org/file"),
+///         Cow::from(""),
+///     ),
+/// );
+/// assert_eq!(
+///     wikitext_text2dest("[ftp://example.org/file]abc").unwrap(),
+///     expected
+/// );
 /// ```
 pub fn wikitext_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
     let (i, (link_text, link_destination)) = nom::sequence::delimited(
@@ -51,6 +71,68 @@ pub fn wikitext_text2dest(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Co
     Ok((i, (link_text, link_destination, Cow::from(""))))
 }
 
+/// Wrapper around `wikitext_wikilink()` that packs the result in
+/// `Link::Text2Dest`.
+pub fn wikitext_wikilink_link(i: &str) -> nom::IResult<&str, Link> {
+    let (i, (te, de, ti)) = wikitext_wikilink(i)?;
+    Ok((i, Link::Text2Dest(te, de, ti)))
+}
+
+/// Parse a _wikilink_ (internal link) as used by MediaWiki and note-taking
+/// applications like Obsidian or Zettlr.
+///
+/// It returns either `Ok((i, (link_text, link_destination, Cow::from("")))`
+/// or some error. The _link destination_ is the page name, including an
+/// optional `#heading` fragment. When no label is given, the _link text_ is
+/// the same as the _link destination_.
+///
+/// The parser expects to start at the link start (`[[`) to succeed.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::wikitext::wikitext_wikilink;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///     wikitext_wikilink("[[Main Page#History|the history]]abc"),
+///     Ok(("abc", (Cow::from("the history"), Cow::from("Main Page#History"), Cow::from(""))))
+/// );
+/// assert_eq!(
+///     wikitext_wikilink("[[Main Page]]abc"),
+///     Ok(("abc", (Cow::from("Main Page"), Cow::from("Main Page"), Cow::from(""))))
+/// );
+/// ```
+pub fn wikitext_wikilink(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>, Cow<str>)> {
+    let (i, (link_destination, link_text)) = nom::sequence::delimited(
+        tag("[["),
+        nom::sequence::pair(
+            nom::combinator::map(
+                nom::combinator::verify(is_not("[]|\n\r"), |s: &str| !s.trim().is_empty()),
+                str::trim,
+            ),
+            nom::combinator::opt(nom::sequence::preceded(
+                tag("|"),
+                nom::combinator::map(
+                    nom::bytes::complete::take_till(|c| "[]\n\r".contains(c)),
+                    str::trim,
+                ),
+            )),
+        ),
+        tag("]]"),
+    )(i)?;
+    let link_text = match link_text {
+        Some(t) if !t.is_empty() => t,
+        _ => link_destination,
+    };
+    Ok((
+        i,
+        (
+            Cow::from(link_text),
+            Cow::from(link_destination),
+            Cow::from(""),
+        ),
+    ))
+}
+
 /// Parse link destination and link text.
 fn parse_inner(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
     let (i, link_destination) = nom::sequence::terminated(
@@ -60,13 +142,55 @@ fn parse_inner(i: &str) -> nom::IResult<&str, (Cow<str>, Cow<str>)> {
         ),
         nom::character::complete::space0,
     )(i)?;
-    let link_text = i;
+    let link_text = i.trim_end();
     Ok((i, (Cow::from(link_text), link_destination)))
 }
 
+/// Protocols MediaWiki recognizes in external links by default. See
+/// [$wgUrlProtocols](https://www.mediawiki.org/wiki/Manual:$wgUrlProtocols).
+const URL_PROTOCOLS: [&str; 29] = [
+    "bitcoin:",
+    "ftp://",
+    "ftps://",
+    "geo:",
+    "git://",
+    "gopher://",
+    "http://",
+    "https://",
+    "irc://",
+    "ircs://",
+    "magnet:",
+    "mailto:",
+    "matrix:",
+    "mms://",
+    "news:",
+    "nntp://",
+    "redis://",
+    "sftp://",
+    "sip:",
+    "sips:",
+    "sms:",
+    "ssh://",
+    "svn://",
+    "tel:",
+    "telnet://",
+    "urn:",
+    "worldwind://",
+    "xmpp:",
+    "//",
+];
+
 /// Parse URL.
 fn parse_url(i: &str) -> nom::IResult<&str, Cow<str>> {
-    nom::combinator::peek(alt((tag("http:"), tag("https:"), tag("mailto:"))))(i)?;
+    if !URL_PROTOCOLS.iter().any(|p| {
+        i.get(..p.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(p) && i.len() > p.len())
+    }) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Tag,
+        )));
+    }
     // We can safely unwrap here because `str` is guaranteed to be
     // UTF-8.
     let url = percent_decode_str(i).decode_utf8().unwrap();
@@ -119,6 +243,10 @@ fn test_wikitext_text2dest() {
         wikitext_text2dest("[http://www.w3schools.com/\tW3Schools website]abc").unwrap(),
         expected
     );
+    assert_eq!(
+        wikitext_text2dest("[http://www.w3schools.com/ W3Schools website ]abc").unwrap(),
+        expected
+    );
     let expected = (
         "abc",
         (
@@ -152,6 +280,31 @@ fn test_wikitext_text2dest() {
         expected
     );
 
+    let expected = (
+        "abc",
+        (
+            Cow::from("Git repository"),
+            Cow::from("GIT://example.org/repo.git"),
+            Cow::from(""),
+        ),
+    );
+    assert_eq!(
+        wikitext_text2dest("[GIT://example.org/repo.git Git repository]abc").unwrap(),
+        expected
+    );
+    let expected = (
+        "abc",
+        (Cow::from(""), Cow::from("//example.org/"), Cow::from("")),
+    );
+    assert_eq!(wikitext_text2dest("[//example.org/]abc").unwrap(), expected);
+
+    assert_eq!(
+        wikitext_text2dest("[https:// W3Schools]abc").unwrap_err(),
+        nom::Err::Error(nom::error::Error::new(
+            "https://",
+            nom::error::ErrorKind::Tag
+        ))
+    );
     assert_eq!(
         wikitext_text2dest(r#"[httpx://www.w3schools.com/ W3Schools]abc"#).unwrap_err(),
         nom::Err::Error(nom::error::Error::new(
@@ -160,3 +313,59 @@ fn test_wikitext_text2dest() {
         ))
     );
 }
+
+#[test]
+fn test_wikitext_wikilink() {
+    let expected = ("abc", (Cow::from("Page"), Cow::from("Page"), Cow::from("")));
+    assert_eq!(wikitext_wikilink("[[Page]]abc").unwrap(), expected);
+    assert_eq!(wikitext_wikilink("[[ Page ]]abc").unwrap(), expected);
+    assert_eq!(wikitext_wikilink("[[Page|]]abc").unwrap(), expected);
+
+    let expected = (
+        "abc",
+        (Cow::from("my label"), Cow::from("Page"), Cow::from("")),
+    );
+    assert_eq!(wikitext_wikilink("[[Page|my label]]abc").unwrap(), expected);
+    assert_eq!(
+        wikitext_wikilink("[[Page | my label ]]abc").unwrap(),
+        expected
+    );
+
+    let expected = (
+        "abc",
+        (
+            Cow::from("Page#Heading"),
+            Cow::from("Page#Heading"),
+            Cow::from(""),
+        ),
+    );
+    assert_eq!(wikitext_wikilink("[[Page#Heading]]abc").unwrap(), expected);
+
+    let expected = (
+        "abc",
+        (Cow::from("#Heading"), Cow::from("#Heading"), Cow::from("")),
+    );
+    assert_eq!(wikitext_wikilink("[[#Heading]]abc").unwrap(), expected);
+
+    assert_eq!(
+        wikitext_wikilink("[[]]abc").unwrap_err(),
+        nom::Err::Error(nom::error::Error::new(
+            "]]abc",
+            nom::error::ErrorKind::IsNot
+        ))
+    );
+    assert_eq!(
+        wikitext_wikilink("[[ ]]abc").unwrap_err(),
+        nom::Err::Error(nom::error::Error::new(
+            " ]]abc",
+            nom::error::ErrorKind::Verify
+        ))
+    );
+    assert_eq!(
+        wikitext_wikilink("[[Page\nPage]]abc").unwrap_err(),
+        nom::Err::Error(nom::error::Error::new(
+            "\nPage]]abc",
+            nom::error::ErrorKind::Tag
+        ))
+    );
+}
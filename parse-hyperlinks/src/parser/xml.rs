@@ -0,0 +1,320 @@
+//! This module implements building blocks for parsers of XML based formats.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use html_escape::decode_html_entities;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::character::complete::multispace0;
+use nom::character::complete::multispace1;
+use nom::error::ErrorKind;
+use std::borrow::Cow;
+
+/// Names of attributes holding an URI, which `take_xml_uri_attributes()`
+/// extracts.
+pub const XML_URI_ATTRIBUTES: [&str; 3] = ["href", "xlink:href", "src"];
+
+/// Consumes the input until the parser finds a start tag with at least one
+/// URI attribute, i.e. `href`, `xlink:href` or `src`, of any element.
+///
+/// It returns either `Ok((i, (skipped, element_name, uri_attributes)))` or
+/// some error. `i` is the input after the start tag. Every item of
+/// `uri_attributes` is a tuple `(start, end, attribute_name, value)` where
+/// `input[start..end]` is the attribute in the source text. Comments are
+/// skipped. This parser does not know any XML vocabulary: it works with
+/// any XML based format, e.g. SVG, XHTML or MathML.
+/// ```
+/// use parse_hyperlinks::parser::xml::take_xml_uri_attributes;
+/// use std::borrow::Cow;
+///
+/// let i = r#"<svg><!-- <a href="x"/> --><image width="5" xlink:href="a.png" src="b.png"/>abc"#;
+/// let (rest, (skipped, element, attributes)) = take_xml_uri_attributes(i).unwrap();
+/// assert_eq!(rest, "abc");
+/// assert_eq!(skipped, r#"<svg><!-- <a href="x"/> -->"#);
+/// assert_eq!(element, "image");
+/// assert_eq!(attributes, vec![
+///     (44, 62, "xlink:href", Cow::from("a.png")),
+///     (63, 74, "src", Cow::from("b.png")),
+/// ]);
+/// assert_eq!(&i[44..62], r#"xlink:href="a.png""#);
+/// ```
+pub fn take_xml_uri_attributes(
+    i: &str,
+) -> nom::IResult<&str, (&str, &str, Vec<(usize, usize, &str, Cow<str>)>)> {
+    let mut k = i;
+    while let Some(idx) = k.find('<') {
+        let j = &k[idx..];
+        if let Some(comment) = j.strip_prefix("<!--") {
+            match comment.find("-->") {
+                Some(end) => {
+                    k = &comment[end + 3..];
+                    continue;
+                }
+                None => break,
+            }
+        }
+        if let Ok((mut m, name)) = nom::sequence::preceded(char('<'), xml_name)(j) {
+            let mut uri_attributes = Vec::new();
+            while let Ok((a, (raw, (n, value)))) = nom::sequence::preceded(
+                multispace1::<_, nom::error::Error<&str>>,
+                nom::combinator::consumed(xml_attribute),
+            )(m)
+            {
+                if XML_URI_ATTRIBUTES.contains(&n) {
+                    let end = i.len() - a.len();
+                    uri_attributes.push((end - raw.len(), end, n, value));
+                }
+                m = a;
+            }
+            let end_of_tag: nom::IResult<&str, &str> =
+                nom::sequence::preceded(multispace0, alt((tag("/>"), tag(">"))))(m);
+            if let Ok((rest, _)) = end_of_tag {
+                if !uri_attributes.is_empty() {
+                    return Ok((rest, (&i[..i.len() - j.len()], name, uri_attributes)));
+                }
+                k = rest;
+                continue;
+            }
+        }
+        k = &j[1..];
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        i,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Parses an XML start tag `<name attribute="value" ...>` or an empty
+/// element tag `<name attribute="value" .../>`.
+///
+/// It returns either `Ok((i, (name, attributes, is_empty_element)))` or some
+/// error. Attribute values can be enclosed in double or single quotes.
+/// Entities in attribute values are decoded. Unlike HTML, XML is case
+/// sensitive: names are returned as they are.
+/// ```
+/// use parse_hyperlinks::parser::xml::xml_start_tag;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(
+///   xml_start_tag(r#"<link xlink:href="https://example.org/?a=1&amp;b=2"/>abc"#),
+///   Ok(("abc", ("link", vec![("xlink:href", Cow::from("https://example.org/?a=1&b=2"))], true)))
+/// );
+/// ```
+pub fn xml_start_tag(i: &str) -> nom::IResult<&str, (&str, Vec<(&str, Cow<str>)>, bool)> {
+    nom::sequence::tuple((
+        nom::sequence::preceded(char('<'), xml_name),
+        nom::multi::many0(nom::sequence::preceded(multispace1, xml_attribute)),
+        nom::sequence::preceded(
+            multispace0,
+            alt((
+                nom::combinator::value(true, tag("/>")),
+                nom::combinator::value(false, tag(">")),
+            )),
+        ),
+    ))(i)
+}
+
+/// Parses the content of the element `name` and its end tag `</name>`.
+///
+/// The parser expects to start right after the start tag. It returns either
+/// `Ok((i, content))` or some error. Nested elements with the same name are
+/// part of the content.
+/// ```
+/// use parse_hyperlinks::parser::xml::xml_element_content;
+///
+/// assert_eq!(
+///   xml_element_content("a<b>b</b><b/>c</b >abc", "b"),
+///   Ok(("abc", "a<b>b</b><b/>c"))
+/// );
+/// ```
+pub fn xml_element_content<'a>(i: &'a str, name: &str) -> nom::IResult<&'a str, &'a str> {
+    let mut depth = 0;
+    let mut k = i;
+    while let Some(idx) = k.find('<') {
+        let j = &k[idx..];
+        let content_len = i.len() - j.len();
+        if let Ok((rest, _)) = nom::sequence::delimited(
+            tag::<_, _, nom::error::Error<&str>>("</"),
+            nom::combinator::verify(xml_name, |n: &str| n == name),
+            nom::sequence::pair(multispace0, char('>')),
+        )(j)
+        {
+            if depth == 0 {
+                return Ok((rest, &i[..content_len]));
+            }
+            depth -= 1;
+            k = rest;
+            continue;
+        }
+        if let Ok((rest, (n, _, is_empty))) = xml_start_tag(j) {
+            if n == name && !is_empty {
+                depth += 1;
+            }
+            k = rest;
+            continue;
+        }
+        k = &j[1..];
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        i,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// Converts the content of an element into plain text: tags and comments
+/// are removed, entities decoded and whitespace sequences replaced by one
+/// space. The content of CDATA sections is taken verbatim. Leading and
+/// trailing whitespace is removed.
+pub fn xml_text(content: &str) -> Cow<str> {
+    let content = content.trim();
+    if !content.contains(['<', '&', '\n', '\r', '\t', ' ']) {
+        return Cow::Borrowed(content);
+    }
+
+    let mut text = String::with_capacity(content.len());
+    let mut k = content;
+    while let Some(idx) = k.find('<') {
+        text.push_str(&decode_html_entities(&k[..idx]));
+        let j = &k[idx..];
+        k = if let Some(comment) = j.strip_prefix("<!--") {
+            comment.find("-->").map_or("", |end| &comment[end + 3..])
+        } else if let Some(cdata) = j.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            text.push_str(&cdata[..end]);
+            cdata.get(end + 3..).unwrap_or_default()
+        } else {
+            j.find('>').map_or("", |end| &j[end + 1..])
+        };
+    }
+    text.push_str(&decode_html_entities(k));
+
+    Cow::Owned(text.split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+/// Parses an XML name. Besides letters and digits, `_`, `:`, `-` and `.`
+/// are allowed. The first character must not be a digit, `-` or `.`.
+fn xml_name(i: &str) -> nom::IResult<&str, &str> {
+    nom::combinator::recognize(nom::sequence::pair(
+        nom::character::complete::satisfy(|c| c.is_alphabetic() || c == '_' || c == ':'),
+        nom::bytes::complete::take_while(|c: char| c.is_alphanumeric() || "_:-.".contains(c)),
+    ))(i)
+}
+
+/// Parses an attribute `name="value"` or `name='value'` and returns
+/// `(name, value)`. Whitespace around `=` is allowed.
+fn xml_attribute(i: &str) -> nom::IResult<&str, (&str, Cow<str>)> {
+    nom::sequence::separated_pair(
+        xml_name,
+        nom::sequence::tuple((multispace0, char('='), multispace0)),
+        nom::combinator::map(
+            alt((
+                nom::sequence::delimited(
+                    char('"'),
+                    nom::bytes::complete::take_till(|c| c == '"' || c == '<'),
+                    char('"'),
+                ),
+                nom::sequence::delimited(
+                    char('\''),
+                    nom::bytes::complete::take_till(|c| c == '\'' || c == '<'),
+                    char('\''),
+                ),
+            )),
+            |s: &str| decode_html_entities(s),
+        ),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_xml_uri_attributes() {
+        let i = "<p class='x'>a < b<math href = 'dest' />abc";
+        let expected = (
+            "abc",
+            (
+                "<p class='x'>a < b",
+                "math",
+                vec![(24, 37, "href", Cow::from("dest"))],
+            ),
+        );
+        assert_eq!(take_xml_uri_attributes(i).unwrap(), expected);
+        assert_eq!(&i[24..37], "href = 'dest'");
+
+        assert_eq!(
+            take_xml_uri_attributes("<p class='x'>abc<!-- <a href='dest'/>"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "<p class='x'>abc<!-- <a href='dest'/>",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+
+    #[test]
+    fn test_xml_start_tag() {
+        let expected = (
+            "abc",
+            (
+                "ulink",
+                vec![
+                    ("url", Cow::from("https://example.org")),
+                    ("xml:lang", Cow::from("en")),
+                ],
+                false,
+            ),
+        );
+        assert_eq!(
+            xml_start_tag("<ulink\n  url = 'https://example.org'\txml:lang=\"en\" >abc").unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            xml_start_tag("<ulink url=https://example.org>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "url=https://example.org>abc",
+                ErrorKind::Tag
+            )))
+        );
+        assert_eq!(
+            xml_start_tag("< ulink>abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                " ulink>abc",
+                ErrorKind::Satisfy
+            )))
+        );
+    }
+
+    #[test]
+    fn test_xml_element_content() {
+        assert_eq!(
+            xml_element_content("a<link>b</link>c</link>abc", "link"),
+            Ok(("abc", "a<link>b</link>c"))
+        );
+        assert_eq!(
+            xml_element_content("a</linkend>c</link>abc", "link"),
+            Ok(("abc", "a</linkend>c"))
+        );
+        assert_eq!(
+            xml_element_content("a<link>b</link>abc", "link"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "a<link>b</link>abc",
+                ErrorKind::TakeUntil
+            )))
+        );
+    }
+
+    #[test]
+    fn test_xml_text() {
+        assert_eq!(xml_text(" name "), Cow::from("name"));
+        assert_eq!(
+            xml_text("The <emphasis>DocBook</emphasis>\n  <!-- a > b -->site &amp; more"),
+            Cow::from("The DocBook site & more")
+        );
+        assert_eq!(
+            xml_text("<![CDATA[https://example.org/?a=1&amp;b=2]]> &amp;"),
+            Cow::from("https://example.org/?a=1&amp;b=2 &")
+        );
+    }
+}
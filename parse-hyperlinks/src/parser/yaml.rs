@@ -0,0 +1,325 @@
+//! This module implements a line oriented scanner to extract URLs from YAML
+//! documents.
+#![allow(dead_code)]
+#![allow(clippy::type_complexity)]
+
+use crate::parser::json::json_looks_like_url;
+use crate::parser::Link;
+use std::borrow::Cow;
+
+/// Wrapper around `yaml_urls()` that packs the results in
+/// `Link::Text2Dest`.
+pub fn yaml_urls_links(i: &str) -> nom::IResult<&str, Vec<Link>> {
+    let (i, links) = yaml_urls(i)?;
+    Ok((
+        i,
+        links
+            .into_iter()
+            .map(|(te, de, ti)| Link::Text2Dest(te, de, ti))
+            .collect(),
+    ))
+}
+
+/// Scans a YAML document line by line and extracts all scalar values that
+/// look like a URL.
+///
+/// A scalar looks like a URL when it starts with a scheme followed by
+/// `://`, e.g. `https://`, or with `mailto:`. Keys are never extracted.
+///
+/// It returns `Ok(("", links))`, where every item of `links` is a tuple
+/// `(link_text, link_destination, Cow::from(""))`. The _link text_ is the
+/// key path of the scalar, written as
+/// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) like the _link
+/// text_ of `json::json_urls()`, e.g. `/jobs/build/steps/0/uses`. The links
+/// appear in document order. The whole input is consumed.
+///
+/// The scanner understands block mappings and block sequences, also when
+/// they are nested as in `- key: value`, plain, single quoted and double
+/// quoted scalars and comments. Document markers `---` reset the key path,
+/// which makes it suitable for front matter and multi document streams.
+/// The content of block scalars (`|` and `>`) and of flow collections
+/// (`[...]` and `{...}`) is skipped, as are multi line plain or quoted
+/// scalars, anchors, aliases and tags.
+/// ```
+/// use parse_hyperlinks::parser::Link;
+/// use parse_hyperlinks::parser::yaml::yaml_urls;
+/// use std::borrow::Cow;
+///
+/// let i = r#"image:
+///   repository: "https://registry.example.org/app"  # the image
+/// links:
+///   - name: home
+///     url: https://example.org/
+///   - mailto:info@example.org
+/// "#;
+///
+/// assert_eq!(
+///   yaml_urls(i),
+///   Ok(("", vec![
+///       (Cow::from("/image/repository"), Cow::from("https://registry.example.org/app"), Cow::from("")),
+///       (Cow::from("/links/0/url"), Cow::from("https://example.org/"), Cow::from("")),
+///       (Cow::from("/links/1"), Cow::from("mailto:info@example.org"), Cow::from("")),
+///   ]))
+/// );
+/// ```
+pub fn yaml_urls(i: &str) -> nom::IResult<&str, Vec<(Cow<str>, Cow<str>, Cow<str>)>> {
+    let mut links = Vec::new();
+    // Every item is `(indent, is_sequence_item, path_segment)`.
+    let mut stack: Vec<(usize, bool, String)> = Vec::new();
+    // The indent of the line introducing a block scalar.
+    let mut block_scalar: Option<usize> = None;
+
+    for line in i.lines() {
+        let content = line.trim_start_matches(' ');
+        let mut indent = line.len() - content.len();
+        let mut content = content.trim_end();
+
+        if let Some(block_indent) = block_scalar {
+            if content.is_empty() || indent > block_indent {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if content == "---" || content.starts_with("--- ") || content == "..." {
+            stack.clear();
+            continue;
+        }
+        if content.is_empty() || content.starts_with('#') || content.starts_with('%') {
+            continue;
+        }
+
+        loop {
+            // Sequence item.
+            if content == "-" || content.starts_with("- ") {
+                let mut index = 0;
+                while let Some((ind, is_item, segment)) = stack.last() {
+                    if *ind < indent || (*ind == indent && !is_item) {
+                        break;
+                    }
+                    if *ind == indent {
+                        index = segment.parse::<usize>().map_or(0, |n| n + 1);
+                    }
+                    stack.pop();
+                }
+                stack.push((indent, true, index.to_string()));
+                let item = content[1..].trim_start_matches(' ');
+                indent += content.len() - item.len();
+                content = item;
+                if content.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            // Mapping entry or scalar.
+            let (key, value) = match yaml_split_key(content) {
+                Some((key, value)) => {
+                    while stack.last().is_some_and(|(ind, _, _)| *ind >= indent) {
+                        stack.pop();
+                    }
+                    (Some(key), value)
+                }
+                None => (None, content),
+            };
+            let value = value.trim_start();
+            if value.is_empty() || value.starts_with('#') {
+                if let Some(key) = key {
+                    stack.push((indent, false, key));
+                }
+            } else if value.starts_with(['|', '>']) {
+                block_scalar = Some(indent);
+            } else if let Some(value) = yaml_scalar(value) {
+                if json_looks_like_url(&value) {
+                    let mut pointer = String::new();
+                    for segment in stack
+                        .iter()
+                        .map(|(_, _, s)| s.as_str())
+                        .chain(key.as_deref())
+                    {
+                        pointer.push('/');
+                        pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+                    }
+                    links.push((Cow::Owned(pointer), value, Cow::Borrowed("")));
+                }
+            }
+            break;
+        }
+    }
+
+    Ok(("", links))
+}
+
+/// Splits a mapping entry `key: value` into the unquoted key and the rest of
+/// the line. Returns `None` if the line is not a mapping entry.
+fn yaml_split_key(content: &str) -> Option<(String, &str)> {
+    let (key, rest) = if content.starts_with(['"', '\'']) {
+        let end = yaml_quoted_end(content)?;
+        let (key, rest) = content.split_at(end);
+        (yaml_scalar(key)?.into_owned(), rest.trim_start_matches(' '))
+    } else {
+        let idx = content
+            .match_indices(':')
+            .map(|(idx, _)| idx)
+            .find(|&idx| content[idx + 1..].is_empty() || content[idx + 1..].starts_with(' '))?;
+        let key = &content[..idx];
+        if key.contains(" #") || key.starts_with(['[', '{', '&', '*', '!']) {
+            return None;
+        }
+        (key.trim_end().to_string(), &content[idx..])
+    };
+    let value = rest.strip_prefix(':')?;
+    if !value.is_empty() && !value.starts_with(' ') {
+        return None;
+    }
+    Some((key, value))
+}
+
+/// Converts a single line scalar into its value. Quotes and trailing
+/// comments are removed, escape sequences in quoted scalars are resolved.
+/// Returns `None` for flow collections, aliases, unterminated quoted
+/// scalars and scalars starting with a tag or anchor.
+fn yaml_scalar(value: &str) -> Option<Cow<str>> {
+    match value.chars().next()? {
+        '\'' => {
+            let end = yaml_quoted_end(value)?;
+            let inner = &value[1..end - 1];
+            Some(if inner.contains("''") {
+                Cow::Owned(inner.replace("''", "'"))
+            } else {
+                Cow::Borrowed(inner)
+            })
+        }
+        '"' => {
+            let end = yaml_quoted_end(value)?;
+            let inner = &value[1..end - 1];
+            if !inner.contains('\\') {
+                return Some(Cow::Borrowed(inner));
+            }
+            let mut s = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    s.push(c);
+                    continue;
+                }
+                match chars.next()? {
+                    'n' => s.push('\n'),
+                    't' => s.push('\t'),
+                    '/' => s.push('/'),
+                    e @ ('x' | 'u' | 'U') => {
+                        let len = match e {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let hex: String = chars.by_ref().take(len).collect();
+                        s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => s.push(c),
+                }
+            }
+            Some(Cow::Owned(s))
+        }
+        '[' | '{' | '*' | '&' | '!' => None,
+        _ => {
+            let value = match value.find(" #") {
+                Some(idx) => &value[..idx],
+                None => value,
+            };
+            Some(Cow::Borrowed(value.trim_end()))
+        }
+    }
+}
+
+/// Returns the byte index after the closing quote of a quoted scalar at the
+/// beginning of `i`.
+fn yaml_quoted_end(i: &str) -> Option<usize> {
+    let quote = i.chars().next()?;
+    let mut chars = i.char_indices().skip(1);
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            c if c == quote => {
+                // `''` is an escaped single quote.
+                if quote == '\'' && i[idx + 1..].starts_with('\'') {
+                    chars.next();
+                    continue;
+                }
+                return Some(idx + 1);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_urls() {
+        let i = "\
+---
+title: 'Rock ''n'' Roll'
+homepage: https://example.org/a#b # comment
+script: |
+  url: https://example.org/ignored
+steps:
+- uses: actions/checkout@v4
+- with:
+    \"repo/url\": \"https://example.org/\\u00e9\"
+  mirror: ftp://example.org/
+- - https://example.org/nested
+tags: [https://example.org/flow]
+...
+---
+next: http://example.org/
+";
+        let expected = (
+            "",
+            vec![
+                (
+                    Cow::from("/homepage"),
+                    Cow::from("https://example.org/a#b"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("/steps/1/with/repo~1url"),
+                    Cow::from("https://example.org/\u{e9}"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("/steps/1/mirror"),
+                    Cow::from("ftp://example.org/"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("/steps/2/0"),
+                    Cow::from("https://example.org/nested"),
+                    Cow::from(""),
+                ),
+                (
+                    Cow::from("/next"),
+                    Cow::from("http://example.org/"),
+                    Cow::from(""),
+                ),
+            ],
+        );
+        assert_eq!(yaml_urls(i).unwrap(), expected);
+
+        let i = "title: 日本語テキスト\nnote: \"日本語テキスト\"\n";
+        assert_eq!(yaml_urls(i).unwrap(), ("", vec![]));
+    }
+
+    #[test]
+    fn test_yaml_scalar() {
+        assert_eq!(yaml_scalar("'it''s' # x"), Some(Cow::from("it's")));
+        assert_eq!(yaml_scalar("\"a\\\"b\""), Some(Cow::from("a\"b")));
+        assert_eq!(yaml_scalar("plain#text # x"), Some(Cow::from("plain#text")));
+        assert_eq!(yaml_scalar("\"unterminated"), None);
+        assert_eq!(yaml_scalar("*alias"), None);
+    }
+}
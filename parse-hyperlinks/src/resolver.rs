@@ -0,0 +1,672 @@
+//! Module resolving link labels to their _link destination_. A label is
+//! defined either directly by a _link reference definition_
+//! (`Link::Label2Dest`) or indirectly by an alias (`Link::Label2Label`),
+//! e.g. a reStructuredText _indirect hyperlink target_ `.. _one: two_`.
+//! Aliases can form chains, which the resolver follows to the final
+//! destination. reStructuredText _anonymous hyperlink references_ are
+//! matched with the _anonymous hyperlink targets_ by position instead, as
+//! docutils does. In a second pass, `Resolver::hyperlinks()` resolves all
+//! _reference links_ of the document. `Resolver::broken_references()` reports
+//! the references, that can not be resolved, and the unused definitions,
+//! `Resolver::duplicate_labels()` the labels defined more than once.
+
+use crate::parser::asciidoc::adoc_xref_destination;
+use crate::parser::parse::insert_definition;
+use crate::parser::parse::normalize_label;
+use crate::parser::parse::take_link;
+use crate::parser::parse::take_link_kind;
+use crate::parser::parse::ParserConfig;
+use crate::parser::Link;
+use crate::parser::ParsedLink;
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Error returned by `Resolver::resolve()` and `Resolver::resolve_anonymous()`.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ResolveError<'a> {
+    /// Neither a definition nor an alias exists for this label.
+    #[error("undefined link label `{0}`")]
+    Undefined(Cow<'a, str>),
+    /// The alias chain comes back to a label visited before. The vector
+    /// lists the labels of the cycle, starting and ending with the same
+    /// label.
+    #[error("cyclic link label aliases: `{}`", .0.join("` -> `"))]
+    Cycle(Vec<Cow<'a, str>>),
+    /// There are less anonymous targets than anonymous references. The
+    /// number is the position of the anonymous reference, counting from 1.
+    #[error("no anonymous target for anonymous reference number {0}")]
+    Anonymous(usize),
+}
+
+/// Result of `Resolver::broken_references()`. The positions are byte
+/// offsets in the input of `Resolver::new()`, see
+/// `iterator::line_column()`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BrokenReferences<'a> {
+    /// Position and error of every _reference link_, that can not be
+    /// resolved, in order of appearance.
+    pub unresolved: Vec<(usize, ResolveError<'a>)>,
+    /// Position and label of every definition or alias, that no _reference
+    /// link_ uses, directly or through aliases, in order of appearance.
+    /// Unused anonymous targets have the label `_`. An embedded URI, e.g.
+    /// `` `text <dest>`_ ``, uses its own definition.
+    pub unused: Vec<(usize, Cow<'a, str>)>,
+}
+
+impl BrokenReferences<'_> {
+    /// True, when all references are resolved and all definitions are used.
+    pub fn is_empty(&self) -> bool {
+        self.unresolved.is_empty() && self.unused.is_empty()
+    }
+}
+
+/// A label defined more than once, see `Resolver::duplicate_labels()`. The
+/// positions are byte offsets in the input of `Resolver::new()`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DuplicateLabel<'a> {
+    /// The label as written in the repeated definition.
+    pub label: Cow<'a, str>,
+    /// Position of the first definition, which is the one `Resolver` uses.
+    pub first: usize,
+    /// Position of the repeated definition.
+    pub duplicate: usize,
+}
+
+#[derive(Debug, PartialEq)]
+/// The right hand side of a definition or an alias.
+enum Target<'a> {
+    /// `(link_destination, link_title)`, e.g. `__ https://example.org`.
+    Dest(Cow<'a, str>, Cow<'a, str>),
+    /// Link label of an indirect target, e.g. `__ other_`.
+    Label(Cow<'a, str>),
+}
+
+#[derive(Debug, PartialEq)]
+/// Collection of all named link reference definitions and aliases of a
+/// document.
+///
+/// ```
+/// use parse_hyperlinks::resolver::{ResolveError, Resolver};
+/// use std::borrow::Cow;
+///
+/// let i = r#"
+/// .. _one: two_
+/// .. _two: `three`_
+/// .. _three: https://example.org
+/// .. _ping: pong_
+/// .. _pong: ping_
+/// "#;
+///
+/// let resolver = Resolver::new(i);
+/// assert_eq!(
+///     resolver.resolve("one"),
+///     Ok((Cow::from("https://example.org"), Cow::from("")))
+/// );
+/// assert_eq!(
+///     resolver.resolve("ping"),
+///     Err(ResolveError::Cycle(vec![
+///         Cow::from("ping"),
+///         Cow::from("pong"),
+///         Cow::from("ping")
+///     ]))
+/// );
+/// assert_eq!(
+///     resolver.resolve("four"),
+///     Err(ResolveError::Undefined(Cow::from("four")))
+/// );
+/// ```
+pub struct Resolver<'a> {
+    /// The text the definitions were collected from.
+    input: &'a str,
+    /// The `HashMap`'s key is the alias label, the value the label it
+    /// refers to.
+    label2label: HashMap<Cow<'a, str>, Cow<'a, str>>,
+    /// The `HashMap`'s key is the `link_label`, the value its
+    /// `(link_destination, link_title)`.
+    label2dest: HashMap<Cow<'a, str>, (Cow<'a, str>, Cow<'a, str>)>,
+    /// The anonymous targets in order of appearance.
+    anonymous: Vec<Target<'a>>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Scans the whole `input` with `parser::parse::take_link()` and
+    /// collects all `Link::Label2Dest`, `Link::TextLabel2Dest` and
+    /// `Link::Label2Label`. When a label is defined more than once, the
+    /// first definition counts, where labels differing only in case or
+    /// whitespace are the same label. Anonymous definitions, with the label `_`,
+    /// are resolved by position, not by name, and are collected separately.
+    pub fn new(input: &'a str) -> Self {
+        let mut resolver = Self {
+            input,
+            label2label: HashMap::new(),
+            label2dest: HashMap::new(),
+            anonymous: Vec::new(),
+        };
+        let mut i = input;
+        while let Ok((j, (_, link))) = take_link(i) {
+            match definition(link) {
+                Some((label, target)) if label == "_" => resolver.anonymous.push(target),
+                Some((label, Target::Dest(dest, title))) => {
+                    insert_definition(&mut resolver.label2dest, label, (dest, title));
+                }
+                Some((alias, Target::Label(label))) => {
+                    insert_definition(&mut resolver.label2label, alias, normalize_label(label));
+                }
+                None => {}
+            }
+            i = j;
+        }
+        resolver
+    }
+
+    /// Follows the alias chain starting at `label` and returns the
+    /// `(link_destination, link_title)` of the definition at its end.
+    /// A direct definition takes precedence over an alias with the same
+    /// label. Labels are compared after `normalize_label()`, the labels in
+    /// a `ResolveError` are normalized too.
+    pub fn resolve(&self, label: &str) -> Result<(Cow<'a, str>, Cow<'a, str>), ResolveError<'a>> {
+        let mut chain: Vec<Cow<'a, str>> = Vec::new();
+        let mut label = Cow::Owned(normalize_label(Cow::Borrowed(label)).into_owned());
+        loop {
+            if let Some((dest, title)) = self.label2dest.get(&label) {
+                return Ok((dest.clone(), title.clone()));
+            }
+            let next = match self.label2label.get_key_value(&label) {
+                Some((alias, next)) => {
+                    chain.push(alias.clone());
+                    next.clone()
+                }
+                None => return Err(ResolveError::Undefined(label)),
+            };
+            if let Some(idx) = chain.iter().position(|l| *l == next) {
+                let mut cycle = chain.split_off(idx);
+                cycle.push(next);
+                return Err(ResolveError::Cycle(cycle));
+            }
+            label = next;
+        }
+    }
+
+    /// Returns the `(link_destination, link_title)` of the `n`-th anonymous
+    /// target, counting from 1, which is the destination of the `n`-th
+    /// anonymous reference. When the anonymous target is an indirect
+    /// target, its label is resolved with `Resolver::resolve()`.
+    ///
+    /// ```
+    /// use parse_hyperlinks::resolver::{ResolveError, Resolver};
+    /// use std::borrow::Cow;
+    ///
+    /// let i = r#"abc `one`__ abc `two`__ abc `three`__
+    /// .. __: https://example.org/1
+    /// __ two_
+    /// .. _two: https://example.org/2
+    /// "#;
+    ///
+    /// let resolver = Resolver::new(i);
+    /// assert_eq!(
+    ///     resolver.resolve_anonymous(1),
+    ///     Ok((Cow::from("https://example.org/1"), Cow::from("")))
+    /// );
+    /// assert_eq!(
+    ///     resolver.resolve_anonymous(2),
+    ///     Ok((Cow::from("https://example.org/2"), Cow::from("")))
+    /// );
+    /// assert_eq!(resolver.resolve_anonymous(3), Err(ResolveError::Anonymous(3)));
+    /// ```
+    pub fn resolve_anonymous(
+        &self,
+        n: usize,
+    ) -> Result<(Cow<'a, str>, Cow<'a, str>), ResolveError<'a>> {
+        match n.checked_sub(1).and_then(|idx| self.anonymous.get(idx)) {
+            Some(Target::Dest(dest, title)) => Ok((dest.clone(), title.clone())),
+            Some(Target::Label(label)) => self.resolve(label),
+            None => Err(ResolveError::Anonymous(n)),
+        }
+    }
+
+    /// Lists the _reference links_ of the input, that can not be resolved,
+    /// and the definitions, that are never used, e.g. to fail a
+    /// documentation build on dangling references.
+    ///
+    /// ```
+    /// use parse_hyperlinks::resolver::{ResolveError, Resolver};
+    /// use std::borrow::Cow;
+    ///
+    /// let i = r#"abc `text1`_ abc `text2`_ abc
+    /// .. _text1: alias_
+    /// .. _alias: https://example.org/1
+    /// .. _unused: https://example.org/3
+    /// "#;
+    ///
+    /// let report = Resolver::new(i).broken_references();
+    /// assert_eq!(report.unresolved, vec![(17, ResolveError::Undefined(Cow::from("text2")))]);
+    /// assert_eq!(report.unused, vec![(81, Cow::from("unused"))]);
+    /// ```
+    pub fn broken_references(&self) -> BrokenReferences<'a> {
+        let mut report = BrokenReferences::default();
+        let mut used = HashSet::new();
+        let mut definitions = Vec::new();
+        let mut anonymous_definitions = Vec::new();
+        let mut anonymous_counter = 0;
+
+        let mut i = self.input;
+        while let Ok((j, (skipped, link))) = take_link(i) {
+            let offset = self.input.len() - i.len() + skipped.len();
+            i = j;
+            match link {
+                Link::Text2Label(_, la) if la == "_" => {
+                    anonymous_counter += 1;
+                    if let Err(e) = self.resolve_anonymous(anonymous_counter) {
+                        report.unresolved.push((offset, e));
+                    }
+                    if let Some(Target::Label(la)) = self.anonymous.get(anonymous_counter - 1) {
+                        self.mark_used(la, &mut used);
+                    }
+                }
+                Link::Text2Label(_, la) => {
+                    if let Err(e) = self.resolve(&la) {
+                        report.unresolved.push((offset, e));
+                    }
+                    self.mark_used(&la, &mut used);
+                }
+                link => {
+                    // An embedded URI is used where it is defined.
+                    if let Link::TextLabel2Dest(la, _, _) = &link {
+                        self.mark_used(la, &mut used);
+                    }
+                    match definition(link) {
+                        Some((la, _)) if la == "_" => anonymous_definitions.push(offset),
+                        Some((la, _)) => definitions.push((offset, la)),
+                        None => {}
+                    }
+                }
+            }
+        }
+
+        for (offset, la) in definitions {
+            if !used.contains(normalize_label(la.clone()).as_ref()) {
+                report.unused.push((offset, la));
+            }
+        }
+        for offset in anonymous_definitions.into_iter().skip(anonymous_counter) {
+            report.unused.push((offset, Cow::Borrowed("_")));
+        }
+        report.unused.sort_by_key(|(offset, _)| *offset);
+        report
+    }
+
+    /// Lists every definition or alias, whose label was defined before,
+    /// which docutils treats as an error. Labels differing only in case or
+    /// whitespace are the same label, see `normalize_label()`. An embedded
+    /// URI, e.g. `` `text <dest>`_ ``, defines its label too. Anonymous
+    /// definitions are not concerned.
+    ///
+    /// ```
+    /// use parse_hyperlinks::resolver::{DuplicateLabel, Resolver};
+    /// use std::borrow::Cow;
+    ///
+    /// let i = r#"abc [text1][label1] abc
+    /// [label1]: dest1
+    /// [Label1]: dest2
+    /// "#;
+    ///
+    /// assert_eq!(
+    ///     Resolver::new(i).duplicate_labels(),
+    ///     vec![DuplicateLabel { label: Cow::from("Label1"), first: 24, duplicate: 40 }]
+    /// );
+    /// ```
+    pub fn duplicate_labels(&self) -> Vec<DuplicateLabel<'a>> {
+        let mut duplicates = Vec::new();
+        let mut first_definitions = HashMap::new();
+
+        let mut i = self.input;
+        while let Ok((j, (skipped, link))) = take_link(i) {
+            let offset = self.input.len() - i.len() + skipped.len();
+            i = j;
+            match definition(link) {
+                Some((la, _)) if la != "_" => {
+                    match first_definitions.entry(normalize_label(la.clone())) {
+                        Entry::Occupied(first) => duplicates.push(DuplicateLabel {
+                            label: la,
+                            first: *first.get(),
+                            duplicate: offset,
+                        }),
+                        Entry::Vacant(first) => {
+                            first.insert(offset);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        duplicates
+    }
+
+    /// Inserts the normalized `label` and all labels of its alias chain
+    /// into `used`.
+    fn mark_used(&self, label: &str, used: &mut HashSet<String>) {
+        let mut label = normalize_label(Cow::Borrowed(label)).into_owned();
+        while used.insert(label.clone()) && !self.label2dest.contains_key(label.as_str()) {
+            match self.label2label.get(label.as_str()) {
+                Some(next) => label = next.to_string(),
+                None => break,
+            }
+        }
+    }
+
+    /// Second pass over the input of `Resolver::new()`: returns every link
+    /// usage in order of appearance, with _reference links_ resolved to
+    /// their final destination with `Resolver::resolve()`.
+    ///
+    /// _Inline links_ are returned as they are. The `kind` of the resulting
+    /// `ParsedLink` is the one `parser::parse::take_link_kind()` returns, e.g.
+    /// `LinkKind::Reference` for resolved _reference links_. Its `source`
+    /// is the markup of the usage. The `n`-th anonymous reference is
+    /// resolved with `Resolver::resolve_anonymous(n)`. A _reference
+    /// link_ without _link text_ gets its _link destination_ as _link text_.
+    /// Link reference definitions and aliases are not returned. A
+    /// reference, that can not be resolved, is returned as error.
+    ///
+    /// ```
+    /// use parse_hyperlinks::parser::LinkKind;
+    /// use parse_hyperlinks::resolver::{ResolveError, Resolver};
+    /// use std::borrow::Cow;
+    ///
+    /// let i = r#"abc [text1][label1] abc [text2](dest2) abc [text3][label3]
+    /// [label1]: dest1 "title1"
+    /// "#;
+    ///
+    /// let resolver = Resolver::new(i);
+    /// let mut links = resolver.hyperlinks();
+    /// let link = links.next().unwrap().unwrap();
+    /// assert_eq!(link.name, "text1");
+    /// assert_eq!(link.destination, "dest1");
+    /// assert_eq!(link.title, "title1");
+    /// assert_eq!(link.kind, LinkKind::Reference);
+    /// assert_eq!(link.source, "[text1][label1]");
+    /// let link = links.next().unwrap().unwrap();
+    /// assert_eq!(link.destination, "dest2");
+    /// assert_eq!(link.source, "[text2](dest2)");
+    /// assert_eq!(links.next(), Some(Err(ResolveError::Undefined(Cow::from("label3")))));
+    /// assert_eq!(links.next(), None);
+    /// ```
+    pub fn hyperlinks(
+        &self,
+    ) -> impl Iterator<Item = Result<ParsedLink<'a>, ResolveError<'a>>> + '_ {
+        let mut i = self.input;
+        let mut anonymous_counter = 0;
+        let config = ParserConfig::new();
+        std::iter::from_fn(move || {
+            while let Ok((j, (skipped, link, kind))) = take_link_kind(i, &config) {
+                let source = &i[skipped.len()..i.len() - j.len()];
+                i = j;
+                let res = match link {
+                    Link::Text2Dest(te, de, ti) | Link::TextLabel2Dest(te, de, ti) => {
+                        Ok(ParsedLink::from((te, de, ti)))
+                    }
+                    Link::Text2Label(te, la) => {
+                        let res = if la == "_" {
+                            anonymous_counter += 1;
+                            self.resolve_anonymous(anonymous_counter)
+                        } else {
+                            self.resolve(&la)
+                        };
+                        res.map(|(de, ti)| {
+                            let te = if te.is_empty() { de.clone() } else { te };
+                            ParsedLink::from((te, de, ti))
+                        })
+                    }
+                    Link::Text2Xref(te, xr) => {
+                        let te = if te.is_empty() { xr.clone() } else { te };
+                        let de = adoc_xref_destination(xr);
+                        Ok(ParsedLink::from((te, de, Cow::Borrowed(""))))
+                    }
+                    _ => continue,
+                };
+                return Some(res.map(|mut link| {
+                    link.kind = kind;
+                    link.source = Cow::Borrowed(source);
+                    link
+                }));
+            }
+            None
+        })
+    }
+}
+
+/// Splits a definition or an alias into its label and its `Target`. This
+/// decides which links `Resolver::new()` records, and which links
+/// `Resolver::broken_references()` and `Resolver::duplicate_labels()`
+/// consider definitions. Returns `None` for all other links.
+fn definition(link: Link<'_>) -> Option<(Cow<'_, str>, Target<'_>)> {
+    match link {
+        Link::Label2Dest(label, dest, title) | Link::TextLabel2Dest(label, dest, title) => {
+            Some((label, Target::Dest(dest, title)))
+        }
+        Link::Label2Label(alias, label) => Some((alias, Target::Label(label))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::LinkKind;
+
+    #[test]
+    fn test_resolver() {
+        let i = r#"abc
+[md]: https://md.example.org "Title"
+.. _rst: md_
+.. _self: self_
+.. _first: first.html
+.. _first: second.html
+.. _a: b_
+.. _b: c_
+.. _c: b_
+.. _`with space`: first_
+.. _UPPER: `MD`_
+"#;
+        let resolver = Resolver::new(i);
+        assert_eq!(
+            resolver.resolve("rst"),
+            Ok((Cow::from("https://md.example.org"), Cow::from("Title")))
+        );
+        assert_eq!(
+            resolver.resolve("with space"),
+            Ok((Cow::from("first.html"), Cow::from("")))
+        );
+        assert_eq!(
+            resolver.resolve("self"),
+            Err(ResolveError::Cycle(vec![
+                Cow::from("self"),
+                Cow::from("self")
+            ]))
+        );
+        assert_eq!(
+            resolver.resolve("a"),
+            Err(ResolveError::Cycle(vec![
+                Cow::from("b"),
+                Cow::from("c"),
+                Cow::from("b")
+            ]))
+        );
+        assert_eq!(
+            resolver.resolve("a").unwrap_err().to_string(),
+            "cyclic link label aliases: `b` -> `c` -> `b`"
+        );
+        assert_eq!(
+            resolver.resolve(" With\n  Space"),
+            Ok((Cow::from("first.html"), Cow::from("")))
+        );
+        assert_eq!(
+            resolver.resolve("Upper"),
+            Ok((Cow::from("https://md.example.org"), Cow::from("Title")))
+        );
+        assert_eq!(
+            resolver.resolve("_"),
+            Err(ResolveError::Undefined(Cow::from("_")))
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks() {
+        let i = r#"abc `Text1`_ abc <<target2,text2>> abc {label3}[] abc
+.. _text1: alias_
+.. _alias: https://example.org/1
+:label3: https://example.org/3
+abc `text4`_ abc https://example.org/5[]
+"#;
+        let resolver = Resolver::new(i);
+        let res: Vec<_> = resolver
+            .hyperlinks()
+            .map(|l| l.map(|l| (l.name, l.destination, l.kind)))
+            .collect();
+        let expected = vec![
+            Ok((
+                Cow::from("Text1"),
+                Cow::from("https://example.org/1"),
+                LinkKind::Reference,
+            )),
+            Ok((Cow::from("text2"), Cow::from("#target2"), LinkKind::Xref)),
+            Ok((
+                Cow::from("https://example.org/3"),
+                Cow::from("https://example.org/3"),
+                LinkKind::Reference,
+            )),
+            Err(ResolveError::Undefined(Cow::from("text4"))),
+            Ok((
+                Cow::from("https://example.org/5"),
+                Cow::from("https://example.org/5"),
+                LinkKind::Autolink,
+            )),
+        ];
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_hyperlinks_duplicate() {
+        // `Resolver` and `iterator::Hyperlink` agree on the first definition.
+        let i = "abc [x] abc\n\n[x]: first\n[X]: second\n";
+        let resolver = Resolver::new(i);
+        assert_eq!(
+            resolver.resolve("x"),
+            Ok((Cow::from("first"), Cow::from("")))
+        );
+        let res: Vec<_> = resolver
+            .hyperlinks()
+            .map(|l| l.unwrap().destination)
+            .collect();
+        assert_eq!(res, vec![Cow::from("first")]);
+        let res: Vec<_> = crate::iterator::Hyperlink::new(i, false)
+            .map(|(_, (_, de, _))| de)
+            .collect();
+        assert_eq!(res, vec![Cow::from("first")]);
+    }
+
+    #[test]
+    fn test_hyperlinks_anonymous() {
+        let i = r#"abc `one`__ abc `two`_ abc `three`__ abc `four`__
+.. __: https://example.org/1
+.. _two: https://example.org/2
+__ two_
+"#;
+        let resolver = Resolver::new(i);
+        let res: Vec<_> = resolver
+            .hyperlinks()
+            .map(|l| l.map(|l| (l.name, l.destination)))
+            .collect();
+        let expected = vec![
+            Ok((Cow::from("one"), Cow::from("https://example.org/1"))),
+            Ok((Cow::from("two"), Cow::from("https://example.org/2"))),
+            Ok((Cow::from("three"), Cow::from("https://example.org/2"))),
+            Err(ResolveError::Anonymous(3)),
+        ];
+        assert_eq!(res, expected);
+        assert_eq!(
+            resolver.resolve("_"),
+            Err(ResolveError::Undefined(Cow::from("_")))
+        );
+    }
+
+    #[test]
+    fn test_broken_references() {
+        let i = r#"abc `One`_ abc `two`__ abc `three`__ abc `ping`_
+.. _one: alias_
+.. _alias: https://example.org/1
+.. _dup: https://example.org/2
+.. __: target_
+.. _target: https://example.org/3
+.. __: https://example.org/4
+.. _ping: pong_
+.. _pong: ping_
+"#;
+        let report = Resolver::new(i).broken_references();
+        assert_eq!(
+            report.unresolved,
+            vec![(
+                41,
+                ResolveError::Cycle(vec![
+                    Cow::from("ping"),
+                    Cow::from("pong"),
+                    Cow::from("ping")
+                ])
+            )]
+        );
+        assert_eq!(report.unused, vec![(98, Cow::from("dup"))]);
+        assert!(!report.is_empty());
+
+        let i = "abc `one`__ abc\n.. __: https://example.org/1\n.. __: https://example.org/2\n";
+        let report = Resolver::new(i).broken_references();
+        assert!(report.unresolved.is_empty());
+        assert_eq!(report.unused, vec![(45, Cow::from("_"))]);
+
+        assert!(Resolver::new("abc").broken_references().is_empty());
+
+        let i = "abc `x <a>`_ abc `x`_ abc `y`_\n.. _y: b\n.. _z: x_\n";
+        let report = Resolver::new(i).broken_references();
+        assert!(report.unresolved.is_empty());
+        assert_eq!(report.unused, vec![(40, Cow::from("z"))]);
+    }
+
+    #[test]
+    fn test_duplicate_labels() {
+        let i = r#"abc `one`_ abc
+.. _one: https://example.org/1
+.. __: https://example.org/2
+.. __: https://example.org/3
+.. _One: two_
+[one]: https://example.org/4
+.. _two: https://example.org/5
+"#;
+        let expected = vec![
+            DuplicateLabel {
+                label: Cow::from("One"),
+                first: 15,
+                duplicate: 104,
+            },
+            DuplicateLabel {
+                label: Cow::from("one"),
+                first: 15,
+                duplicate: 118,
+            },
+        ];
+        assert_eq!(Resolver::new(i).duplicate_labels(), expected);
+        assert!(Resolver::new("abc").duplicate_labels().is_empty());
+
+        let i = "abc `x <a>`_ abc\n.. _x: b\n";
+        let resolver = Resolver::new(i);
+        assert_eq!(
+            resolver.duplicate_labels(),
+            vec![DuplicateLabel {
+                label: Cow::from("x"),
+                first: 4,
+                duplicate: 17,
+            }]
+        );
+        assert_eq!(resolver.resolve("x"), Ok((Cow::from("a"), Cow::from(""))));
+    }
+}